@@ -0,0 +1,233 @@
+//! Modo em segundo plano com ícone na bandeja.
+//!
+//! Com `backgroundMode` ligado, fechar a janela só a esconde: o
+//! `AchievementMonitor` e as notificações de desbloqueio continuam rodando e
+//! a bandeja oferece abrir o app, pausar o monitoramento e sair. Com
+//! `launchAtLogin`, o app é registrado para iniciar com o sistema já na
+//! bandeja (argumento [`BACKGROUND_ARG`]).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri_plugin_autostart::ManagerExt;
+
+/// Chave das configurações que mantém o app na bandeja ao fechar a janela.
+pub const BACKGROUND_MODE_KEY: &str = "backgroundMode";
+/// Chave das configurações que registra o app para iniciar com o sistema.
+pub const LAUNCH_AT_LOGIN_KEY: &str = "launchAtLogin";
+/// Argumento passado pelo início automático para abrir sem a janela.
+pub const BACKGROUND_ARG: &str = "--background";
+
+const TRAY_ID: &str = "main";
+const MENU_OPEN: &str = "open";
+const MENU_PAUSE: &str = "pause";
+const MENU_QUIT: &str = "quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundSettings {
+    pub background_mode: bool,
+    pub launch_at_login: bool,
+}
+
+impl BackgroundSettings {
+    pub fn from_settings(settings: &Value) -> Self {
+        let flag = |key: &str| settings.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        Self {
+            background_mode: flag(BACKGROUND_MODE_KEY),
+            launch_at_login: flag(LAUNCH_AT_LOGIN_KEY),
+        }
+    }
+
+    pub fn load(app_handle: &AppHandle) -> Self {
+        Self::from_settings(&crate::utils::settings::load_settings_or_default(
+            app_handle,
+        ))
+    }
+}
+
+/// Se o processo foi aberto pelo início automático.
+pub fn started_in_background<I: IntoIterator<Item = String>>(args: I) -> bool {
+    args.into_iter().any(|arg| arg == BACKGROUND_ARG)
+}
+
+/// Item de pausa do menu da bandeja, para atualizar o texto.
+pub struct TrayState {
+    pause_item: MenuItem<Wry>,
+}
+
+/// Cria o ícone da bandeja, visível só no modo em segundo plano.
+pub fn setup_tray(app_handle: &AppHandle, settings: BackgroundSettings) -> tauri::Result<()> {
+    let open_item = MenuItem::with_id(
+        app_handle,
+        MENU_OPEN,
+        "Open Project HAM",
+        true,
+        None::<&str>,
+    )?;
+    let pause_item = MenuItem::with_id(
+        app_handle,
+        MENU_PAUSE,
+        pause_label(false),
+        true,
+        None::<&str>,
+    )?;
+    let quit_item = MenuItem::with_id(app_handle, MENU_QUIT, "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(
+        app_handle,
+        &[
+            &open_item,
+            &pause_item,
+            &PredefinedMenuItem::separator(app_handle)?,
+            &quit_item,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Project HAM")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app_handle, event| match event.id.as_ref() {
+            MENU_OPEN => show_main_window(app_handle),
+            MENU_PAUSE => {
+                let paused = is_monitoring_paused(app_handle);
+                if let Err(e) = set_monitoring_paused(app_handle, !paused) {
+                    log::error!("Failed to toggle monitoring from tray: {}", e);
+                }
+            }
+            MENU_QUIT => {
+                log::info!("Quit requested from tray");
+                app_handle.exit(0);
+            }
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app_handle.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    let tray = builder.build(app_handle)?;
+    tray.set_visible(settings.background_mode)?;
+    app_handle.manage(TrayState { pause_item });
+    Ok(())
+}
+
+/// Aplica as configurações: visibilidade da bandeja e registro no início
+/// automático do sistema.
+pub fn apply_settings(app_handle: &AppHandle, settings: BackgroundSettings) -> Result<(), String> {
+    if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+        tray.set_visible(settings.background_mode)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let autolaunch = app_handle.autolaunch();
+    let registered = autolaunch.is_enabled().unwrap_or(false);
+    if settings.launch_at_login != registered {
+        let result = if settings.launch_at_login {
+            autolaunch.enable()
+        } else {
+            autolaunch.disable()
+        };
+        result.map_err(|e| format!("Failed to update launch at login: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Se fechar a janela deve só escondê-la.
+pub fn keeps_running_on_close(app_handle: &AppHandle) -> bool {
+    BackgroundSettings::load(app_handle).background_mode && app_handle.tray_by_id(TRAY_ID).is_some()
+}
+
+pub fn show_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+pub fn is_monitoring_paused(app_handle: &AppHandle) -> bool {
+    app_handle
+        .try_state::<crate::AppState>()
+        .and_then(|state| {
+            state
+                .monitor
+                .lock()
+                .ok()
+                .and_then(|monitor| monitor.as_ref().map(|m| m.is_paused()))
+        })
+        .unwrap_or(false)
+}
+
+/// Pausa ou retoma o `AchievementMonitor`, atualiza a bandeja e emite
+/// `monitoring-paused`.
+pub fn set_monitoring_paused(app_handle: &AppHandle, paused: bool) -> Result<(), String> {
+    let state = app_handle
+        .try_state::<crate::AppState>()
+        .ok_or("App state not initialized")?;
+    {
+        let mut monitor_lock = state.monitor.lock().map_err(|e| e.to_string())?;
+        let monitor = monitor_lock.as_mut().ok_or("Monitor not initialized")?;
+        monitor.set_paused(paused).map_err(|e| e.to_string())?;
+    }
+    log::info!(
+        "Achievement monitoring {}",
+        if paused { "paused" } else { "resumed" }
+    );
+
+    if let Some(tray) = app_handle.try_state::<TrayState>() {
+        let _ = tray.pause_item.set_text(pause_label(paused));
+    }
+    if let Err(e) = app_handle.emit("monitoring-paused", paused) {
+        log::error!("Failed to emit monitoring pause: {}", e);
+    }
+    Ok(())
+}
+
+fn pause_label(paused: bool) -> &'static str {
+    if paused {
+        "Resume monitoring"
+    } else {
+        "Pause monitoring"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_background_settings_and_launch_argument() {
+        assert_eq!(
+            BackgroundSettings::from_settings(&serde_json::json!({})),
+            BackgroundSettings {
+                background_mode: false,
+                launch_at_login: false,
+            }
+        );
+        let settings = BackgroundSettings::from_settings(&serde_json::json!({
+            "backgroundMode": true,
+            "launchAtLogin": "yes",
+        }));
+        assert!(settings.background_mode);
+        assert!(!settings.launch_at_login);
+
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(started_in_background(args(&[
+            "project-ham",
+            "--background"
+        ])));
+        assert!(!started_in_background(args(&["project-ham"])));
+    }
+}
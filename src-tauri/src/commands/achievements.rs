@@ -1,19 +1,31 @@
+use super::backup::journaled;
 use super::language::{map_ui_language_to_hydra_lang, map_ui_language_to_steam_store_lang};
 use super::settings::{load_settings, save_settings};
+use crate::formats::detect::DetectionCandidate;
 use crate::integrations::hydra::HydraApi;
-use crate::integrations::steam::SteamWebApi;
-use crate::models::{DirectoryConfig, DirectoryDetectionPreset, UnlockOptions};
+use crate::integrations::steam::{LocalSteamState, SteamSchema, SteamWebApi};
+use crate::jobs::JobKind;
+use crate::models::{
+    ChangeReport, ConversionPreview, ConversionRequest, Cracker, DirectoryConfig,
+    DirectoryDetectionPreset, MirrorTargetResult, Timestamp, TimestampStrategy, UnlockOptions,
+    UnlockPreview,
+};
 use crate::parser::AchievementParser;
-use crate::unlocker::AchievementUnlocker;
+use crate::unlocker::convert::AchievementConverter;
+use crate::unlocker::journal::OperationKind;
+use crate::unlocker::{AchievementUnlocker, AchievementWriter};
+use crate::utils::cache::CachedAchievement;
 use crate::utils::{AchievementExporter, CacheManager};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::sync::Mutex;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_dialog::DialogExt;
 
+/// Espera máxima pelos metadados online quando há schema local do cliente Steam.
+const ONLINE_METADATA_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Obtém achievements de um jogo
 #[tauri::command]
 pub async fn get_game_achievements(
@@ -123,75 +135,19 @@ pub async fn get_game_achievements(
     let steam_language = map_ui_language_to_steam_store_lang(&language);
     let hydra_language = map_ui_language_to_hydra_lang(&language);
 
-    let mut achievements_json = if selected_api == "steam" && !steam_api_key.is_empty() {
-        let app_id: u32 = game_id
-            .parse()
-            .map_err(|e: std::num::ParseIntError| e.to_string())?;
-        let mut schema_achievements =
-            SteamWebApi::get_game_achievements(app_id, &steam_api_key, steam_language)
-                .await
-                .map_err(|e| e.to_string())?;
-
-        if !steam_id.is_empty() {
-            let player_achievements_result = match SteamWebApi::get_player_achievements(
-                app_id,
-                &steam_api_key,
-                &steam_id,
-            )
-            .await
-            {
-                Ok(player_achievements) => Ok(player_achievements),
-                Err(error) => {
-                    if SteamWebApi::is_player_achievements_forbidden(&error) {
-                        log::debug!(
-                                "Steam player achievements for app {} are not accessible through Steam Web API (profile/game details may be private)",
-                                app_id
-                            );
-                    } else {
-                        log::warn!(
-                            "Steam Web API achievements failed for app {} ({}).",
-                            app_id,
-                            error
-                        );
-                    }
-                    Err(error)
-                }
-            };
-
-            if let Ok(player_achievements) = player_achievements_result {
-                let player_map: HashMap<String, i32> = player_achievements
-                    .iter()
-                    .map(|a| (a.apiname.clone(), a.achieved))
-                    .collect();
-
-                let time_map: HashMap<String, i64> = player_achievements
-                    .iter()
-                    .map(|a| (a.apiname.clone(), a.unlocktime))
-                    .collect();
-
-                for ach in &mut schema_achievements {
-                    if let Some(achieved) = player_map.get(&ach.apiname) {
-                        ach.achieved = *achieved;
-                    }
-                    if let Some(unlocktime) = time_map.get(&ach.apiname) {
-                        ach.unlocktime = *unlocktime;
-                    }
-                }
-            }
-        }
-        serde_json::to_value(schema_achievements).map_err(|e| e.to_string())?
-    } else {
-        let result = HydraApi::get_game_achievements(&game_id, Some(hydra_language))
-            .await
-            .map_err(|e| e.to_string())?;
-
-        let mut final_achievements =
-            serde_json::to_value(result.achievements).map_err(|e| e.to_string())?;
-
-        if let Ok(app_id) = game_id.parse::<u32>() {
-            let mut steam_status_map: HashMap<String, (i32, i64)> = HashMap::new();
-
-            if !steam_api_key.is_empty() && !steam_id.is_empty() {
+    // Hydra/Web API primeiro (trazem raridade); sem rede, usa o schema em cache do cliente Steam
+    let offline = offline_steam_achievements(&game_id, steam_language);
+    let fetched = async {
+        let value = if selected_api == "steam" && !steam_api_key.is_empty() {
+            let app_id: u32 = game_id
+                .parse()
+                .map_err(|e: std::num::ParseIntError| e.to_string())?;
+            let mut schema_achievements =
+                SteamWebApi::get_game_achievements(app_id, &steam_api_key, steam_language)
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+            if !steam_id.is_empty() {
                 let player_achievements_result = match SteamWebApi::get_player_achievements(
                     app_id,
                     &steam_api_key,
@@ -203,12 +159,12 @@ pub async fn get_game_achievements(
                     Err(error) => {
                         if SteamWebApi::is_player_achievements_forbidden(&error) {
                             log::debug!(
-                                "Steam player achievement status for app {} is not accessible through Steam Web API (profile/game details may be private)",
-                                app_id
-                            );
+                                    "Steam player achievements for app {} are not accessible through Steam Web API (profile/game details may be private)",
+                                    app_id
+                                );
                         } else {
                             log::warn!(
-                                "Steam Web API status merge failed for app {} ({}).",
+                                "Steam Web API achievements failed for app {} ({}).",
                                 app_id,
                                 error
                             );
@@ -218,36 +174,120 @@ pub async fn get_game_achievements(
                 };
 
                 if let Ok(player_achievements) = player_achievements_result {
-                    for ach in player_achievements {
-                        steam_status_map.insert(ach.apiname, (ach.achieved, ach.unlocktime));
+                    let player_map: HashMap<String, i32> = player_achievements
+                        .iter()
+                        .map(|a| (a.apiname.clone(), a.achieved))
+                        .collect();
+
+                    let time_map: HashMap<String, i64> = player_achievements
+                        .iter()
+                        .map(|a| (a.apiname.clone(), a.unlocktime))
+                        .collect();
+
+                    for ach in &mut schema_achievements {
+                        if let Some(achieved) = player_map.get(&ach.apiname) {
+                            ach.achieved = *achieved;
+                        }
+                        if let Some(unlocktime) = time_map.get(&ach.apiname) {
+                            ach.unlocktime = *unlocktime;
+                        }
                     }
                 }
-            } else if !steam_id.is_empty() {
             }
+            serde_json::to_value(schema_achievements).map_err(|e| e.to_string())?
+        } else {
+            let result = HydraApi::get_game_achievements(&game_id, Some(hydra_language))
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let mut final_achievements =
+                serde_json::to_value(result.achievements).map_err(|e| e.to_string())?;
+
+            if let Ok(app_id) = game_id.parse::<u32>() {
+                let mut steam_status_map: HashMap<String, (i32, i64)> = HashMap::new();
+
+                if !steam_api_key.is_empty() && !steam_id.is_empty() {
+                    let player_achievements_result = match SteamWebApi::get_player_achievements(
+                        app_id,
+                        &steam_api_key,
+                        &steam_id,
+                    )
+                    .await
+                    {
+                        Ok(player_achievements) => Ok(player_achievements),
+                        Err(error) => {
+                            if SteamWebApi::is_player_achievements_forbidden(&error) {
+                                log::debug!(
+                                    "Steam player achievement status for app {} is not accessible through Steam Web API (profile/game details may be private)",
+                                    app_id
+                                );
+                            } else {
+                                log::warn!(
+                                    "Steam Web API status merge failed for app {} ({}).",
+                                    app_id,
+                                    error
+                                );
+                            }
+                            Err(error)
+                        }
+                    };
+
+                    if let Ok(player_achievements) = player_achievements_result {
+                        for ach in player_achievements {
+                            steam_status_map.insert(ach.apiname, (ach.achieved, ach.unlocktime));
+                        }
+                    }
+                } else if !steam_id.is_empty() {
+                }
 
-            if !steam_status_map.is_empty() {
-                if let Some(ach_array) = final_achievements.as_array_mut() {
-                    for ach in ach_array {
-                        if let Some(name) = ach.get("name").and_then(|v| v.as_str()) {
-                            if let Some((achieved, unlocktime)) = steam_status_map.get(name) {
-                                if let Some(obj) = ach.as_object_mut() {
-                                    obj.insert(
-                                        "achieved".to_string(),
-                                        serde_json::json!(*achieved > 0),
-                                    );
-                                    obj.insert(
-                                        "unlockTime".to_string(),
-                                        serde_json::json!(unlocktime),
-                                    );
+                if !steam_status_map.is_empty() {
+                    if let Some(ach_array) = final_achievements.as_array_mut() {
+                        for ach in ach_array {
+                            if let Some(name) = ach.get("name").and_then(|v| v.as_str()) {
+                                if let Some((achieved, unlocktime)) = steam_status_map.get(name) {
+                                    if let Some(obj) = ach.as_object_mut() {
+                                        obj.insert(
+                                            "achieved".to_string(),
+                                            serde_json::json!(*achieved > 0),
+                                        );
+                                        obj.insert(
+                                            "unlockTime".to_string(),
+                                            serde_json::json!(unlocktime),
+                                        );
+                                    }
                                 }
                             }
                         }
                     }
                 }
             }
-        }
 
-        final_achievements
+            final_achievements
+        };
+        Ok::<Value, String>(value)
+    };
+    // Com o schema local em mãos, não fica preso aos timeouts HTTP sem rede
+    let fetched = if offline.is_some() {
+        tokio::time::timeout(ONLINE_METADATA_TIMEOUT, fetched)
+            .await
+            .unwrap_or_else(|_| Err("request timed out".to_string()))
+    } else {
+        fetched.await
+    };
+
+    let mut achievements_json = match fetched {
+        Ok(value) => value,
+        Err(error) => match offline {
+            Some(value) => {
+                log::warn!(
+                    "Online achievement metadata for {} failed ({}); using local Steam schema",
+                    game_id,
+                    error
+                );
+                value
+            }
+            None => return Err(error),
+        },
     };
 
     let achievements_array = achievements_json
@@ -256,6 +296,7 @@ pub async fn get_game_achievements(
 
     let mut rarity_map = HashMap::new();
     let mut hidden_list = Vec::new();
+    let mut metadata = HashMap::new();
 
     for ach in achievements_array.iter() {
         if let Some(name) = ach
@@ -263,6 +304,7 @@ pub async fn get_game_achievements(
             .and_then(|v| v.as_str())
             .or_else(|| ach.get("name").and_then(|v| v.as_str()))
         {
+            metadata.insert(name.to_string(), cached_achievement(ach));
             if let Some(percent) = ach.get("percent").and_then(|v| v.as_f64()) {
                 rarity_map.insert(name.to_string(), percent);
             }
@@ -282,6 +324,28 @@ pub async fn get_game_achievements(
         Some(rarity_map),
         Some(hidden_list),
     );
+    if let Err(e) = CacheManager::update_achievement_metadata(&app_handle, &game_id, metadata) {
+        log::warn!(
+            "Failed to cache achievement metadata for game {}: {}",
+            game_id,
+            e
+        );
+    }
+
+    // Só jogos da biblioteca Steam (`force_steam_api`) usam o estado do cliente
+    // Steam; um jogo emulado com o mesmo AppID tem o próprio progresso
+    let steam_game = force_steam_api.unwrap_or(false);
+    if let Some(app_id) = game_id.parse::<u32>().ok().filter(|_| steam_game) {
+        match LocalSteamState::load(app_id) {
+            Ok(Some(local_state)) => merge_local_steam_unlocks(achievements_array, &local_state),
+            Ok(None) => {}
+            Err(error) => log::debug!(
+                "Local Steam stats for app {} unavailable: {}",
+                app_id,
+                error
+            ),
+        }
+    }
 
     if let Ok(steam_lock) = state.steam_monitor.lock() {
         if let Some(steam_monitor) = &*steam_lock {
@@ -319,6 +383,84 @@ pub async fn get_game_achievements(
     }))
 }
 
+/// Metadados de uma conquista no formato Hydra (`name` é o nome de API) ou
+/// Steam Web API (`apiname`, com o nome de exibição em `name`).
+fn cached_achievement(ach: &Value) -> CachedAchievement {
+    let text = |key: &str| {
+        ach.get(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let display_name = if ach.get("apiname").is_some() {
+        text("name")
+    } else {
+        text("displayName")
+    };
+
+    CachedAchievement {
+        display_name,
+        description: text("description"),
+        icon: text("icon"),
+    }
+}
+
+/// Schema de conquistas do cache local do cliente Steam, no formato da Steam Web API.
+fn offline_steam_achievements(game_id: &str, steam_language: &str) -> Option<Value> {
+    let app_id = game_id.parse::<u32>().ok()?;
+    let schema = match SteamSchema::load(app_id) {
+        Ok(schema) => schema?,
+        Err(error) => {
+            log::warn!(
+                "Local Steam schema for app {} is unreadable: {}",
+                app_id,
+                error
+            );
+            return None;
+        }
+    };
+    if schema.achievements.is_empty() {
+        return None;
+    }
+    serde_json::to_value(schema.to_steam_achievements(steam_language)).ok()
+}
+
+/// Marca como desbloqueadas as conquistas que o cache local da Steam registra,
+/// respeitando o formato de cada fonte (Hydra: `name`/`unlockTime`, Steam: `apiname`/`unlocktime`).
+fn merge_local_steam_unlocks(achievements: &mut [Value], local_state: &LocalSteamState) {
+    let unlocked: HashMap<&str, i64> = local_state
+        .achievements
+        .iter()
+        .filter(|achievement| achievement.achieved)
+        .map(|achievement| (achievement.name.as_str(), achievement.unlock_time))
+        .collect();
+    if unlocked.is_empty() {
+        return;
+    }
+
+    for achievement in achievements.iter_mut() {
+        let Some(obj) = achievement.as_object_mut() else {
+            continue;
+        };
+        let steam_shape = obj.contains_key("apiname");
+        let name = obj
+            .get("apiname")
+            .or_else(|| obj.get("name"))
+            .and_then(|v| v.as_str());
+        let Some(&unlock_time) = name.and_then(|name| unlocked.get(name)) else {
+            continue;
+        };
+
+        if steam_shape {
+            obj.insert("achieved".to_string(), serde_json::json!(1));
+            obj.insert("unlocktime".to_string(), serde_json::json!(unlock_time));
+        } else {
+            obj.insert("achieved".to_string(), serde_json::json!(true));
+            obj.insert("unlockTime".to_string(), serde_json::json!(unlock_time));
+        }
+    }
+}
+
 /// Recarrega achievements de um arquivo
 #[tauri::command]
 pub async fn reload_achievements(
@@ -356,6 +498,48 @@ pub async fn reload_achievements(
     }))
 }
 
+/// Detecta o formato de um arquivo de conquista, com a confiança de cada candidato
+#[tauri::command]
+pub async fn detect_achievement_format(path: String) -> Result<Vec<DetectionCandidate>, String> {
+    let file_path = crate::parser::expand_path(&path);
+    if !file_path.exists() {
+        return Err(format!("File not found: {}", file_path.display()));
+    }
+
+    Ok(crate::formats::detect::rank_file(&file_path))
+}
+
+/// Faz o parse de um arquivo de conquista e devolve os diagnósticos por linha,
+/// para diferenciar arquivo corrompido de conquista bloqueada.
+#[tauri::command]
+pub async fn inspect_achievement_file(
+    path: String,
+    cracker: Option<Cracker>,
+) -> Result<Value, String> {
+    let file_path = crate::parser::expand_path(&path);
+    if !file_path.exists() {
+        return Err(format!("File not found: {}", file_path.display()));
+    }
+
+    let cracker = cracker
+        .or_else(|| crate::formats::detect::best_match(&file_path))
+        .ok_or_else(|| format!("Unknown achievement format: {}", file_path.display()))?;
+
+    let (result, mut report) =
+        AchievementParser::parse_achievement_file_with_report(&file_path, cracker);
+    let achievements = result.unwrap_or_else(|e| {
+        report.error(None, "", e.to_string(), None);
+        Vec::new()
+    });
+
+    Ok(serde_json::json!({
+        "cracker": cracker,
+        "achievements": achievements,
+        "maxSeverity": report.max_severity(),
+        "issues": report.issues
+    }))
+}
+
 /// Unlock achievements
 #[tauri::command]
 pub async fn unlock_achievements(
@@ -363,6 +547,26 @@ pub async fn unlock_achievements(
     state: tauri::State<'_, crate::AppState>,
     app_handle: AppHandle,
 ) -> Result<(), String> {
+    if unlock_game(options, &state, &app_handle).await? {
+        if let Err(e) = refresh_monitor_after_local_unlock(&state, app_handle.clone()).await {
+            log::warn!("Failed to refresh monitor after local unlock: {}", e);
+        }
+
+        app_handle
+            .emit("achievements-updated", ())
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Unlock de um jogo, via Steam ou no arquivo local. Devolve `true` quando um
+/// arquivo local foi gravado (e o monitor precisa ser atualizado).
+pub(crate) async fn unlock_game(
+    mut options: UnlockOptions,
+    state: &tauri::State<'_, crate::AppState>,
+    app_handle: &AppHandle,
+) -> Result<bool, String> {
     log::info!(
         "Unlocking achievements for game {} (Path: {})",
         options.game_id,
@@ -402,25 +606,270 @@ pub async fn unlock_achievements(
                 }
 
                 unlock_result?;
-                return Ok(());
+                return Ok(false);
             }
         }
         return Err("Steam integration not available or Steam not running".into());
     }
 
+    resolve_timestamp_inputs(&mut options, app_handle).await;
+    let preset = directory_preset_for_path(state, &options.selected_path).unwrap_or_default();
+    let (file_path, cracker) = AchievementUnlocker::selected_target(&options, preset);
+    journaled(
+        app_handle,
+        OperationKind::Unlock,
+        &options.game_id,
+        &[(cracker, file_path)],
+        || {
+            AchievementUnlocker::unlock_achievements_with_preset(&options, preset)
+                .map_err(|e| e.to_string())
+        },
+    )?;
+
+    Ok(true)
+}
+
+/// Prévia de `unlock_achievements`: arquivo, formato e o que muda em cada
+/// conquista, sem gravar nada. Para aplicá-la, envie `options` da prévia a
+/// `unlock_achievements`
+#[tauri::command]
+pub async fn preview_unlock_achievements(
+    mut options: UnlockOptions,
+    state: tauri::State<'_, crate::AppState>,
+    app_handle: AppHandle,
+) -> Result<UnlockPreview, String> {
+    if options.selected_path.starts_with("steam://") {
+        return Err("Previews are only available for local achievement files".into());
+    }
+
+    resolve_timestamp_inputs(&mut options, &app_handle).await;
     let preset = directory_preset_for_path(&state, &options.selected_path).unwrap_or_default();
-    AchievementUnlocker::unlock_achievements_with_preset(&options, preset)
-        .map_err(|e| e.to_string())?;
+    AchievementUnlocker::preview_unlock_with_preset(&options, preset)
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// Valida um horário de desbloqueio e devolve o unix correspondente
+#[tauri::command]
+pub async fn validate_unlock_timestamp(timestamp: Timestamp) -> Result<i64, String> {
+    AchievementWriter::timestamp_to_unix(&timestamp).map_err(|e| format!("{:#}", e))
+}
+
+/// Aplica só as conquistas informadas, preservando as demais e os horários
+/// já gravados, e devolve o que mudou em cada uma
+#[tauri::command]
+pub async fn apply_achievement_changes(
+    mut options: UnlockOptions,
+    state: tauri::State<'_, crate::AppState>,
+    app_handle: AppHandle,
+) -> Result<ChangeReport, String> {
+    if options.selected_path.starts_with("steam://") {
+        return Err("Applying changes is only supported for local achievement files".into());
+    }
+
+    log::info!(
+        "Applying achievement changes for game {} (Path: {})",
+        options.game_id,
+        options.selected_path
+    );
 
-    if let Err(e) = refresh_monitor_after_local_unlock(&state, app_handle.clone()).await {
-        log::warn!("Failed to refresh monitor after local unlock: {}", e);
+    resolve_timestamp_inputs(&mut options, &app_handle).await;
+    let preset = directory_preset_for_path(&state, &options.selected_path).unwrap_or_default();
+    let (file_path, cracker) = AchievementUnlocker::selected_target(&options, preset);
+    let report = journaled(
+        &app_handle,
+        OperationKind::Unlock,
+        &options.game_id,
+        &[(cracker, file_path)],
+        || {
+            AchievementUnlocker::apply_changes_with_preset(&options, preset)
+                .map_err(|e| e.to_string())
+        },
+    )?;
+
+    if report.written {
+        if let Err(e) = refresh_monitor_after_local_unlock(&state, app_handle.clone()).await {
+            log::warn!("Failed to refresh monitor after applying changes: {}", e);
+        }
+
+        app_handle
+            .emit("achievements-updated", ())
+            .map_err(|e| e.to_string())?;
     }
 
-    app_handle
-        .emit("achievements-updated", ())
-        .map_err(|e| e.to_string())?;
+    Ok(report)
+}
 
-    Ok(())
+/// Aplica as mesmas mudanças em todos os arquivos de conquista detectados
+/// para o jogo (todos os crackers e Wine prefixes), além do diretório
+/// selecionado, para que o Hydra veja o mesmo estado em qualquer emulador
+#[tauri::command]
+pub async fn mirror_achievement_changes(
+    mut options: UnlockOptions,
+    state: tauri::State<'_, crate::AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<MirrorTargetResult>, String> {
+    let settings = load_settings(app_handle.clone())
+        .await
+        .unwrap_or_else(|_| serde_json::json!({}));
+    let wine_prefix = settings
+        .get("winePrefixPath")
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string());
+
+    let mut targets: Vec<(Cracker, std::path::PathBuf)> = Vec::new();
+    if !options.selected_path.is_empty() && !options.selected_path.starts_with("steam://") {
+        let preset = directory_preset_for_path(&state, &options.selected_path).unwrap_or_default();
+        let (file_path, cracker) = AchievementUnlocker::selected_target(&options, preset);
+        targets.push((cracker, file_path));
+    }
+
+    let user_data = super::directories::hydra_user_data_dir();
+    let found = super::directories::find_achievement_files_for_game(
+        &options.game_id,
+        wine_prefix.as_deref(),
+        None,
+        None,
+        &user_data,
+    );
+    for (cracker, file_path) in found {
+        // O cache da biblioteca Steam não é lido por nenhum emulador
+        if cracker == Cracker::SteamCache || targets.iter().any(|(_, p)| *p == file_path) {
+            continue;
+        }
+        let cracker = crate::formats::detect::best_match(&file_path).unwrap_or(cracker);
+        targets.push((cracker, file_path));
+    }
+
+    if targets.is_empty() {
+        return Err(format!(
+            "No achievement files found for game {}",
+            options.game_id
+        ));
+    }
+
+    log::info!(
+        "Mirroring achievement changes for game {} to {} file(s)",
+        options.game_id,
+        targets.len()
+    );
+    resolve_timestamp_inputs(&mut options, &app_handle).await;
+    let results = journaled(
+        &app_handle,
+        OperationKind::Unlock,
+        &options.game_id,
+        &targets,
+        || AchievementUnlocker::mirror_changes(&options, &targets).map_err(|e| format!("{:#}", e)),
+    )?;
+
+    if results
+        .iter()
+        .any(|r| r.report.as_ref().is_some_and(|report| report.written))
+    {
+        if let Err(e) = refresh_monitor_after_local_unlock(&state, app_handle.clone()).await {
+            log::warn!("Failed to refresh monitor after mirroring changes: {}", e);
+        }
+
+        app_handle
+            .emit("achievements-updated", ())
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(results)
+}
+
+/// Converte o arquivo de um emulador para o formato de outro. Sem `apply`,
+/// devolve só a prévia do que mudaria no destino
+#[tauri::command]
+pub async fn convert_achievement_file(
+    request: ConversionRequest,
+    apply: Option<bool>,
+    app_handle: AppHandle,
+) -> Result<ConversionPreview, String> {
+    let apply = apply.unwrap_or(false);
+    let convert = || AchievementConverter::convert(&request, apply).map_err(|e| format!("{:#}", e));
+    let preview = if apply {
+        let target = (
+            request.target_cracker,
+            AchievementConverter::target_path(&request),
+        );
+        journaled(
+            &app_handle,
+            OperationKind::Convert,
+            &request.game_id,
+            &[target],
+            convert,
+        )?
+    } else {
+        convert()?
+    };
+
+    if preview.written {
+        app_handle
+            .emit("achievements-updated", ())
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(preview)
+}
+
+/// Completa os dados das estratégias de horário: raridade do cache (ou da
+/// Steam Web API) e última sessão/tempo de jogo da Steam local ou do Hydra
+async fn resolve_timestamp_inputs(options: &mut UnlockOptions, app_handle: &AppHandle) {
+    if options.order_by_rarity && options.rarity.is_empty() {
+        let cached = CacheManager::load(app_handle)
+            .ok()
+            .and_then(|cache| cache.games.get(&options.game_id)?.rarity.clone());
+        if let Some(rarity) = cached {
+            options.rarity = rarity;
+        } else if let Ok(app_id) = options.game_id.parse::<u32>() {
+            match SteamWebApi::get_global_achievement_percentages(app_id).await {
+                Ok(percentages) => options.rarity = percentages.into_iter().collect(),
+                Err(e) => log::warn!("Failed to fetch rarity for {}: {}", app_id, e),
+            }
+        }
+    }
+
+    let Some(TimestampStrategy::Playtime {
+        last_played,
+        playtime_minutes,
+    }) = &mut options.strategy
+    else {
+        return;
+    };
+    if last_played.is_some() && playtime_minutes.is_some() {
+        return;
+    }
+
+    let known = match options.game_id.parse::<u32>() {
+        Ok(app_id) => crate::integrations::steam::steam_local_profile::get_local_playtime(app_id),
+        Err(_) => None,
+    }
+    .or_else(|| hydra_playtime(app_handle, &options.game_id));
+
+    match known {
+        Some((last, minutes)) => {
+            last_played.get_or_insert(last);
+            playtime_minutes.get_or_insert(minutes);
+        }
+        None => log::info!(
+            "No playtime data for game {}; using defaults",
+            options.game_id
+        ),
+    }
+}
+
+/// Última sessão e tempo de jogo (minutos) registrados pela biblioteca do Hydra.
+fn hydra_playtime(app_handle: &AppHandle, game_id: &str) -> Option<(i64, u64)> {
+    let settings = crate::utils::settings::load_settings_or_default(app_handle);
+    let custom_path = settings.get("hydraDbPath").and_then(|v| v.as_str());
+    let games = crate::integrations::hydra::get_hydra_library_games(custom_path).ok()?;
+    let game = games.iter().find(|game| game.object_id == game_id)?;
+
+    let last_played = chrono::DateTime::parse_from_rfc3339(game.last_time_played.as_deref()?)
+        .ok()?
+        .timestamp();
+    let minutes = game.play_time_in_milliseconds.unwrap_or(0) / 60_000;
+    Some((last_played, minutes))
 }
 
 fn directory_preset_for_path(
@@ -436,7 +885,7 @@ fn directory_preset_for_path(
         .map(|dir| dir.detection_preset)
 }
 
-async fn refresh_monitor_after_local_unlock(
+pub(crate) async fn refresh_monitor_after_local_unlock(
     state: &tauri::State<'_, crate::AppState>,
     app_handle: AppHandle,
 ) -> Result<(), String> {
@@ -491,76 +940,77 @@ async fn refresh_monitor_after_local_unlock(
     Ok(())
 }
 
-static LAST_EXPORT_TIME: Mutex<Option<u64>> = Mutex::new(None);
-const EXPORT_COOLDOWN: u64 = 2000;
-
 /// Exporta achievements
 #[tauri::command]
 pub async fn export_achievements(
     game_id: String,
+    state: tauri::State<'_, crate::AppState>,
     app_handle: AppHandle,
 ) -> Result<serde_json::Value, String> {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| e.to_string())?
-        .as_millis() as u64;
-
-    {
-        let mut last_time = LAST_EXPORT_TIME.lock().map_err(|e| e.to_string())?;
-        if let Some(last) = *last_time {
-            if now - last < EXPORT_COOLDOWN {
-                return Ok(serde_json::json!({
-                    "success": false,
-                    "message": "Export is on cooldown. Please wait."
-                }));
-            }
-        }
-        *last_time = Some(now);
+    let busy = serde_json::json!({
+        "success": false,
+        "message": "An export is already running. Please wait."
+    });
+    if state.jobs.is_running(JobKind::Export) {
+        return Ok(busy);
     }
 
-    let export_dir = app_handle
-        .dialog()
-        .file()
-        .set_title("Select directory to export achievements")
-        .blocking_pick_folder();
-
-    let export_dir = match export_dir {
-        Some(fp) => fp.as_path().map(|p| p.to_path_buf()),
-        None => {
+    // Só registra o job depois que o diretório foi escolhido
+    let export_dir = match pick_export_dir(&app_handle) {
+        Ok(dir) => dir,
+        Err(message) => {
             return Ok(serde_json::json!({
                 "success": false,
-                "message": "Export cancelled"
+                "message": message
             }));
         }
     };
-
-    let export_dir = match export_dir {
-        Some(p) => p,
-        None => {
-            return Ok(serde_json::json!({
-                "success": false,
-                "message": "Invalid export path"
-            }));
-        }
+    let Some(mut run) = state.jobs.try_start(JobKind::Export) else {
+        return Ok(busy);
     };
 
-    let mut language = "en-US".to_string();
-    let settings = load_settings(app_handle.clone())
-        .await
-        .unwrap_or(serde_json::json!({}));
-    if let Some(lang) = settings.get("language").and_then(|v| v.as_str()) {
-        language = lang.to_string();
-    }
+    let language = export_language(&app_handle).await;
+    let result =
+        AchievementExporter::export_achievements(&game_id, export_dir, &language, &app_handle)
+            .await
+            .map_err(|e| e.to_string());
+    run.record(&game_id, result.clone().map(|_| None));
+    state.jobs.finish(run, None);
 
-    match AchievementExporter::export_achievements(&game_id, export_dir, &language, &app_handle)
-        .await
-    {
+    match result {
         Ok(_) => Ok(serde_json::json!({
             "success": true
         })),
         Err(e) => Ok(serde_json::json!({
             "success": false,
-            "message": e.to_string()
+            "message": e
         })),
     }
 }
+
+/// Diretório de exportação escolhido pelo usuário; o erro é a mensagem para a UI
+pub(crate) fn pick_export_dir(app_handle: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let export_dir = app_handle
+        .dialog()
+        .file()
+        .set_title("Select directory to export achievements")
+        .blocking_pick_folder();
+
+    export_dir
+        .ok_or_else(|| "Export cancelled".to_string())?
+        .as_path()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| "Invalid export path".to_string())
+}
+
+/// Idioma da UI salvo nas configurações, usado nos nomes exportados
+pub(crate) async fn export_language(app_handle: &AppHandle) -> String {
+    let settings = load_settings(app_handle.clone())
+        .await
+        .unwrap_or(serde_json::json!({}));
+    settings
+        .get("language")
+        .and_then(|v| v.as_str())
+        .unwrap_or("en-US")
+        .to_string()
+}
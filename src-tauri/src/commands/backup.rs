@@ -1,5 +1,9 @@
-use crate::models::AchievementEntry;
+use crate::formats::snapshot::{self, FileSnapshot};
+use crate::integrations::steam::LocalSteamState;
+use crate::models::{AchievementEntry, Cracker, UserStat};
 use crate::parser::expand_path;
+use crate::stats::{StatsParser, StatsWriter};
+use crate::unlocker::journal::{JournalRecord, OperationJournal, OperationKind};
 use crate::unlocker::AchievementWriter;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
@@ -36,6 +40,8 @@ struct BackupGameEntry {
     file_format: String,
     last_modified: i64,
     achievements: Vec<AchievementEntry>,
+    #[serde(default)]
+    stats: Vec<UserStat>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -43,6 +49,8 @@ struct BackupGameEntry {
 pub struct SteamBackupEntryInput {
     pub game_id: String,
     pub achievements: Vec<AchievementEntry>,
+    #[serde(default)]
+    pub stats: Vec<UserStat>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -70,6 +78,7 @@ pub struct RestorePreviewItem {
     pub directory: String,
     pub file_format: String,
     pub backup_achievements: usize,
+    pub backup_stats: usize,
     pub existing_achievements: usize,
     pub overlapping_achievements: usize,
     pub changed_achievements: usize,
@@ -132,12 +141,50 @@ pub async fn create_achievements_backup(
     state: State<'_, crate::AppState>,
     app_handle: AppHandle,
 ) -> Result<BackupResult, String> {
-    let monitor_lock = state.monitor.lock().map_err(|e| e.to_string())?;
-    let monitor = monitor_lock
-        .as_ref()
-        .ok_or_else(|| "Monitor not initialized".to_string())?;
+    write_backup(
+        output_path,
+        selected_game_ids,
+        include_settings,
+        steam_entries,
+        &state,
+        &app_handle,
+        |_| true,
+    )
+}
+
+/// Etapa de [`write_backup`] para um jogo.
+pub(crate) enum BackupStep<'a> {
+    /// Antes de incluir `game_ids[index]`; devolver `false` cancela o backup
+    /// sem gravar nada.
+    Collecting {
+        index: usize,
+        game_ids: &'a [String],
+    },
+    /// O jogo foi incluído, talvez com um aviso, ou ficou de fora pelo erro.
+    Collected {
+        game_id: &'a str,
+        result: Result<Option<String>, String>,
+    },
+}
+
+/// Monta e grava o backup, chamando `step` a cada jogo (ver [`BackupStep`]).
+pub(crate) fn write_backup(
+    output_path: String,
+    selected_game_ids: Option<Vec<String>>,
+    include_settings: Option<bool>,
+    steam_entries: Option<Vec<SteamBackupEntryInput>>,
+    state: &crate::AppState,
+    app_handle: &AppHandle,
+    mut step: impl FnMut(BackupStep<'_>) -> bool,
+) -> Result<BackupResult, String> {
+    let games = {
+        let monitor_lock = state.monitor.lock().map_err(|e| e.to_string())?;
+        let monitor = monitor_lock
+            .as_ref()
+            .ok_or_else(|| "Monitor not initialized".to_string())?;
+        monitor.get_current_achievements()
+    };
 
-    let games = monitor.get_current_achievements();
     let selected_set: HashSet<String> = selected_game_ids
         .clone()
         .unwrap_or_default()
@@ -156,50 +203,111 @@ pub async fn create_achievements_backup(
 
     let should_include_settings = include_settings.unwrap_or(true);
     let settings_snapshot = if should_include_settings {
-        read_current_settings(&app_handle)?
+        read_current_settings(app_handle)?
     } else {
         None
     };
 
+    let selected_filter_active = !selected_set.is_empty();
+    let mut seen_steam = HashSet::<String>::new();
+    let steam_entries: Vec<SteamBackupEntryInput> = steam_entries
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|steam_entry| {
+            !steam_entry.game_id.trim().is_empty()
+                && (!selected_filter_active || selected_set.contains(&steam_entry.game_id))
+                && seen_steam.insert(steam_entry.game_id.clone())
+        })
+        .collect();
+
     if filtered_games.is_empty() && settings_snapshot.is_none() {
         return Err("No games or settings available for backup".to_string());
     }
 
-    let mut backup_games: Vec<BackupGameEntry> = filtered_games
-        .into_iter()
-        .map(|game| BackupGameEntry {
+    let game_ids: Vec<String> = filtered_games
+        .iter()
+        .map(|game| game.game_id.clone())
+        .chain(steam_entries.iter().map(|entry| entry.game_id.clone()))
+        .collect();
+    let mut backup_games: Vec<BackupGameEntry> = Vec::with_capacity(game_ids.len());
+    let mut index = 0;
+    for game in filtered_games {
+        if !step(BackupStep::Collecting {
+            index,
+            game_ids: &game_ids,
+        }) {
+            return Err("Backup cancelled".to_string());
+        }
+        index += 1;
+
+        // Sem as estatísticas, o jogo entra no backup só com as conquistas
+        let (stats, note) = match read_existing_stats(&game.directory, &game.game_id) {
+            Ok(stats) => (stats, None),
+            Err(e) => {
+                log::warn!(
+                    "Failed to read stats for backup of game {}: {}",
+                    game.game_id,
+                    e
+                );
+                (Vec::new(), Some(format!("Stats not included: {}", e)))
+            }
+        };
+        step(BackupStep::Collected {
+            game_id: &game.game_id,
+            result: Ok(note),
+        });
+        backup_games.push(BackupGameEntry {
             file_format: detect_game_file_format(&game.directory, &game.game_id),
+            stats,
             game_id: game.game_id,
             directory: game.directory,
             last_modified: game.last_modified,
             achievements: game.achievements,
-        })
-        .collect();
+        });
+    }
 
     let backup_created_at = chrono::Utc::now().to_rfc3339();
     let backup_created_at_unix = chrono::DateTime::parse_from_rfc3339(&backup_created_at)
         .map(|d| d.timestamp())
         .unwrap_or_else(|_| chrono::Utc::now().timestamp());
 
-    let selected_filter_active = !selected_set.is_empty();
-    let mut seen_steam = HashSet::<String>::new();
-    for steam_entry in steam_entries.unwrap_or_default() {
-        if steam_entry.game_id.trim().is_empty() {
-            continue;
-        }
-        if selected_filter_active && !selected_set.contains(&steam_entry.game_id) {
-            continue;
+    for steam_entry in steam_entries {
+        if !step(BackupStep::Collecting {
+            index,
+            game_ids: &game_ids,
+        }) {
+            return Err("Backup cancelled".to_string());
         }
-        if !seen_steam.insert(steam_entry.game_id.clone()) {
+        index += 1;
+
+        // Sem dados vindos do Steamworks, usa o cache local do cliente Steam
+        let (achievements, stats) = if steam_entry.achievements.is_empty() {
+            match read_local_steam_state(&steam_entry.game_id) {
+                Some(local_state) => (local_state.achievements, local_state.stats),
+                None => (steam_entry.achievements, steam_entry.stats),
+            }
+        } else {
+            (steam_entry.achievements, steam_entry.stats)
+        };
+        if achievements.is_empty() && stats.is_empty() {
+            step(BackupStep::Collected {
+                game_id: &steam_entry.game_id,
+                result: Err("No Steam achievements or stats to back up".to_string()),
+            });
             continue;
         }
 
+        step(BackupStep::Collected {
+            game_id: &steam_entry.game_id,
+            result: Ok(None),
+        });
         backup_games.push(BackupGameEntry {
             game_id: steam_entry.game_id,
             directory: "steam://".to_string(),
             file_format: "steam".to_string(),
             last_modified: backup_created_at_unix,
-            achievements: steam_entry.achievements,
+            achievements,
+            stats,
         });
     }
 
@@ -325,7 +433,7 @@ pub async fn apply_achievements_restore(
             continue;
         }
 
-        restore_entry(item, &state)?;
+        restore_entry(item, &state, &app_handle)?;
         restored_entries += 1;
     }
 
@@ -350,6 +458,138 @@ pub async fn apply_achievements_restore(
     })
 }
 
+/// Lista os snapshots `.ham-bak` de um arquivo de conquista (ou diretório
+/// FLT), do mais recente para o mais antigo
+#[tauri::command]
+pub async fn list_file_snapshots(file_path: String) -> Result<Vec<FileSnapshot>, String> {
+    snapshot::list_snapshots(&expand_path(&file_path)).map_err(|e| e.to_string())
+}
+
+/// Restaura um snapshot `.ham-bak` sobre o arquivo original
+#[tauri::command]
+pub async fn restore_file_snapshot(
+    snapshot_path: String,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let snapshot_path = expand_path(&snapshot_path);
+    let target = snapshot::snapshot_target(&snapshot_path).map_err(|e| format!("{:#}", e))?;
+    let targets: Vec<(Cracker, PathBuf)> = crate::formats::detect::best_match(&target)
+        .map(|cracker| (cracker, target.clone()))
+        .into_iter()
+        .collect();
+    // O diretório do arquivo é o ID do jogo em todos os formatos suportados
+    let game_id = target
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let restored = journaled(
+        &app_handle,
+        OperationKind::Restore,
+        &game_id,
+        &targets,
+        || snapshot::restore_snapshot(&snapshot_path).map_err(|e| format!("{:#}", e)),
+    )?;
+
+    app_handle
+        .emit("achievements-updated", ())
+        .map_err(|e| e.to_string())?;
+
+    Ok(restored.to_string_lossy().to_string())
+}
+
+/// Operações registradas no diário, da mais recente para a mais antiga
+#[tauri::command]
+pub async fn list_operations(
+    game_id: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Vec<JournalRecord>, String> {
+    let records = operation_journal(&app_handle)?
+        .list()
+        .map_err(|e| format!("{:#}", e))?;
+
+    Ok(match game_id {
+        Some(game_id) => records
+            .into_iter()
+            .filter(|record| record.game_id == game_id)
+            .collect(),
+        None => records,
+    })
+}
+
+/// Reverte as conquistas alteradas por uma operação do diário
+#[tauri::command]
+pub async fn undo_operation(id: String, app_handle: AppHandle) -> Result<JournalRecord, String> {
+    let record = operation_journal(&app_handle)?
+        .undo(&id)
+        .map_err(|e| format!("{:#}", e))?;
+
+    app_handle
+        .emit("achievements-updated", ())
+        .map_err(|e| e.to_string())?;
+
+    Ok(record)
+}
+
+/// Diário de operações em `<app data>/journal.jsonl`
+pub(crate) fn operation_journal(app_handle: &AppHandle) -> Result<OperationJournal, String> {
+    let app_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app_data_dir: {}", e))?;
+    Ok(OperationJournal::new(app_dir.join("journal.jsonl")))
+}
+
+/// Executa `write` e registra no diário o conteúdo de cada destino antes e
+/// depois. Falhas no diário só geram aviso: a escrita em si não é desfeita.
+pub(crate) fn journaled<T>(
+    app_handle: &AppHandle,
+    kind: OperationKind,
+    game_id: &str,
+    targets: &[(Cracker, PathBuf)],
+    write: impl FnOnce() -> Result<T, String>,
+) -> Result<T, String> {
+    let previous: Vec<_> = targets
+        .iter()
+        .map(|(cracker, path)| OperationJournal::capture(path, *cracker))
+        .collect();
+
+    let result = write();
+
+    let journal = match operation_journal(app_handle) {
+        Ok(journal) => journal,
+        Err(e) => {
+            log::warn!("Operation journal unavailable: {}", e);
+            return result;
+        }
+    };
+
+    for ((cracker, path), previous) in targets.iter().zip(previous) {
+        // Sem o estado anterior não há como desfazer depois
+        let previous = match previous {
+            Ok(previous) => previous,
+            Err(e) => {
+                log::warn!("Not journaling {}: {:#}", path.display(), e);
+                continue;
+            }
+        };
+        let entries = match OperationJournal::capture(path, *cracker) {
+            Ok(Some(entries)) => entries,
+            Ok(None) => continue,
+            Err(e) => {
+                log::warn!("Not journaling {}: {:#}", path.display(), e);
+                continue;
+            }
+        };
+        if let Err(e) = journal.record(kind, game_id, path, *cracker, previous, entries) {
+            log::warn!("Failed to journal operation on {}: {:#}", path.display(), e);
+        }
+    }
+
+    result
+}
+
 fn read_backup_file(path: &str) -> Result<BackupFile, String> {
     let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
     let decoded = decode_backup_content(&raw)?;
@@ -428,7 +668,9 @@ fn read_existing_achievements(
     game_id: &str,
 ) -> Result<Vec<AchievementEntry>, String> {
     if is_steam_directory(directory) {
-        return Ok(Vec::new());
+        return Ok(read_local_steam_state(game_id)
+            .map(|local_state| local_state.achievements)
+            .unwrap_or_default());
     }
 
     let game_dir = expand_path(directory).join(game_id);
@@ -443,6 +685,29 @@ fn read_existing_achievements(
     Ok(Vec::new())
 }
 
+fn read_existing_stats(directory: &str, game_id: &str) -> Result<Vec<UserStat>, String> {
+    if is_steam_directory(directory) {
+        return Ok(Vec::new());
+    }
+
+    let game_dir = expand_path(directory).join(game_id);
+    StatsParser::read_game_stats(&game_dir, &StatsParser::schema_stat_types(game_id))
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// Estado Steam do cache local do cliente (`appcache/stats`), sem Steamworks.
+fn read_local_steam_state(game_id: &str) -> Option<LocalSteamState> {
+    let app_id = game_id.parse::<u32>().ok()?;
+    LocalSteamState::load(app_id).unwrap_or_else(|e| {
+        log::warn!(
+            "Failed to read local Steam stats for game {}: {}",
+            game_id,
+            e
+        );
+        None
+    })
+}
+
 fn build_preview_item(
     index: usize,
     item: &BackupGameEntry,
@@ -489,6 +754,7 @@ fn build_preview_item(
         directory: item.directory.clone(),
         file_format: item.file_format.clone(),
         backup_achievements: item.achievements.len(),
+        backup_stats: item.stats.len(),
         existing_achievements: existing.len(),
         overlapping_achievements: overlapping,
         changed_achievements: changed,
@@ -524,7 +790,11 @@ fn entry_has_conflict(item: &BackupGameEntry) -> Result<bool, String> {
     Ok(false)
 }
 
-fn restore_entry(item: &BackupGameEntry, state: &State<'_, crate::AppState>) -> Result<(), String> {
+fn restore_entry(
+    item: &BackupGameEntry,
+    state: &State<'_, crate::AppState>,
+    app_handle: &AppHandle,
+) -> Result<(), String> {
     if is_steam_directory(&item.directory) {
         return restore_steam_entry(item, state);
     }
@@ -535,13 +805,26 @@ fn restore_entry(item: &BackupGameEntry, state: &State<'_, crate::AppState>) ->
     let json_path = game_dir.join("achievements.json");
 
     let use_json = should_write_json(&expanded_base, &ini_path, &json_path, &item.file_format);
-
-    if use_json {
-        AchievementWriter::write_goldberg_json(&json_path, &item.achievements)
-            .map_err(|e| e.to_string())?;
+    let target = if use_json {
+        (Cracker::Goldberg, json_path)
     } else {
-        AchievementWriter::write_default_ini(&ini_path, &item.achievements)
-            .map_err(|e| e.to_string())?;
+        (Cracker::Codex, ini_path)
+    };
+
+    journaled(
+        app_handle,
+        OperationKind::Restore,
+        &item.game_id,
+        std::slice::from_ref(&target),
+        || {
+            AchievementWriter::replace_achievements(&target.1, &item.achievements, target.0)
+                .map_err(|e| e.to_string())
+        },
+    )?;
+
+    if !item.stats.is_empty() {
+        let location = StatsParser::stats_location_for_game(&game_dir);
+        StatsWriter::write_stats(&location, &item.stats).map_err(|e| e.to_string())?;
     }
 
     Ok(())
@@ -648,6 +931,12 @@ fn restore_steam_entry(
                 .map_err(|e| e.to_string())?;
         }
 
+        if !item.stats.is_empty() {
+            steam_monitor
+                .set_stats(&item.stats)
+                .map_err(|e| e.to_string())?;
+        }
+
         Ok(())
     })();
 
@@ -1,4 +1,5 @@
 pub use crate::integrations::hydra::hydra_launcher_paths::{
     build_default_directory_configs, build_wine_prefix_dirs_for_game,
     find_achievement_files_for_game, find_existing_achievement_dirs_for_game_in_prefix,
+    hydra_user_data_dir,
 };
@@ -0,0 +1,50 @@
+use crate::monitor::history::{UnlockDay, UnlockHistory, UnlockRecord};
+use tauri::{AppHandle, Manager};
+
+/// Quantidade padrão de `get_latest_unlocks`
+const DEFAULT_LATEST_LIMIT: usize = 20;
+
+/// Histórico de desbloqueios em `<app data>/unlock_history.jsonl`
+pub(crate) fn unlock_history(app_handle: &AppHandle) -> Result<UnlockHistory, String> {
+    let app_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app_data_dir: {}", e))?;
+    Ok(UnlockHistory::new(app_dir.join("unlock_history.jsonl")))
+}
+
+/// Desbloqueios observados de um jogo, dos mais recentes aos mais antigos
+#[tauri::command]
+pub async fn get_game_unlock_history(
+    game_id: String,
+    app_handle: AppHandle,
+) -> Result<Vec<UnlockRecord>, String> {
+    unlock_history(&app_handle)?
+        .for_game(&game_id)
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// Desbloqueios observados entre `from` e `to` (unix), agrupados por dia no
+/// fuso `time_zone` (IANA) ou no fuso local
+#[tauri::command]
+pub async fn get_unlock_history_by_day(
+    from: Option<i64>,
+    to: Option<i64>,
+    time_zone: Option<String>,
+    app_handle: AppHandle,
+) -> Result<Vec<UnlockDay>, String> {
+    unlock_history(&app_handle)?
+        .by_day(from, to, time_zone.as_deref())
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// Os desbloqueios observados mais recentes, de todos os jogos e emuladores
+#[tauri::command]
+pub async fn get_latest_unlocks(
+    limit: Option<usize>,
+    app_handle: AppHandle,
+) -> Result<Vec<UnlockRecord>, String> {
+    unlock_history(&app_handle)?
+        .latest(limit.unwrap_or(DEFAULT_LATEST_LIMIT))
+        .map_err(|e| format!("{:#}", e))
+}
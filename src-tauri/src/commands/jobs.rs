@@ -0,0 +1,207 @@
+use super::achievements::{
+    export_language, pick_export_dir, refresh_monitor_after_local_unlock, unlock_game,
+};
+use super::backup::{write_backup, BackupStep, SteamBackupEntryInput};
+use crate::jobs::{JobKind, JobReport, JobRun};
+use crate::models::UnlockOptions;
+use crate::utils::AchievementExporter;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Inicia o unlock de vários jogos em segundo plano e devolve o ID do job
+#[tauri::command]
+pub async fn start_bulk_unlock(
+    requests: Vec<UnlockOptions>,
+    state: tauri::State<'_, crate::AppState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    if requests.is_empty() {
+        return Err("No games selected".to_string());
+    }
+
+    let run = state
+        .jobs
+        .try_start(JobKind::Unlock)
+        .ok_or_else(|| "A bulk unlock is already running".to_string())?;
+    let job_id = run.id().to_string();
+
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<crate::AppState>();
+        let mut run = run;
+        let game_ids: Vec<String> = requests.iter().map(|r| r.game_id.clone()).collect();
+        let mut wrote_local = false;
+
+        for (index, options) in requests.into_iter().enumerate() {
+            if run.is_cancelled() {
+                run.skip(game_ids[index..].iter().map(String::as_str));
+                break;
+            }
+            emit_progress(&app_handle, &run, game_ids.len(), &game_ids[index]);
+
+            let result = unlock_game(options, &state, &app_handle).await;
+            wrote_local |= result == Ok(true);
+            run.record(&game_ids[index], result.map(|_| None));
+        }
+
+        if wrote_local {
+            if let Err(e) = refresh_monitor_after_local_unlock(&state, app_handle.clone()).await {
+                log::warn!("Failed to refresh monitor after bulk unlock: {}", e);
+            }
+            if let Err(e) = app_handle.emit("achievements-updated", ()) {
+                log::warn!("Failed to emit achievements-updated: {}", e);
+            }
+        }
+        emit_finished(&app_handle, state.jobs.finish(run, None));
+    });
+
+    Ok(job_id)
+}
+
+/// Exporta vários jogos, cada um em um subdiretório do diretório escolhido
+#[tauri::command]
+pub async fn start_bulk_export(
+    game_ids: Vec<String>,
+    state: tauri::State<'_, crate::AppState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    if game_ids.is_empty() {
+        return Err("No games selected".to_string());
+    }
+
+    if state.jobs.is_running(JobKind::Export) {
+        return Err("An export is already running".to_string());
+    }
+    // Só registra o job depois que o diretório foi escolhido
+    let export_dir = pick_export_dir(&app_handle)?;
+    let run = state
+        .jobs
+        .try_start(JobKind::Export)
+        .ok_or_else(|| "An export is already running".to_string())?;
+    let job_id = run.id().to_string();
+
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<crate::AppState>();
+        let mut run = run;
+        let language = export_language(&app_handle).await;
+
+        for (index, game_id) in game_ids.iter().enumerate() {
+            if run.is_cancelled() {
+                run.skip(game_ids[index..].iter().map(String::as_str));
+                break;
+            }
+            emit_progress(&app_handle, &run, game_ids.len(), game_id);
+
+            let result = AchievementExporter::export_achievements(
+                game_id,
+                export_dir.join(game_id),
+                &language,
+                &app_handle,
+            )
+            .await
+            .map(|_| None)
+            .map_err(|e| format!("{:#}", e));
+            run.record(game_id, result);
+        }
+
+        emit_finished(&app_handle, state.jobs.finish(run, None));
+    });
+
+    Ok(job_id)
+}
+
+/// `create_achievements_backup` como job, com progresso por jogo
+#[tauri::command]
+pub async fn start_backup_job(
+    output_path: String,
+    selected_game_ids: Option<Vec<String>>,
+    include_settings: Option<bool>,
+    steam_entries: Option<Vec<SteamBackupEntryInput>>,
+    state: tauri::State<'_, crate::AppState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let run = state
+        .jobs
+        .try_start(JobKind::Backup)
+        .ok_or_else(|| "A backup is already running".to_string())?;
+    let job_id = run.id().to_string();
+
+    // `write_backup` lê e grava arquivos de forma síncrona
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<crate::AppState>();
+        let mut run = run;
+        let mut remaining: Vec<String> = Vec::new();
+
+        let result = write_backup(
+            output_path,
+            selected_game_ids,
+            include_settings,
+            steam_entries,
+            &state,
+            &app_handle,
+            |step| match step {
+                BackupStep::Collecting { index, game_ids } => {
+                    if run.is_cancelled() {
+                        remaining = game_ids[index..].to_vec();
+                        return false;
+                    }
+                    emit_progress(&app_handle, &run, game_ids.len(), &game_ids[index]);
+                    true
+                }
+                BackupStep::Collected { game_id, result } => {
+                    run.record(game_id, result);
+                    true
+                }
+            },
+        );
+
+        let error = match result {
+            Ok(backup) => {
+                log::info!("Backup written to {}", backup.output_path);
+                None
+            }
+            Err(_) if run.is_cancelled() => {
+                run.skip(remaining.iter().map(String::as_str));
+                None
+            }
+            Err(e) => Some(e),
+        };
+        emit_finished(&app_handle, state.jobs.finish(run, error));
+    });
+
+    Ok(job_id)
+}
+
+/// Pede o cancelamento de um job; ele para antes do próximo item
+#[tauri::command]
+pub async fn cancel_job(
+    job_id: String,
+    state: tauri::State<'_, crate::AppState>,
+) -> Result<bool, String> {
+    Ok(state.jobs.cancel(&job_id))
+}
+
+/// Relatório de um job em andamento ou concluído recentemente
+#[tauri::command]
+pub async fn get_job_report(
+    job_id: String,
+    state: tauri::State<'_, crate::AppState>,
+) -> Result<Option<JobReport>, String> {
+    Ok(state.jobs.report(&job_id))
+}
+
+/// Jobs em andamento e concluídos recentemente
+#[tauri::command]
+pub async fn list_jobs(state: tauri::State<'_, crate::AppState>) -> Result<Vec<JobReport>, String> {
+    Ok(state.jobs.list())
+}
+
+fn emit_progress(app_handle: &AppHandle, run: &JobRun, total: usize, item: &str) {
+    if let Err(e) = app_handle.emit("job-progress", run.progress(total, item)) {
+        log::error!("Failed to emit job progress: {}", e);
+    }
+}
+
+fn emit_finished(app_handle: &AppHandle, report: JobReport) {
+    if let Err(e) = app_handle.emit("job-finished", report) {
+        log::error!("Failed to emit job report: {}", e);
+    }
+}
@@ -4,10 +4,13 @@ pub mod cache_system;
 pub mod connections;
 pub mod directories;
 pub mod game_lookup;
+pub mod history;
+pub mod jobs;
 pub mod language;
 pub mod monitoring;
 pub mod retro_achievements;
 pub mod settings;
+pub mod stats;
 pub mod steam;
 pub mod ui;
 
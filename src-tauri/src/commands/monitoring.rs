@@ -1,6 +1,8 @@
 use super::directories::build_default_directory_configs;
 use super::settings::{load_settings, save_settings};
-use crate::models::{DirectoryConfig, DirectoryDetectionPreset, GameAchievements};
+use crate::models::{
+    DirectoryConfig, DirectoryDetectionPreset, DirectoryHealth, GameAchievements, WatchMode,
+};
 use serde_json::Value;
 use std::time::UNIX_EPOCH;
 use tauri::AppHandle;
@@ -96,6 +98,7 @@ pub async fn add_monitored_directory(
                     enabled: true,
                     is_default: false,
                     detection_preset: preset,
+                    watch_mode: WatchMode::Auto,
                 });
                 monitor.set_directories(configs.clone());
                 monitor.restart_monitoring().map_err(|e| e.to_string())?;
@@ -159,6 +162,65 @@ pub async fn toggle_monitored_directory(
     Ok(current_directories)
 }
 
+/// Define se um diretório usa watcher nativo, polling ou escolha automática
+#[tauri::command]
+pub async fn set_directory_watch_mode(
+    path: String,
+    mode: WatchMode,
+    state: tauri::State<'_, crate::AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<DirectoryHealth>, String> {
+    let (configs, health) = {
+        let mut monitor_lock = state.monitor.lock().map_err(|e| e.to_string())?;
+        let monitor = monitor_lock
+            .as_mut()
+            .ok_or_else(|| "Monitor not initialized".to_string())?;
+
+        let mut configs = monitor.get_directories();
+        let config = configs
+            .iter_mut()
+            .find(|c| c.path == path)
+            .ok_or_else(|| format!("Directory is not monitored: {}", path))?;
+        config.watch_mode = mode;
+        monitor.set_directories(configs.clone());
+        monitor.restart_monitoring().map_err(|e| e.to_string())?;
+        (configs, monitor.get_health())
+    };
+
+    let mut settings = load_settings(app_handle.clone())
+        .await
+        .unwrap_or(serde_json::json!({}));
+    settings["monitoredConfigs"] = serde_json::to_value(&configs).map_err(|e| e.to_string())?;
+    let _ = save_settings(settings, app_handle).await;
+
+    Ok(health)
+}
+
+/// Situação do monitoramento de cada diretório (nativo, polling, ausente...)
+#[tauri::command]
+pub async fn get_monitor_health(
+    state: tauri::State<'_, crate::AppState>,
+) -> Result<Vec<DirectoryHealth>, String> {
+    let monitor_lock = state.monitor.lock().map_err(|e| e.to_string())?;
+    match &*monitor_lock {
+        Some(monitor) => Ok(monitor.get_health()),
+        None => Err("Monitor not initialized".to_string()),
+    }
+}
+
+/// Se o monitoramento está pausado
+#[tauri::command]
+pub async fn get_monitoring_paused(app_handle: AppHandle) -> Result<bool, String> {
+    Ok(crate::background::is_monitoring_paused(&app_handle))
+}
+
+/// Pausa ou retoma o monitoramento de arquivos
+#[tauri::command]
+pub async fn set_monitoring_paused(paused: bool, app_handle: AppHandle) -> Result<bool, String> {
+    crate::background::set_monitoring_paused(&app_handle, paused)?;
+    Ok(paused)
+}
+
 /// Solicita os achievements atuais
 #[tauri::command]
 pub async fn request_achievements(
@@ -1,3 +1,5 @@
+use crate::background::{self, BackgroundSettings, BACKGROUND_MODE_KEY, LAUNCH_AT_LOGIN_KEY};
+use crate::monitor::notifications::{muted_games, MUTED_GAMES_KEY};
 use crate::utils::settings::{load_settings_value, merge_settings};
 use serde_json::Value;
 use tauri::AppHandle;
@@ -8,6 +10,52 @@ pub async fn save_settings(settings: Value, app_handle: AppHandle) -> Result<(),
     merge_settings(&app_handle, &settings)
 }
 
+/// Silencia (ou não) as notificações de desbloqueio de um jogo e devolve a
+/// lista atualizada de jogos silenciados
+#[tauri::command]
+pub async fn set_game_notifications_muted(
+    game_id: String,
+    muted: bool,
+    app_handle: AppHandle,
+) -> Result<Vec<String>, String> {
+    let mut muted_ids = muted_games(&load_settings_value(&app_handle)?);
+    muted_ids.retain(|id| *id != game_id);
+    if muted {
+        muted_ids.push(game_id);
+    }
+
+    merge_settings(
+        &app_handle,
+        &serde_json::json!({ MUTED_GAMES_KEY: muted_ids }),
+    )?;
+    Ok(muted_ids)
+}
+
+/// Configurações do modo em segundo plano
+#[tauri::command]
+pub async fn get_background_settings(app_handle: AppHandle) -> Result<BackgroundSettings, String> {
+    Ok(BackgroundSettings::from_settings(&load_settings_value(
+        &app_handle,
+    )?))
+}
+
+/// Liga ou desliga a permanência na bandeja e o início com o sistema
+#[tauri::command]
+pub async fn set_background_settings(
+    settings: BackgroundSettings,
+    app_handle: AppHandle,
+) -> Result<BackgroundSettings, String> {
+    background::apply_settings(&app_handle, settings)?;
+    merge_settings(
+        &app_handle,
+        &serde_json::json!({
+            BACKGROUND_MODE_KEY: settings.background_mode,
+            LAUNCH_AT_LOGIN_KEY: settings.launch_at_login,
+        }),
+    )?;
+    Ok(settings)
+}
+
 /// Carrega configurações
 #[tauri::command]
 pub async fn load_settings(app_handle: AppHandle) -> Result<Value, String> {
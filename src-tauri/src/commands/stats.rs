@@ -0,0 +1,162 @@
+use crate::integrations::steam::{LocalSteamState, SteamMonitor};
+use crate::models::{GameStats, UserStat};
+use crate::parser::expand_path;
+use crate::stats::{StatsParser, StatsWriter};
+use tauri::State;
+
+/// Obtém as estatísticas de usuário de um jogo.
+///
+/// Para `steam://`, o Steamworks não lista os nomes disponíveis: apenas os
+/// stats em `names` são consultados. Sem `names` ou sem Steamworks, valem os
+/// `UserGameStats` em cache do cliente Steam.
+#[tauri::command]
+pub async fn get_game_stats(
+    game_id: String,
+    path: String,
+    names: Option<Vec<String>>,
+    state: State<'_, crate::AppState>,
+) -> Result<GameStats, String> {
+    if is_steam_path(&path) {
+        let names = names.unwrap_or_default();
+        let steamworks = (!names.is_empty()).then(|| {
+            with_steam_app(&state, &game_id, |steam_monitor| {
+                Ok(names
+                    .iter()
+                    .filter_map(|name| {
+                        steam_monitor.get_stat(name).ok().map(|value| UserStat {
+                            name: name.clone(),
+                            value,
+                        })
+                    })
+                    .collect())
+            })
+        });
+        let stats = match steamworks {
+            Some(Ok(stats)) => stats,
+            steamworks => match read_local_steam_stats(&game_id) {
+                Some(stats) => stats
+                    .into_iter()
+                    .filter(|stat| names.is_empty() || names.contains(&stat.name))
+                    .collect(),
+                None => steamworks.transpose()?.unwrap_or_default(),
+            },
+        };
+
+        return Ok(GameStats {
+            game_id,
+            stats,
+            directory: path,
+            source_path: None,
+        });
+    }
+
+    let game_dir = expand_path(&path).join(&game_id);
+    let location = StatsParser::find_stats_location(&game_dir);
+    let stats = match &location {
+        Some(location) => {
+            StatsParser::read_stats(location, &StatsParser::schema_stat_types(&game_id))
+                .map_err(|e| e.to_string())?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(GameStats {
+        game_id,
+        stats,
+        directory: path,
+        source_path: location.map(|l| l.path().to_string_lossy().to_string()),
+    })
+}
+
+/// Grava estatísticas de usuário; stats não informados permanecem intactos.
+#[tauri::command]
+pub async fn set_game_stats(
+    game_id: String,
+    path: String,
+    stats: Vec<UserStat>,
+    state: State<'_, crate::AppState>,
+) -> Result<GameStats, String> {
+    if is_steam_path(&path) {
+        with_steam_app(&state, &game_id, |steam_monitor| {
+            steam_monitor.set_stats(&stats).map_err(|e| e.to_string())
+        })?;
+
+        return Ok(GameStats {
+            game_id,
+            stats,
+            directory: path,
+            source_path: None,
+        });
+    }
+
+    let game_dir = expand_path(&path).join(&game_id);
+    let location = StatsParser::stats_location_for_game(&game_dir);
+    StatsWriter::write_stats(&location, &stats).map_err(|e| e.to_string())?;
+
+    log::info!(
+        "Stats updated for game {} at {}",
+        game_id,
+        location.path().display()
+    );
+
+    Ok(GameStats {
+        game_id,
+        stats: StatsParser::read_stats(&location, &StatsParser::schema_stat_types(&game_id))
+            .map_err(|e| e.to_string())?,
+        directory: path,
+        source_path: Some(location.path().to_string_lossy().to_string()),
+    })
+}
+
+/// Stats do `UserGameStats` em cache do cliente Steam, sem Steamworks.
+fn read_local_steam_stats(game_id: &str) -> Option<Vec<UserStat>> {
+    let app_id = game_id.parse::<u32>().ok()?;
+    match LocalSteamState::load(app_id) {
+        Ok(state) => state.map(|state| state.stats),
+        Err(e) => {
+            log::warn!(
+                "Failed to read local Steam stats for game {}: {}",
+                game_id,
+                e
+            );
+            None
+        }
+    }
+}
+
+fn is_steam_path(path: &str) -> bool {
+    path.trim_start().starts_with("steam://")
+}
+
+/// Inicializa o Steamworks no app do jogo, executa `action` e encerra.
+pub(crate) fn with_steam_app<T>(
+    state: &State<'_, crate::AppState>,
+    game_id: &str,
+    action: impl FnOnce(&SteamMonitor) -> Result<T, String>,
+) -> Result<T, String> {
+    let app_id = game_id
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid Steam AppID: {}", game_id))?;
+
+    let steam_lock = state.steam_monitor.lock().map_err(|e| e.to_string())?;
+    let steam_monitor = steam_lock
+        .as_ref()
+        .ok_or_else(|| "Steam monitor not initialized".to_string())?;
+
+    let result = (|| -> Result<T, String> {
+        steam_monitor.initialize().map_err(|e| e.to_string())?;
+        if !steam_monitor.is_enabled() {
+            return Err("Steam integration not available or Steam not running".to_string());
+        }
+
+        steam_monitor
+            .switch_app_id(app_id)
+            .map_err(|e| e.to_string())?;
+
+        action(steam_monitor)
+    })();
+
+    let _ = steam_monitor.shutdown();
+
+    result
+}
@@ -2,7 +2,7 @@ use super::language::{map_ui_language_to_hydra_lang, map_ui_language_to_steam_st
 use super::settings::{load_settings, save_settings};
 use crate::integrations::hydra::HydraApi;
 use crate::integrations::steam::SteamWebApi;
-use crate::integrations::steam::{SteamAchievementData, SteamGame};
+use crate::integrations::steam::{LocalSteamState, SteamAchievementData, SteamGame};
 use crate::utils::CacheManager;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -728,6 +728,12 @@ pub async fn get_steam_game_achievements(
     Ok(achievements)
 }
 
+/// Conquistas e stats do jogador lidos do cache local do cliente Steam, sem Steamworks
+#[tauri::command]
+pub async fn get_steam_local_state(app_id: u32) -> Result<Option<LocalSteamState>, String> {
+    LocalSteamState::load(app_id).map_err(|e| e.to_string())
+}
+
 /// Define o estado de uma conquista Steam
 #[tauri::command]
 pub async fn set_steam_achievement(
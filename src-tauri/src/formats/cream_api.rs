@@ -0,0 +1,104 @@
+//! Formato CreamAPI.
+//!
+//! ```ini
+//! [AchievementName]
+//! achieved=true
+//! unlocktime=1234567890
+//! ```
+
+use super::report::ParseReport;
+use super::{ini, read_ini_document, write_text, AchievementFormat, BasePathVar};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::path::Path;
+
+pub struct CreamApi;
+
+impl AchievementFormat for CreamApi {
+    fn cracker(&self) -> Cracker {
+        Cracker::CreamApi
+    }
+
+    fn display_name(&self) -> &'static str {
+        "CreamAPI"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let sections = ini::parse_sections_reported(content, report);
+        let mut achievements = Vec::new();
+
+        for section in sections {
+            let name = section.name;
+            let mut achieved = false;
+            let mut unlock_time: i64 = 0;
+
+            for pair in &section.pairs {
+                match pair.key.as_str() {
+                    "achieved" => achieved = ini::bool_value(pair, &name, report),
+                    "unlocktime" => {
+                        unlock_time = ini::number_value(pair, &name, report).unwrap_or(0)
+                    }
+                    _ => {}
+                }
+            }
+
+            achievements.push(AchievementEntry {
+                name,
+                achieved,
+                unlock_time,
+                cur_progress: None,
+                max_progress: None,
+            });
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut doc = read_ini_document(path)?;
+        for ach in achievements {
+            doc.set(
+                &ach.name,
+                "achieved",
+                if ach.achieved { "true" } else { "false" },
+            );
+            doc.set(&ach.name, "unlocktime", &ach.unlock_time.to_string());
+        }
+
+        write_text(path, &doc.to_string(), "CreamAPI")
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["stats/CreamAPI.Achievements.cfg"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/creamapi/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["CreamAPI.Achievements.cfg"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[(
+            BasePathVar::AppData,
+            "CreamAPI/<objectId>/stats/CreamAPI.Achievements.cfg",
+        )]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let sections = ini::parse_sections(content);
+        let ratio = ini::section_ratio(&sections, |_, pairs| {
+            ini::value_of(pairs, "achieved")
+                .is_some_and(|v| v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("false"))
+                && ini::value_of(pairs, "unlocktime").is_some()
+        });
+        0.9 * ratio
+    }
+}
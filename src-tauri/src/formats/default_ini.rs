@@ -0,0 +1,344 @@
+//! Formato INI padrão: CODEX, RUNE, RLE e SmartSteamEmu.
+//!
+//! ```ini
+//! [AchievementName]
+//! Achieved=1
+//! CurProgress=0
+//! MaxProgress=0
+//! UnlockTime=1234567890
+//! ```
+//!
+//! `CurProgress`/`MaxProgress` são opcionais.
+
+use super::report::ParseReport;
+use super::{ini, read_ini_document, write_text, AchievementFormat, BasePathVar};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::path::Path;
+
+pub struct Codex;
+pub struct Rune;
+pub struct Rle;
+pub struct SmartSteamEmu;
+
+/// Seção de índice mantida por alguns emuladores; não é uma conquista.
+const INDEX_SECTION: &str = "SteamAchievements";
+
+/// Parser padrão compartilhado pelos crackers deste módulo.
+pub fn parse(content: &str, report: &mut ParseReport) -> Result<Vec<AchievementEntry>> {
+    let sections = ini::parse_sections_reported(content, report);
+    let mut achievements = Vec::new();
+
+    for section in sections {
+        if section.name == INDEX_SECTION {
+            continue;
+        }
+
+        let name = section.name;
+        let mut achieved = None;
+        let mut unlock_time: i64 = 0;
+        let mut cur_progress = None;
+        let mut max_progress = None;
+
+        for pair in &section.pairs {
+            match pair.key.as_str() {
+                "Achieved" => {
+                    achieved = Some(match pair.value.as_str() {
+                        "1" => true,
+                        "0" => false,
+                        _ => {
+                            report.warning(
+                                Some(pair.line),
+                                &pair.raw,
+                                "Achieved must be 0 or 1; treated as locked",
+                                Some(&name),
+                            );
+                            false
+                        }
+                    })
+                }
+                "UnlockTime" => unlock_time = ini::number_value(pair, &name, report).unwrap_or(0),
+                "CurProgress" => cur_progress = ini::number_value(pair, &name, report),
+                "MaxProgress" => max_progress = ini::number_value(pair, &name, report),
+                _ => {}
+            }
+        }
+
+        if achieved.is_none() {
+            report.warning(
+                Some(section.line),
+                format!("[{}]", name),
+                "Section has no Achieved key; treated as locked",
+                Some(&name),
+            );
+        }
+
+        achievements.push(AchievementEntry {
+            name,
+            achieved: achieved.unwrap_or(false),
+            unlock_time,
+            cur_progress,
+            max_progress,
+        });
+    }
+
+    Ok(achievements)
+}
+
+/// Writer padrão compartilhado pelos crackers deste módulo.
+pub fn write(file_path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+    let mut doc = read_ini_document(file_path)?;
+    for ach in achievements {
+        doc.set(&ach.name, "Achieved", if ach.achieved { "1" } else { "0" });
+        // Sem progresso informado, mantém os valores já gravados
+        if let Some(cur_progress) = ach.cur_progress {
+            doc.set(&ach.name, "CurProgress", &cur_progress.to_string());
+        }
+        if let Some(max_progress) = ach.max_progress {
+            doc.set(&ach.name, "MaxProgress", &max_progress.to_string());
+        }
+        doc.set(&ach.name, "UnlockTime", &ach.unlock_time.to_string());
+        register_in_index(&mut doc, &ach.name);
+    }
+
+    write_text(file_path, &doc.to_string(), "INI")
+}
+
+/// Adiciona a conquista ao índice `[SteamAchievements]`, se o arquivo tiver um.
+///
+/// ```ini
+/// [SteamAchievements]
+/// Count=2
+/// 00000=ACH_ONE
+/// 00001=ACH_TWO
+/// ```
+fn register_in_index(doc: &mut ini::IniDocument, name: &str) {
+    if !doc.has_section(INDEX_SECTION) {
+        return;
+    }
+
+    let entries: Vec<(String, String)> = doc
+        .pairs(INDEX_SECTION)
+        .into_iter()
+        .filter(|(key, _)| *key != "Count")
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    if entries.iter().any(|(_, value)| value == name) {
+        return;
+    }
+
+    let count = entries.len() + 1;
+    doc.set(INDEX_SECTION, &format!("{:05}", entries.len()), name);
+    doc.set(INDEX_SECTION, "Count", &count.to_string());
+}
+
+/// Pontuação compartilhada: seções com `Achieved` (0/1) e `UnlockTime` numérico.
+pub fn sniff(content: &str) -> f32 {
+    let sections = ini::parse_sections(content);
+    let full = ini::section_ratio(&sections, |_, pairs| {
+        matches!(ini::value_of(pairs, "Achieved"), Some("0" | "1"))
+            && ini::value_of(pairs, "UnlockTime").is_some_and(is_unix_time)
+    });
+    let partial = ini::section_ratio(&sections, |_, pairs| {
+        matches!(ini::value_of(pairs, "Achieved"), Some("0" | "1"))
+    });
+    (0.9 * full).max(0.6 * partial)
+}
+
+fn is_unix_time(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+}
+
+impl AchievementFormat for Codex {
+    fn cracker(&self) -> Cracker {
+        Cracker::Codex
+    }
+
+    fn display_name(&self) -> &'static str {
+        "CODEX"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        parse(content, report)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        write(path, achievements)
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["achievements.ini", "Achievements.ini"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/codex/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.ini"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[
+            (
+                BasePathVar::PublicDocuments,
+                "Steam/CODEX/<objectId>/achievements.ini",
+            ),
+            (
+                BasePathVar::AppData,
+                "Steam/CODEX/<objectId>/achievements.ini",
+            ),
+        ]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        sniff(content)
+    }
+}
+
+impl AchievementFormat for Rune {
+    fn cracker(&self) -> Cracker {
+        Cracker::Rune
+    }
+
+    fn display_name(&self) -> &'static str {
+        "RUNE"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        parse(content, report)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        write(path, achievements)
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["achievements.ini", "Achievements.ini"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/rune/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.ini"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[(
+            BasePathVar::PublicDocuments,
+            "Steam/RUNE/<objectId>/achievements.ini",
+        )]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        sniff(content)
+    }
+}
+
+impl AchievementFormat for Rle {
+    fn cracker(&self) -> Cracker {
+        Cracker::Rle
+    }
+
+    fn display_name(&self) -> &'static str {
+        "RLE"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        parse(content, report)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        write(path, achievements)
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["achievements.ini", "Achievements.ini"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/rle/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.ini"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[
+            (BasePathVar::AppData, "RLE/<objectId>/achievements.ini"),
+            (BasePathVar::AppData, "RLE/<objectId>/Achievements.ini"),
+        ]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        sniff(content)
+    }
+}
+
+impl AchievementFormat for SmartSteamEmu {
+    fn cracker(&self) -> Cracker {
+        Cracker::SmartSteamEmu
+    }
+
+    fn display_name(&self) -> &'static str {
+        "SmartSteamEmu"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        parse(content, report)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        write(path, achievements)
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &[
+            "User/Achievements.ini",
+            "achievements.ini",
+            "Achievements.ini",
+        ]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/smartsteamemu/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.ini"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[(
+            BasePathVar::AppData,
+            "SmartSteamEmu/<objectId>/User/Achievements.ini",
+        )]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        sniff(content)
+    }
+}
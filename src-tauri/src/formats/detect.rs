@@ -0,0 +1,175 @@
+//! Detecção de formato por conteúdo, com pontuação de confiança.
+//!
+//! Combina três sinais por formato registrado:
+//! - conteúdo do arquivo (`AchievementFormat::sniff`)
+//! - assinatura do caminho (`path_signatures`)
+//! - nome do arquivo (`candidate_files`)
+
+use super::{read_text, registry, AchievementFormat};
+use crate::models::Cracker;
+use serde::Serialize;
+use std::path::Path;
+
+const CONTENT_WEIGHT: f32 = 0.7;
+const PATH_WEIGHT: f32 = 0.2;
+const FILE_NAME_WEIGHT: f32 = 0.1;
+
+/// Candidato de formato com confiança entre 0.0 e 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DetectionCandidate {
+    pub cracker: Cracker,
+    pub confidence: f32,
+}
+
+/// Ordena os formatos registrados pela confiança para o arquivo informado.
+///
+/// Diretórios são tratados como FLT. Formatos sem nenhum sinal são omitidos;
+/// empates mantêm a ordem do registro.
+pub fn rank_file(file_path: &Path) -> Vec<DetectionCandidate> {
+    if file_path.is_dir() {
+        return vec![DetectionCandidate {
+            cracker: Cracker::Flt,
+            confidence: 1.0,
+        }];
+    }
+
+    let content = read_text(file_path).unwrap_or_default();
+    rank_content(&content, file_path)
+}
+
+/// Ordena os formatos para um conteúdo já lido. `file_path` fornece os sinais
+/// de caminho e nome de arquivo.
+pub fn rank_content(content: &str, file_path: &Path) -> Vec<DetectionCandidate> {
+    let normalized_path = file_path
+        .to_string_lossy()
+        .replace('\\', "/")
+        .to_lowercase();
+    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+    let mut candidates: Vec<DetectionCandidate> = registry()
+        .iter()
+        .map(|format| DetectionCandidate {
+            cracker: format.cracker(),
+            confidence: score(*format, content, &normalized_path, file_name),
+        })
+        .filter(|candidate| candidate.confidence > 0.0)
+        .collect();
+
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+/// Retorna o cracker mais provável, se algum sinal foi encontrado.
+pub fn best_match(file_path: &Path) -> Option<Cracker> {
+    rank_file(file_path)
+        .first()
+        .map(|candidate| candidate.cracker)
+}
+
+fn score(
+    format: &dyn AchievementFormat,
+    content: &str,
+    normalized_path: &str,
+    file_name: &str,
+) -> f32 {
+    let content_score = format.sniff(content).clamp(0.0, 1.0);
+    let path_score = if format
+        .path_signatures()
+        .iter()
+        .any(|signature| normalized_path.contains(signature))
+    {
+        1.0
+    } else {
+        0.0
+    };
+    let file_name_score = if !file_name.is_empty()
+        && format.candidate_files().iter().any(|candidate| {
+            candidate
+                .rsplit('/')
+                .next()
+                .is_some_and(|name| name.eq_ignore_ascii_case(file_name))
+        }) {
+        1.0
+    } else {
+        0.0
+    };
+
+    content_score * CONTENT_WEIGHT + path_score * PATH_WEIGHT + file_name_score * FILE_NAME_WEIGHT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn top(content: &str, path: &str) -> Cracker {
+        rank_content(content, &PathBuf::from(path))
+            .first()
+            .map(|candidate| candidate.cracker)
+            .expect("at least one candidate")
+    }
+
+    #[test]
+    fn rld_hex_values_win_over_codex_in_custom_directories() {
+        let content = "[ACH_ONE]\nState=01000000\nTime=60E3A458\n";
+        assert_eq!(
+            top(content, "/games/custom/123/achievements.ini"),
+            Cracker::Rld
+        );
+    }
+
+    #[test]
+    fn path_breaks_ties_between_identical_ini_layouts() {
+        let content = "[ACH_ONE]\nAchieved=1\nUnlockTime=1700000000\n";
+        assert_eq!(top(content, "/custom/123/achievements.ini"), Cracker::Codex);
+        assert_eq!(
+            top(content, "/Public/Documents/Steam/RUNE/123/achievements.ini"),
+            Cracker::Rune
+        );
+    }
+
+    #[test]
+    fn recognizes_section_and_value_shapes() {
+        assert_eq!(
+            top("[Achievements]\nACH=1@1700000000@ACH\n", "/x/achiev.ini"),
+            Cracker::Skidrow
+        );
+        assert_eq!(
+            top(
+                "[State]\nACH=0101\n\n[Time]\nACH=60E3A458\n",
+                "/x/stats.ini"
+            ),
+            Cracker::ThreeDm
+        );
+        assert_eq!(
+            top("[ACH]\nachieved=true\nunlocktime=1700000000\n", "/x/a.cfg"),
+            Cracker::CreamApi
+        );
+        assert_eq!(
+            top("[ACH]\nachieved=true\ntimestamp=1700000000\n", "/x/a.ini"),
+            Cracker::OnlineFix
+        );
+        assert_eq!(
+            top("ACH_ONE 1 1700000000\n", "/x/achievement"),
+            Cracker::Razor1911
+        );
+    }
+
+    #[test]
+    fn recognizes_json_shapes() {
+        assert_eq!(
+            top(
+                r#"{"ACH":{"earned":true,"earned_time":1700000000}}"#,
+                "/x/data.json"
+            ),
+            Cracker::Goldberg
+        );
+        assert_eq!(
+            top(
+                r#"[{"strID":"ACH","bAchieved":true,"rtUnlocked":1}]"#,
+                "/x/data.json"
+            ),
+            Cracker::SteamCache
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Codificação de texto dos arquivos de conquista.
+//!
+//! Alguns emuladores e editores do Windows gravam UTF-16LE (com ou sem BOM), e
+//! nomes em cirílico/CJK às vezes chegam em code pages legadas
+//! (Windows-1251, Shift-JIS...). A leitura detecta a codificação e a escrita
+//! devolve o arquivo na mesma codificação em que foi lido.
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+/// Codificação detectada de um arquivo de texto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextEncoding {
+    encoding: &'static Encoding,
+    bom: bool,
+}
+
+impl Default for TextEncoding {
+    fn default() -> Self {
+        Self::UTF8
+    }
+}
+
+impl TextEncoding {
+    /// UTF-8 sem BOM, usado para arquivos novos.
+    pub const UTF8: Self = Self {
+        encoding: UTF_8,
+        bom: false,
+    };
+
+    /// Nome WHATWG da codificação (ex.: `UTF-16LE`, `windows-1251`).
+    pub fn name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    pub fn is_utf8(&self) -> bool {
+        self.encoding == UTF_8
+    }
+
+    /// Detecta a codificação e decodifica o conteúdo. O BOM não faz parte do texto.
+    pub fn decode(bytes: &[u8]) -> (String, Self) {
+        let detected = Self::detect(bytes);
+        let body = if detected.bom {
+            &bytes[bom_len(detected.encoding)..]
+        } else {
+            bytes
+        };
+        let (text, _) = detected.encoding.decode_without_bom_handling(body);
+        (text.into_owned(), detected)
+    }
+
+    /// Codifica o texto nesta codificação, repondo o BOM original.
+    ///
+    /// Se algum caractere não existir na code page legada, grava em UTF-8 para
+    /// não perder o nome da conquista.
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let text = text.trim_start_matches('\u{FEFF}');
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+
+        if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+            let little_endian = self.encoding == UTF_16LE;
+            if self.bom {
+                bytes.extend_from_slice(if little_endian {
+                    &[0xFF, 0xFE]
+                } else {
+                    &[0xFE, 0xFF]
+                });
+            }
+            for unit in text.encode_utf16() {
+                bytes.extend_from_slice(&if little_endian {
+                    unit.to_le_bytes()
+                } else {
+                    unit.to_be_bytes()
+                });
+            }
+            return bytes;
+        }
+
+        if self.bom && self.encoding == UTF_8 {
+            bytes.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+        }
+
+        let (encoded, _, unmappable) = self.encoding.encode(text);
+        if unmappable {
+            log::warn!(
+                "Text has characters not representable in {}; writing UTF-8 instead",
+                self.name()
+            );
+            return text.as_bytes().to_vec();
+        }
+        bytes.extend_from_slice(&encoded);
+        bytes
+    }
+
+    /// Detecta a codificação: BOM, UTF-16 sem BOM, UTF-8 válido e, por fim,
+    /// a code page legada mais provável.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+            return Self {
+                encoding,
+                bom: true,
+            };
+        }
+
+        if let Some(encoding) = utf16_without_bom(bytes) {
+            return Self {
+                encoding,
+                bom: false,
+            };
+        }
+
+        if std::str::from_utf8(bytes).is_ok() {
+            return Self::UTF8;
+        }
+
+        let mut detector = chardetng::EncodingDetector::new();
+        detector.feed(bytes, true);
+        Self {
+            encoding: detector.guess(None, false),
+            bom: false,
+        }
+    }
+}
+
+fn bom_len(encoding: &'static Encoding) -> usize {
+    if encoding == UTF_8 {
+        3
+    } else {
+        2
+    }
+}
+
+/// UTF-16 sem BOM: texto ASCII deixa um byte zero em cada par, sempre do
+/// mesmo lado.
+fn utf16_without_bom(bytes: &[u8]) -> Option<&'static Encoding> {
+    let sample = &bytes[..bytes.len().min(1024) & !1];
+    let pairs = sample.len() / 2;
+    if pairs < 2 {
+        return None;
+    }
+
+    let zeros_at = |offset: usize| {
+        sample
+            .chunks_exact(2)
+            .filter(|pair| pair[offset] == 0)
+            .count()
+    };
+    let (even_zeros, odd_zeros) = (zeros_at(0), zeros_at(1));
+
+    if odd_zeros * 10 >= pairs * 4 && even_zeros * 10 < pairs {
+        Some(UTF_16LE)
+    } else if even_zeros * 10 >= pairs * 4 && odd_zeros * 10 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_and_reemits_utf16_and_legacy_code_pages() {
+        let text = "[Достижение]\r\nAchieved=1\r\n";
+
+        let mut utf16_bom = vec![0xFF, 0xFE];
+        utf16_bom.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        let utf16_plain: Vec<u8> = "[ACH]\r\nAchieved=1\r\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let (cp1251, _, _) = encoding_rs::WINDOWS_1251
+            .encode("[Достижение_первое]\r\nAchieved=1\r\nUnlockTime=0\r\n");
+
+        for (bytes, name) in [
+            (utf16_bom, "UTF-16LE"),
+            (utf16_plain, "UTF-16LE"),
+            (cp1251.into_owned(), "windows-1251"),
+            (b"\xEF\xBB\xBF[ACH]\n".to_vec(), "UTF-8"),
+        ] {
+            let (decoded, encoding) = TextEncoding::decode(&bytes);
+            assert_eq!(encoding.name(), name, "{decoded}");
+            assert!(!decoded.starts_with('\u{FEFF}'));
+            assert_eq!(encoding.encode(&decoded), bytes, "{name}");
+        }
+    }
+}
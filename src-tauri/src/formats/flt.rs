@@ -0,0 +1,94 @@
+//! Formato FLT (diretório de stats).
+//!
+//! Cada arquivo no diretório = uma conquista desbloqueada.
+//! O nome do arquivo é o nome da conquista.
+
+use super::report::ParseReport;
+use super::{snapshot, AchievementFormat};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+pub struct Flt;
+
+impl AchievementFormat for Flt {
+    fn cracker(&self) -> Cracker {
+        Cracker::Flt
+    }
+
+    fn display_name(&self) -> &'static str {
+        "FLT"
+    }
+
+    fn parse_reported(
+        &self,
+        directory_path: &Path,
+        _report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        if !directory_path.exists() || !directory_path.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut achievements = Vec::new();
+
+        let entries = fs::read_dir(directory_path).with_context(|| {
+            format!("Failed to read FLT directory: {}", directory_path.display())
+        })?;
+
+        for entry in entries.flatten() {
+            if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                achievements.push(AchievementEntry {
+                    name,
+                    achieved: true,
+                    unlock_time: 0, // FLT não armazena timestamp
+                    cur_progress: None,
+                    max_progress: None,
+                });
+            }
+        }
+
+        Ok(achievements)
+    }
+
+    fn parse_content(
+        &self,
+        _content: &str,
+        path: &Path,
+        _report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        anyhow::bail!(
+            "FLT achievements are stored as a directory, not a file: {}",
+            path.display()
+        )
+    }
+
+    /// Cada conquista desbloqueada vira um arquivo vazio e cada uma marcada
+    /// como bloqueada perde o seu; arquivos de conquistas fora da lista ficam.
+    ///
+    /// A lista anterior fica em um snapshot `.ham-bak` ao lado do diretório.
+    fn write(&self, directory_path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut unlocked = snapshot::directory_listing(directory_path);
+        for ach in achievements {
+            if ach.achieved {
+                unlocked.insert(ach.name.clone());
+            } else {
+                unlocked.remove(&ach.name);
+            }
+        }
+        snapshot::set_directory_listing(directory_path, &unlocked).with_context(|| {
+            format!(
+                "Failed to write FLT directory: {}",
+                directory_path.display()
+            )
+        })?;
+
+        log::info!("FLT directory written: {}", directory_path.display());
+        Ok(())
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/flt/"]
+    }
+}
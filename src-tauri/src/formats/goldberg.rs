@@ -0,0 +1,304 @@
+//! Formato JSON do Goldberg/GSE e EMPRESS.
+//!
+//! ```json
+//! {
+//!   "AchievementName": {
+//!     "earned": true,
+//!     "earned_time": 1234567890
+//!   }
+//! }
+//! ```
+//! Também suporta formato de array.
+
+use super::report::ParseReport;
+use super::{read_text, write_text, AchievementFormat, BasePathVar};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+pub struct Goldberg;
+pub struct Empress;
+
+/// Parser compartilhado por Goldberg e EMPRESS.
+pub fn parse(
+    content: &str,
+    file_path: &Path,
+    report: &mut ParseReport,
+) -> Result<Vec<AchievementEntry>> {
+    let json: Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(e) => {
+            report.error(Some(e.line()), "", e.to_string(), None);
+            return Err(e)
+                .with_context(|| format!("Failed to parse JSON: {}", file_path.display()));
+        }
+    };
+
+    let mut achievements = Vec::new();
+
+    // Formato de objeto (mais comum)
+    if let Some(obj) = json.as_object() {
+        for (name, payload) in obj {
+            match payload.as_object() {
+                Some(payload_obj) => {
+                    achievements.push(entry_from_object(name, payload_obj, report))
+                }
+                None => report.warning(
+                    None,
+                    payload.to_string(),
+                    "Achievement entry is not an object",
+                    Some(name),
+                ),
+            }
+        }
+        return Ok(achievements);
+    }
+
+    // Formato de array
+    if let Some(arr) = json.as_array() {
+        for item in arr {
+            let Some(obj) = item.as_object() else {
+                report.warning(None, item.to_string(), "Array item is not an object", None);
+                continue;
+            };
+            let name = obj.get("name").and_then(|v| v.as_str()).unwrap_or("");
+            if name.is_empty() {
+                report.warning(None, item.to_string(), "Array item without a name", None);
+            }
+            achievements.push(entry_from_object(name, obj, report));
+        }
+        return Ok(achievements);
+    }
+
+    report.error(
+        Some(1),
+        "",
+        "Expected a JSON object or array at the root",
+        None,
+    );
+    Ok(achievements)
+}
+
+/// Monta uma conquista a partir de `{ earned, earned_time, progress, max_progress }`.
+fn entry_from_object(
+    name: &str,
+    obj: &Map<String, Value>,
+    report: &mut ParseReport,
+) -> AchievementEntry {
+    let achieved = match obj.get("earned") {
+        Some(Value::Bool(earned)) => *earned,
+        Some(other) => {
+            report.warning(
+                None,
+                other.to_string(),
+                "earned is not a boolean; treated as locked",
+                Some(name),
+            );
+            false
+        }
+        None => false,
+    };
+    let unlock_time = match obj.get("earned_time") {
+        Some(value) => value.as_i64().unwrap_or_else(|| {
+            report.error(
+                None,
+                value.to_string(),
+                "earned_time is not an integer",
+                Some(name),
+            );
+            0
+        }),
+        None => 0,
+    };
+
+    AchievementEntry {
+        name: name.to_string(),
+        achieved,
+        unlock_time,
+        cur_progress: progress_value(obj.get("progress")),
+        max_progress: progress_value(obj.get("max_progress")),
+    }
+}
+
+/// Lê `progress`/`max_progress`, que alguns forks gravam como float.
+fn progress_value(value: Option<&Value>) -> Option<u32> {
+    let value = value?;
+    value
+        .as_u64()
+        .or_else(|| value.as_f64().filter(|v| *v >= 0.0).map(|v| v as u64))
+        .map(|v| v.min(u32::MAX as u64) as u32)
+}
+
+/// Writer compartilhado por Goldberg e EMPRESS.
+///
+/// Preserva campos extras existentes no arquivo.
+pub fn write(file_path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+    let mut root_obj: Map<String, Value> = if file_path.exists() {
+        serde_json::from_str(&read_text(file_path)?).unwrap_or_default()
+    } else {
+        Map::new()
+    };
+
+    for ach in achievements {
+        let mut entry_obj = root_obj
+            .get(&ach.name)
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+
+        entry_obj.insert("earned".to_string(), Value::Bool(ach.achieved));
+        entry_obj.insert(
+            "earned_time".to_string(),
+            Value::from(if ach.achieved { ach.unlock_time } else { 0 }),
+        );
+        if let Some(cur_progress) = ach.cur_progress {
+            entry_obj.insert("progress".to_string(), Value::from(cur_progress));
+        }
+        if let Some(max_progress) = ach.max_progress {
+            entry_obj.insert("max_progress".to_string(), Value::from(max_progress));
+        }
+
+        root_obj.insert(ach.name.clone(), Value::Object(entry_obj));
+    }
+
+    let content = serde_json::to_string_pretty(&root_obj)
+        .with_context(|| format!("Failed to serialize JSON: {}", file_path.display()))?;
+
+    write_text(file_path, &content, "Goldberg JSON")
+}
+
+/// Pontuação compartilhada: objeto de objetos com `earned`, ou array com `name`/`earned`.
+pub fn sniff(content: &str) -> f32 {
+    let Ok(json) = serde_json::from_str::<Value>(content) else {
+        return 0.0;
+    };
+
+    if let Some(obj) = json.as_object() {
+        if obj.is_empty() {
+            return 0.3;
+        }
+        let matching = obj
+            .values()
+            .filter(|payload| payload.get("earned").is_some())
+            .count();
+        return 0.9 * matching as f32 / obj.len() as f32;
+    }
+
+    if let Some(arr) = json.as_array() {
+        if arr.is_empty() {
+            return 0.0;
+        }
+        let matching = arr
+            .iter()
+            .filter(|item| item.get("name").is_some() && item.get("earned").is_some())
+            .count();
+        return 0.8 * matching as f32 / arr.len() as f32;
+    }
+
+    0.0
+}
+
+impl AchievementFormat for Goldberg {
+    fn cracker(&self) -> Cracker {
+        Cracker::Goldberg
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Goldberg"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        parse(content, path, report)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        write(path, achievements)
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["achievements.json"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["goldberg steamemu saves", "gse saves", "goldberg_steamemu"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.json"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[
+            (
+                BasePathVar::AppData,
+                "Goldberg SteamEmu Saves/<objectId>/achievements.json",
+            ),
+            (
+                BasePathVar::AppData,
+                "GSE Saves/<objectId>/achievements.json",
+            ),
+        ]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        sniff(content)
+    }
+}
+
+impl AchievementFormat for Empress {
+    fn cracker(&self) -> Cracker {
+        Cracker::Empress
+    }
+
+    fn display_name(&self) -> &'static str {
+        "EMPRESS"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        parse(content, path, report)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        write(path, achievements)
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["achievements.json", "remote/<objectId>/achievements.json"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/empress/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.json"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[
+            (
+                BasePathVar::AppData,
+                "EMPRESS/remote/<objectId>/achievements.json",
+            ),
+            (
+                BasePathVar::PublicDocuments,
+                "EMPRESS/<objectId>/remote/<objectId>/achievements.json",
+            ),
+        ]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        sniff(content)
+    }
+}
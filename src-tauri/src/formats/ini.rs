@@ -0,0 +1,501 @@
+//! Leitura e edição de arquivos INI de conquista.
+//!
+//! [`parse_sections`] é a leitura simples usada pelos parsers. [`IniDocument`]
+//! é usado pelos writers: altera valores no lugar e devolve o resto do arquivo
+//! (chaves desconhecidas, comentários, ordem, quebras de linha) sem alteração.
+
+use super::report::ParseReport;
+use std::fmt;
+use std::str::FromStr;
+
+/// Seções de um INI como `(nome, pares chave/valor)`, na ordem do arquivo.
+pub type IniSections = Vec<(String, Vec<(String, String)>)>;
+
+/// Par chave/valor com a linha (1-based) e o texto original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniPair {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+    pub raw: String,
+}
+
+/// Seção com pares numerados, para parsers que geram [`ParseReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniSection {
+    pub name: String,
+    pub line: usize,
+    pub pairs: Vec<IniPair>,
+}
+
+/// Faz o parse de um arquivo INI manualmente, sem biblioteca externa.
+///
+/// - Remove BOM (0xFEFF) se presente
+/// - Ignora comentários (`###`, `#`, `;`)
+/// - Seções: `[NomeSeção]`
+/// - Chaves: `chave=valor`
+pub fn parse_sections(content: &str) -> IniSections {
+    parse_sections_reported(content, &mut ParseReport::new())
+        .into_iter()
+        .map(|section| {
+            let pairs = section
+                .pairs
+                .into_iter()
+                .map(|pair| (pair.key, pair.value))
+                .collect();
+            (section.name, pairs)
+        })
+        .collect()
+}
+
+/// Igual a [`parse_sections`], mantendo números de linha e registrando linhas
+/// ignoradas em `report`.
+pub fn parse_sections_reported(content: &str, report: &mut ParseReport) -> Vec<IniSection> {
+    let content = content.trim_start_matches('\u{FEFF}'); // Remove BOM
+    let mut sections: Vec<IniSection> = Vec::new();
+
+    for (index, raw) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = raw.trim();
+
+        // Ignora linhas em branco e comentários (###, #, ;)
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        // Seção
+        if line.starts_with('[') && line.ends_with(']') {
+            sections.push(IniSection {
+                name: line[1..line.len() - 1].to_string(),
+                line: line_number,
+                pairs: Vec::new(),
+            });
+            continue;
+        }
+
+        // Chave=Valor
+        if let Some((key, value)) = line.split_once('=') {
+            match sections.last_mut() {
+                Some(section) => section.pairs.push(IniPair {
+                    key: key.trim().to_string(),
+                    value: value.trim().to_string(),
+                    line: line_number,
+                    raw: raw.to_string(),
+                }),
+                None => report.warning(
+                    Some(line_number),
+                    raw,
+                    "Key outside of any section was ignored",
+                    None,
+                ),
+            }
+            continue;
+        }
+
+        report.info(Some(line_number), raw, "Unrecognized line was ignored");
+    }
+
+    // Seções sem nome não identificam nenhuma conquista
+    sections.retain(|section| !section.name.is_empty());
+    sections
+}
+
+/// Lê um número do par; se inválido, registra um erro e retorna `None`.
+pub fn number_value<T: FromStr>(
+    pair: &IniPair,
+    achievement: &str,
+    report: &mut ParseReport,
+) -> Option<T> {
+    match pair.value.parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            report.error(
+                Some(pair.line),
+                &pair.raw,
+                format!("Invalid {} value: {:?}", pair.key, pair.value),
+                Some(achievement),
+            );
+            None
+        }
+    }
+}
+
+/// Lê um uint32 little-endian em 8 dígitos hex (RLD!, 3DM).
+pub fn hex_value(pair: &IniPair, achievement: &str, report: &mut ParseReport) -> Option<u32> {
+    if !is_hex_of_len(&pair.value, 8) {
+        report.error(
+            Some(pair.line),
+            &pair.raw,
+            format!("{} must have 8 hex digits: {:?}", pair.key, pair.value),
+            Some(achievement),
+        );
+        return None;
+    }
+    super::hex_to_u32_le(&pair.value).ok()
+}
+
+/// Lê `true`/`false` (case-insensitive); outro valor vira `false` com aviso.
+pub fn bool_value(pair: &IniPair, achievement: &str, report: &mut ParseReport) -> bool {
+    if pair.value.eq_ignore_ascii_case("true") {
+        return true;
+    }
+    if !pair.value.eq_ignore_ascii_case("false") {
+        report.warning(
+            Some(pair.line),
+            &pair.raw,
+            format!("{} must be true or false; treated as locked", pair.key),
+            Some(achievement),
+        );
+    }
+    false
+}
+
+/// Fração (0.0 a 1.0) das seções que satisfazem `predicate`.
+pub fn section_ratio<F>(sections: &IniSections, predicate: F) -> f32
+where
+    F: Fn(&str, &[(String, String)]) -> bool,
+{
+    if sections.is_empty() {
+        return 0.0;
+    }
+
+    let matching = sections
+        .iter()
+        .filter(|(name, pairs)| predicate(name, pairs))
+        .count();
+    matching as f32 / sections.len() as f32
+}
+
+/// Retorna o valor de uma chave (comparação exata) dentro de uma seção.
+pub fn value_of<'a>(pairs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    pairs
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+/// Verifica se o valor é hexadecimal com exatamente `len` dígitos.
+pub fn is_hex_of_len(value: &str, len: usize) -> bool {
+    value.len() == len && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Linha de um [`IniDocument`], guardada com o texto original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IniLine {
+    /// `[Nome]`
+    Section { name: String, raw: String },
+    /// `chave=valor`. `prefix` vai até o início do valor, `suffix` é o que
+    /// sobra depois dele (espaços finais).
+    Pair {
+        key: String,
+        prefix: String,
+        value: String,
+        suffix: String,
+    },
+    /// Comentário, linha em branco ou qualquer texto não reconhecido.
+    Other(String),
+}
+
+/// Documento INI sem perdas: `IniDocument::parse(c).to_string() == c`.
+///
+/// Somente os valores alterados por [`IniDocument::set`] são reescritos; chaves
+/// novas entram no fim da seção e seções novas no fim do arquivo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniDocument {
+    bom: bool,
+    /// Linhas sem o terminador.
+    lines: Vec<IniLine>,
+    /// Terminador original de cada linha (vazio na última sem quebra).
+    endings: Vec<&'static str>,
+    newline: &'static str,
+    default_separator: &'static str,
+}
+
+impl Default for IniDocument {
+    fn default() -> Self {
+        Self::parse("")
+    }
+}
+
+impl IniDocument {
+    /// Faz o parse preservando todo o conteúdo original.
+    pub fn parse(content: &str) -> Self {
+        let bom = content.starts_with('\u{FEFF}');
+        let content = content.trim_start_matches('\u{FEFF}');
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        let mut lines = Vec::new();
+        let mut endings = Vec::new();
+        let mut rest = content;
+        while !rest.is_empty() {
+            let (line, ending, next) = match rest.find('\n') {
+                Some(pos) if rest[..pos].ends_with('\r') => {
+                    (&rest[..pos - 1], "\r\n", &rest[pos + 1..])
+                }
+                Some(pos) => (&rest[..pos], "\n", &rest[pos + 1..]),
+                None => (rest, "", ""),
+            };
+            lines.push(Self::classify(line));
+            endings.push(ending);
+            rest = next;
+        }
+
+        Self {
+            bom,
+            lines,
+            endings,
+            newline,
+            default_separator: "=",
+        }
+    }
+
+    fn classify(line: &str) -> IniLine {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') && trimmed.ends_with(']') && trimmed.len() >= 2 {
+            return IniLine::Section {
+                name: trimmed[1..trimmed.len() - 1].to_string(),
+                raw: line.to_string(),
+            };
+        }
+
+        if trimmed.starts_with('#') || trimmed.starts_with(';') {
+            return IniLine::Other(line.to_string());
+        }
+
+        if let Some(eq_pos) = line.find('=') {
+            let after_eq = &line[eq_pos + 1..];
+            let value_start = eq_pos + 1 + (after_eq.len() - after_eq.trim_start().len());
+            let value_end = value_start + line[value_start..].trim_end().len();
+            return IniLine::Pair {
+                key: line[..eq_pos].trim().to_string(),
+                prefix: line[..value_start].to_string(),
+                value: line[value_start..value_end].to_string(),
+                suffix: line[value_end..].to_string(),
+            };
+        }
+
+        IniLine::Other(line.to_string())
+    }
+
+    /// Nomes das seções, na ordem do arquivo.
+    pub fn section_names(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                IniLine::Section { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn has_section(&self, section: &str) -> bool {
+        self.section_range(section).is_some()
+    }
+
+    /// Pares chave/valor de uma seção, na ordem do arquivo.
+    pub fn pairs(&self, section: &str) -> Vec<(&str, &str)> {
+        let Some((start, end)) = self.section_range(section) else {
+            return Vec::new();
+        };
+
+        self.lines[start..end]
+            .iter()
+            .filter_map(|line| match line {
+                IniLine::Pair { key, value, .. } => Some((key.as_str(), value.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Valor de uma chave (comparação exata) na primeira seção com o nome.
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.pair_index(section, key)
+            .and_then(|index| match &self.lines[index] {
+                IniLine::Pair { value, .. } => Some(value.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Define um valor, mantendo o espaçamento da linha original.
+    ///
+    /// Cria a chave no fim da seção, ou a seção no fim do arquivo, se necessário.
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        if let Some(index) = self.pair_index(section, key) {
+            if let IniLine::Pair { value: current, .. } = &mut self.lines[index] {
+                *current = value.to_string();
+            }
+            return;
+        }
+
+        let (start, end) = match self.section_range(section) {
+            Some(range) => range,
+            None => {
+                self.push_section(section);
+                (self.lines.len(), self.lines.len())
+            }
+        };
+
+        let separator = self.separator();
+        // Depois do último par da seção, antes de comentários/linhas em branco finais
+        let insert_at = (start..end)
+            .rev()
+            .find(|&i| matches!(self.lines[i], IniLine::Pair { .. }))
+            .map(|i| i + 1)
+            .unwrap_or(start);
+
+        self.insert_line(
+            insert_at,
+            IniLine::Pair {
+                key: key.to_string(),
+                prefix: format!("{}{}", key, separator),
+                value: value.to_string(),
+                suffix: String::new(),
+            },
+        );
+    }
+
+    /// Separador usado em chaves novas quando o documento ainda não tem pares
+    /// (ex.: `" = "` no user_stats). Com pares existentes, o estilo deles vence.
+    pub fn with_default_separator(mut self, separator: &'static str) -> Self {
+        self.default_separator = separator;
+        self
+    }
+
+    fn separator(&self) -> String {
+        self.lines
+            .iter()
+            .find_map(|line| match line {
+                IniLine::Pair { key, prefix, .. } => prefix
+                    .trim_start()
+                    .strip_prefix(key.as_str())
+                    .map(str::to_string),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.default_separator.to_string())
+    }
+
+    /// Intervalo `[início, fim)` das linhas de conteúdo da primeira seção `section`.
+    fn section_range(&self, section: &str) -> Option<(usize, usize)> {
+        let header = self
+            .lines
+            .iter()
+            .position(|line| matches!(line, IniLine::Section { name, .. } if name == section))?;
+        let end = self.lines[header + 1..]
+            .iter()
+            .position(|line| matches!(line, IniLine::Section { .. }))
+            .map(|offset| header + 1 + offset)
+            .unwrap_or(self.lines.len());
+        Some((header + 1, end))
+    }
+
+    fn pair_index(&self, section: &str, key: &str) -> Option<usize> {
+        let (start, end) = self.section_range(section)?;
+        (start..end).find(|&i| matches!(&self.lines[i], IniLine::Pair { key: k, .. } if k == key))
+    }
+
+    fn push_section(&mut self, section: &str) {
+        // Separa da seção anterior com uma linha em branco, como os writers originais
+        if let Some(last) = self.endings.last_mut() {
+            if last.is_empty() {
+                *last = self.newline;
+            }
+        }
+        if matches!(self.lines.last(), Some(line) if !matches!(line, IniLine::Other(text) if text.trim().is_empty()))
+        {
+            self.lines.push(IniLine::Other(String::new()));
+            self.endings.push(self.newline);
+        }
+
+        self.lines.push(IniLine::Section {
+            name: section.to_string(),
+            raw: format!("[{}]", section),
+        });
+        self.endings.push(self.newline);
+    }
+
+    fn insert_line(&mut self, index: usize, line: IniLine) {
+        // A linha anterior pode ser a última do arquivo, sem quebra
+        if index > 0 && self.endings[index - 1].is_empty() {
+            self.endings[index - 1] = self.newline;
+        }
+        self.lines.insert(index, line);
+        self.endings.insert(index, self.newline);
+    }
+}
+
+impl fmt::Display for IniDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bom {
+            f.write_str("\u{FEFF}")?;
+        }
+        for (line, ending) in self.lines.iter().zip(&self.endings) {
+            match line {
+                IniLine::Section { raw, .. } | IniLine::Other(raw) => f.write_str(raw)?,
+                IniLine::Pair {
+                    prefix,
+                    value,
+                    suffix,
+                    ..
+                } => {
+                    f.write_str(prefix)?;
+                    f.write_str(value)?;
+                    f.write_str(suffix)?;
+                }
+            }
+            f.write_str(ending)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_documents_are_written_back_byte_for_byte() {
+        let samples = [
+            "\u{FEFF}### comment\r\n[SteamAchievements]\r\nCount=1\r\n00000=ACH\r\n\r\n[ACH]\r\nAchieved = 1 \r\n",
+            "[ACH]\nAchieved=0\nUnlockTime=0\n; trailing comment\nno equals sign",
+            "",
+        ];
+
+        for sample in samples {
+            assert_eq!(IniDocument::parse(sample).to_string(), sample);
+        }
+    }
+
+    #[test]
+    fn set_edits_in_place_and_keeps_unknown_keys() {
+        let mut doc = IniDocument::parse(
+            "[ACH]\r\nAchieved = 0\r\nCurProgress=3\r\nMaxProgress=10\r\nUnlockTime=0\r\n",
+        );
+        doc.set("ACH", "Achieved", "1");
+        doc.set("ACH", "UnlockTime", "1700000000");
+
+        assert_eq!(
+            doc.to_string(),
+            "[ACH]\r\nAchieved = 1\r\nCurProgress=3\r\nMaxProgress=10\r\nUnlockTime=1700000000\r\n"
+        );
+    }
+
+    #[test]
+    fn set_appends_missing_keys_and_sections() {
+        let mut doc = IniDocument::parse("[A]\nAchieved=1\n\n### keep\n[B]\nAchieved=0");
+        doc.set("A", "UnlockTime", "5");
+        doc.set("B", "UnlockTime", "6");
+        doc.set("C", "Achieved", "1");
+
+        assert_eq!(
+            doc.to_string(),
+            "[A]\nAchieved=1\nUnlockTime=5\n\n### keep\n[B]\nAchieved=0\nUnlockTime=6\n\n[C]\nAchieved=1\n"
+        );
+        assert_eq!(doc.section_names(), vec!["A", "B", "C"]);
+        assert_eq!(doc.get("B", "UnlockTime"), Some("6"));
+    }
+}
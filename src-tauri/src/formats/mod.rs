@@ -0,0 +1,509 @@
+//! Formatos de arquivo de conquista suportados.
+//!
+//! Cada emulador/cracker é descrito por uma implementação de [`AchievementFormat`]
+//! registrada em [`registry`]. Parser, writer, descoberta de arquivos, detecção
+//! por caminho e o monitor consultam o registro em vez de `match` espalhados.
+
+pub mod cream_api;
+pub mod default_ini;
+pub mod detect;
+pub mod encoding;
+pub mod flt;
+pub mod goldberg;
+pub mod ini;
+pub mod online_fix;
+pub mod razor1911;
+pub mod report;
+pub mod rld;
+pub mod skidrow;
+pub mod snapshot;
+pub mod steam_cache;
+pub mod three_dm;
+pub mod user_stats;
+
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::{Context, Result};
+use encoding::TextEncoding;
+use report::ParseReport;
+use std::fs;
+use std::path::Path;
+
+/// Variável de base para paths do Hydra (`%APPDATA%`, `%DOCUMENTS%`, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasePathVar {
+    AppData,
+    Documents,
+    PublicDocuments,
+    LocalAppData,
+    ProgramData,
+}
+
+/// Descrição completa de um formato de arquivo de conquista.
+///
+/// Um novo emulador precisa apenas de uma variante em [`Cracker`] e de um
+/// módulo que implemente esta trait e seja adicionado a [`registry`].
+pub trait AchievementFormat: Sync {
+    /// Cracker descrito por este formato.
+    fn cracker(&self) -> Cracker;
+
+    /// Nome legível do cracker.
+    fn display_name(&self) -> &'static str;
+
+    /// Faz o parse do arquivo (ou diretório) de conquistas.
+    ///
+    /// Valores inválidos são tolerados; o resumo vai para o log.
+    fn parse(&self, path: &Path) -> Result<Vec<AchievementEntry>> {
+        let mut report = ParseReport::new();
+        let achievements = self.parse_reported(path, &mut report)?;
+        report.log_summary(path);
+        Ok(achievements)
+    }
+
+    /// Faz o parse registrando cada valor descartado em `report`.
+    fn parse_reported(
+        &self,
+        path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let (content, encoding) = read_text_with_encoding(path)?;
+        if !encoding.is_utf8() {
+            report.info(None, "", format!("Decoded as {}", encoding.name()));
+        }
+        self.parse_content(&content, path, report)
+    }
+
+    /// Faz o parse do conteúdo já lido. `path` é usado apenas para mensagens de erro.
+    fn parse_content(
+        &self,
+        content: &str,
+        path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>>;
+
+    /// Escreve as conquistas no formato nativo do emulador.
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()>;
+
+    /// Se o HAM pode gravar neste formato.
+    fn is_writable(&self) -> bool {
+        true
+    }
+
+    /// Nomes de arquivo relativos ao diretório `<cracker>/<objectId>/`.
+    ///
+    /// Podem conter `<objectId>`. O primeiro item é usado ao criar um arquivo novo.
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Trechos do caminho (minúsculos, separador `/`) que identificam o cracker.
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Nomes de arquivo que disparam atualização no monitor (case-insensitive).
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Caminhos conforme documentação oficial do Hydra, como
+    /// (variável_base, padrão_de_arquivo com `<objectId>`).
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[]
+    }
+
+    /// Pontua (0.0 a 1.0) o quanto o conteúdo se parece com este formato.
+    ///
+    /// Usado por [`detect`] para identificar arquivos fora dos diretórios padrão.
+    fn sniff(&self, _content: &str) -> f32 {
+        0.0
+    }
+}
+
+/// Registro de todos os formatos, na ordem de prioridade de detecção.
+static REGISTRY: &[&dyn AchievementFormat] = &[
+    &default_ini::Codex,
+    &default_ini::Rune,
+    &online_fix::OnlineFix,
+    &goldberg::Goldberg,
+    &rld::Rld,
+    &goldberg::Empress,
+    &skidrow::Skidrow,
+    &cream_api::CreamApi,
+    &default_ini::SmartSteamEmu,
+    &default_ini::Rle,
+    &razor1911::Razor1911,
+    &user_stats::UserStats,
+    &three_dm::ThreeDm,
+    &flt::Flt,
+    &steam_cache::SteamCache,
+];
+
+/// Retorna todos os formatos registrados.
+pub fn registry() -> &'static [&'static dyn AchievementFormat] {
+    REGISTRY
+}
+
+/// Retorna o formato responsável por um cracker.
+pub fn format_for(cracker: Cracker) -> &'static dyn AchievementFormat {
+    REGISTRY
+        .iter()
+        .copied()
+        .find(|format| format.cracker() == cracker)
+        .unwrap_or(&default_ini::Codex)
+}
+
+/// Detecta o cracker pelas assinaturas de caminho registradas.
+pub fn detect_by_path(file_path: &str) -> Option<Cracker> {
+    let normalized = file_path.replace('\\', "/").to_lowercase();
+
+    REGISTRY
+        .iter()
+        .find(|format| {
+            format
+                .path_signatures()
+                .iter()
+                .any(|signature| normalized.contains(signature))
+        })
+        .map(|format| format.cracker())
+}
+
+/// Ordem em que o diretório de um jogo é sondado, a mesma de antes do
+/// registro: com mais de um arquivo presente, o primeiro da lista vence.
+const CANDIDATE_PRIORITY: &[&str] = &[
+    "achievements.ini",
+    "Achievements.ini",
+    "achievements.json",
+    "Stats/Achievements.ini",
+    "stats/achievements.ini",
+    "remote/<objectId>/achievements.json",
+    "SteamEmu/UserStats/achiev.ini",
+    "stats/CreamAPI.Achievements.cfg",
+    "User/Achievements.ini",
+    "achievement",
+];
+
+/// Todos os nomes de arquivo candidatos, sem repetição: os de
+/// [`CANDIDATE_PRIORITY`] primeiro, os demais na ordem do registro.
+pub fn all_candidate_files() -> Vec<&'static str> {
+    let mut candidates: Vec<&'static str> = CANDIDATE_PRIORITY.to_vec();
+    for format in REGISTRY {
+        for candidate in format.candidate_files() {
+            if !candidates.contains(candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
+}
+
+/// Verifica se um nome de arquivo deve ser observado pelo monitor.
+pub fn is_monitored_file_name(file_name: &str) -> bool {
+    REGISTRY.iter().any(|format| {
+        format
+            .monitor_patterns()
+            .iter()
+            .any(|pattern| file_name.eq_ignore_ascii_case(pattern))
+    })
+}
+
+// ── Utilitários compartilhados ──────────────────────────────────────
+
+/// Lê um arquivo de conquista como texto, em qualquer codificação suportada.
+pub(crate) fn read_text(file_path: &Path) -> Result<String> {
+    read_text_with_encoding(file_path).map(|(content, _)| content)
+}
+
+/// Lê um arquivo de conquista como texto, junto com a codificação detectada.
+pub(crate) fn read_text_with_encoding(file_path: &Path) -> Result<(String, TextEncoding)> {
+    let bytes = fs::read(file_path)
+        .with_context(|| format!("Failed to read achievement file: {}", file_path.display()))?;
+    Ok(TextEncoding::decode(&bytes))
+}
+
+/// Lê o INI existente como documento editável, ou um documento vazio se o
+/// arquivo ainda não existe.
+pub(crate) fn read_ini_document(file_path: &Path) -> Result<ini::IniDocument> {
+    if !file_path.exists() {
+        return Ok(ini::IniDocument::default());
+    }
+    Ok(ini::IniDocument::parse(&read_text(file_path)?))
+}
+
+/// Escreve um arquivo de conquista, criando o diretório pai se necessário.
+///
+/// `label` identifica o formato nas mensagens de log e erro. Um arquivo
+/// existente é regravado na codificação original; um novo sai em UTF-8. A
+/// escrita é atômica e guarda um snapshot do conteúdo anterior.
+pub(crate) fn write_text(file_path: &Path, content: &str, label: &str) -> Result<()> {
+    ensure_parent_dir(file_path)?;
+    let encoding = fs::read(file_path)
+        .map(|bytes| TextEncoding::detect(&bytes))
+        .unwrap_or_default();
+    snapshot::write_with_snapshot(file_path, &encoding.encode(content))
+        .with_context(|| format!("Failed to write {} file: {}", label, file_path.display()))?;
+    log::info!(
+        "{} file written ({}): {}",
+        label,
+        encoding.name(),
+        file_path.display()
+    );
+    Ok(())
+}
+
+pub(crate) fn ensure_parent_dir(file_path: &Path) -> Result<()> {
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+/// Converte uma string hexadecimal para u32 little-endian.
+///
+/// Exemplo: "60E3A458" → 0x58A4E360
+pub(crate) fn hex_to_u32_le(hex: &str) -> Result<u32> {
+    let clean_hex = hex.trim().replace(' ', "");
+    if clean_hex.len() != 8 {
+        return Ok(0);
+    }
+
+    let bytes = u32::from_str_radix(&clean_hex, 16)
+        .with_context(|| format!("Invalid hex value: {}", hex))?;
+
+    // Converte de big-endian (como lido) para little-endian
+    Ok(bytes.swap_bytes())
+}
+
+/// Converte u32 para string hexadecimal little-endian (8 chars).
+pub(crate) fn u32_to_hex_le(value: u32) -> String {
+    let swapped = value.swap_bytes();
+    format!("{:08X}", swapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn every_cracker_has_a_registered_format() {
+        let all = [
+            Cracker::Codex,
+            Cracker::Rune,
+            Cracker::OnlineFix,
+            Cracker::Goldberg,
+            Cracker::Rld,
+            Cracker::Empress,
+            Cracker::Skidrow,
+            Cracker::CreamApi,
+            Cracker::SmartSteamEmu,
+            Cracker::Rle,
+            Cracker::Razor1911,
+            Cracker::UserStats,
+            Cracker::ThreeDm,
+            Cracker::Flt,
+            Cracker::SteamCache,
+        ];
+
+        for cracker in all {
+            assert_eq!(format_for(cracker).cracker(), cracker);
+        }
+        assert_eq!(Cracker::all().len(), all.len());
+    }
+
+    #[test]
+    fn detects_crackers_from_windows_and_unix_paths() {
+        assert_eq!(
+            detect_by_path(r"C:\Users\Public\Documents\Steam\RUNE\123\achievements.ini"),
+            Some(Cracker::Rune)
+        );
+        assert_eq!(
+            detect_by_path("/prefix/drive_c/ProgramData/Steam/dodi/123/stats/achievements.ini"),
+            Some(Cracker::Rld)
+        );
+        assert_eq!(
+            detect_by_path("/home/u/AppData/Roaming/GSE Saves/123/achievements.json"),
+            Some(Cracker::Goldberg)
+        );
+        assert_eq!(detect_by_path("/tmp/123/achievements.ini"), None);
+    }
+
+    #[test]
+    fn writable_formats_roundtrip_their_own_output() {
+        let temp_dir = unique_temp_dir("formats_roundtrip");
+        let achievements = vec![
+            AchievementEntry {
+                name: "FIRST_BLOOD".to_string(),
+                achieved: true,
+                unlock_time: 1_710_000_000,
+                cur_progress: None,
+                max_progress: None,
+            },
+            AchievementEntry {
+                name: "LOCKED_ONE".to_string(),
+                achieved: false,
+                unlock_time: 0,
+                cur_progress: None,
+                max_progress: None,
+            },
+        ];
+
+        for format in registry() {
+            if format.cracker() == Cracker::Flt || !format.is_writable() {
+                continue;
+            }
+
+            let path = temp_dir
+                .join(format!("{:?}", format.cracker()))
+                .join("achievements");
+            format.write(&path, &achievements).expect("write");
+            let mut parsed = format.parse(&path).expect("parse");
+            parsed.sort_by(|a, b| a.name.cmp(&b.name));
+
+            assert_eq!(parsed, achievements, "{}", format.display_name());
+        }
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn flt_writer_only_touches_listed_achievements() {
+        let temp_dir = unique_temp_dir("formats_flt");
+        let stats = temp_dir.join("stats");
+        let entry = |name: &str, achieved: bool| AchievementEntry {
+            name: name.to_string(),
+            achieved,
+            unlock_time: 0,
+            cur_progress: None,
+            max_progress: None,
+        };
+        let flt = format_for(Cracker::Flt);
+
+        flt.write(&stats, &[entry("ACH_ONE", true), entry("ACH_TWO", true)])
+            .expect("write");
+        flt.write(&stats, &[entry("ACH_TWO", false), entry("ACH_THREE", true)])
+            .expect("write");
+
+        let mut names: Vec<String> = flt
+            .parse(&stats)
+            .expect("parse")
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["ACH_ONE", "ACH_THREE"]);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn ini_writers_keep_unknown_keys_comments_and_index() {
+        let temp_dir = unique_temp_dir("formats_lossless");
+        let path = temp_dir.join("achievements.ini");
+        fs::create_dir_all(&temp_dir).expect("create dir");
+        fs::write(
+            &path,
+            "### CODEX\r\n[SteamAchievements]\r\nCount=1\r\n00000=ACH_ONE\r\n\r\n\
+             [ACH_ONE]\r\nAchieved=0\r\nCurProgress=4\r\nMaxProgress=10\r\nUnlockTime=0\r\n",
+        )
+        .expect("write file");
+
+        let achievements = vec![
+            AchievementEntry {
+                name: "ACH_ONE".to_string(),
+                achieved: true,
+                unlock_time: 1_710_000_000,
+                cur_progress: None,
+                max_progress: None,
+            },
+            AchievementEntry {
+                name: "ACH_TWO".to_string(),
+                achieved: false,
+                unlock_time: 0,
+                cur_progress: None,
+                max_progress: None,
+            },
+        ];
+        default_ini::Codex
+            .write(&path, &achievements)
+            .expect("write");
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("read"),
+            "### CODEX\r\n[SteamAchievements]\r\nCount=2\r\n00000=ACH_ONE\r\n00001=ACH_TWO\r\n\r\n\
+             [ACH_ONE]\r\nAchieved=1\r\nCurProgress=4\r\nMaxProgress=10\r\nUnlockTime=1710000000\r\n\
+             \r\n[ACH_TWO]\r\nAchieved=0\r\nUnlockTime=0\r\n"
+        );
+        let mut expected = achievements;
+        expected[0].cur_progress = Some(4);
+        expected[0].max_progress = Some(10);
+        assert_eq!(default_ini::Codex.parse(&path).expect("parse"), expected);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn progress_roundtrips_in_formats_that_store_it() {
+        let temp_dir = unique_temp_dir("formats_progress");
+        let achievements = vec![AchievementEntry {
+            name: "KILL_100".to_string(),
+            achieved: false,
+            unlock_time: 0,
+            cur_progress: Some(40),
+            max_progress: Some(100),
+        }];
+
+        for format in [
+            format_for(Cracker::Codex),
+            format_for(Cracker::Rune),
+            format_for(Cracker::Goldberg),
+        ] {
+            let path = temp_dir
+                .join(format!("{:?}", format.cracker()))
+                .join("achievements");
+            format.write(&path, &achievements).expect("write");
+            assert_eq!(
+                format.parse(&path).expect("parse"),
+                achievements,
+                "{}",
+                format.display_name()
+            );
+        }
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn monitor_patterns_match_case_insensitively() {
+        assert!(is_monitored_file_name("Achievements.ini"));
+        assert!(is_monitored_file_name("achiev.ini"));
+        assert!(is_monitored_file_name("creamapi.achievements.cfg"));
+        assert!(!is_monitored_file_name("settings.ini"));
+    }
+
+    #[test]
+    fn parse_report_points_at_the_offending_lines() {
+        let path = Path::new("achievements.ini");
+        let mut report = ParseReport::new();
+        let parsed = format_for(Cracker::Rld)
+            .parse_content(
+                "; rld\n\n[ACH_A]\nState=01000000\nTime=zz\n",
+                path,
+                &mut report,
+            )
+            .expect("parse");
+        assert_eq!(parsed[0].unlock_time, 0);
+        assert!(report.has_errors());
+        assert_eq!(report.issues[0].line, Some(5));
+        assert_eq!(report.issues[0].achievement.as_deref(), Some("ACH_A"));
+
+        let mut report = ParseReport::new();
+        let parsed = format_for(Cracker::Razor1911)
+            .parse_content("ACH_A 1 100\nACH_B 1\n", path, &mut report)
+            .expect("parse");
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].line, Some(2));
+        assert_eq!(report.issues[0].raw, "ACH_B 1");
+    }
+}
@@ -0,0 +1,136 @@
+//! Formato OnlineFix.
+//!
+//! ```ini
+//! [AchievementName]
+//! achieved=true
+//! timestamp=1234567890
+//! ```
+//! ou
+//! ```ini
+//! [AchievementName]
+//! Achieved=true
+//! TimeUnlocked=1234567890
+//! ```
+
+use super::report::ParseReport;
+use super::{ini, read_ini_document, write_text, AchievementFormat, BasePathVar};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::path::Path;
+
+pub struct OnlineFix;
+
+impl AchievementFormat for OnlineFix {
+    fn cracker(&self) -> Cracker {
+        Cracker::OnlineFix
+    }
+
+    fn display_name(&self) -> &'static str {
+        "OnlineFix"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let sections = ini::parse_sections_reported(content, report);
+        let mut achievements = Vec::new();
+
+        for section in sections {
+            let name = section.name;
+            let mut achieved = false;
+            let mut unlock_time: i64 = 0;
+
+            for pair in &section.pairs {
+                match pair.key.as_str() {
+                    "achieved" | "Achieved" => {
+                        achieved = ini::bool_value(pair, &name, report);
+                    }
+                    "timestamp" | "TimeUnlocked" => {
+                        unlock_time = ini::number_value(pair, &name, report).unwrap_or(0);
+                    }
+                    _ => {}
+                }
+            }
+
+            achievements.push(AchievementEntry {
+                name,
+                achieved,
+                unlock_time,
+                cur_progress: None,
+                max_progress: None,
+            });
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut doc = read_ini_document(path)?;
+        for ach in achievements {
+            // Mantém a grafia de chave já usada pelo arquivo
+            let achieved_key = if doc.get(&ach.name, "Achieved").is_some() {
+                "Achieved"
+            } else {
+                "achieved"
+            };
+            let time_key = if doc.get(&ach.name, "TimeUnlocked").is_some() {
+                "TimeUnlocked"
+            } else {
+                "timestamp"
+            };
+
+            doc.set(
+                &ach.name,
+                achieved_key,
+                if ach.achieved { "true" } else { "false" },
+            );
+            doc.set(&ach.name, time_key, &ach.unlock_time.to_string());
+        }
+
+        write_text(path, &doc.to_string(), "OnlineFix")
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &[
+            "Stats/Achievements.ini",
+            "Achievements.ini",
+            "achievements.ini",
+        ]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/onlinefix/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.ini"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[
+            (
+                BasePathVar::PublicDocuments,
+                "OnlineFix/<objectId>/Stats/Achievements.ini",
+            ),
+            (
+                BasePathVar::PublicDocuments,
+                "OnlineFix/<objectId>/Achievements.ini",
+            ),
+        ]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let sections = ini::parse_sections(content);
+        let ratio = ini::section_ratio(&sections, |_, pairs| {
+            let achieved = ini::value_of(pairs, "achieved").or(ini::value_of(pairs, "Achieved"));
+            let time = ini::value_of(pairs, "timestamp").or(ini::value_of(pairs, "TimeUnlocked"));
+            achieved
+                .is_some_and(|v| v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("false"))
+                && time.is_some()
+        });
+        0.9 * ratio
+    }
+}
@@ -0,0 +1,133 @@
+//! Formato Razor1911 (texto plano).
+//!
+//! Cada linha: `<nome> <unlocked(0|1)> <unlockTime>` separado por espaços.
+
+use super::report::ParseReport;
+use super::{write_text, AchievementFormat, BasePathVar};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::path::Path;
+
+pub struct Razor1911;
+
+impl AchievementFormat for Razor1911 {
+    fn cracker(&self) -> Cracker {
+        Cracker::Razor1911
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Razor1911"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let mut achievements = Vec::new();
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let line = raw_line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 3 {
+                report.error(
+                    Some(line_number),
+                    raw_line,
+                    "Expected <name> <unlocked> <unlockTime>",
+                    parts.first().copied(),
+                );
+                continue;
+            }
+
+            let name = parts[0].to_string();
+            let achieved = match parts[1] {
+                "1" => true,
+                "0" => false,
+                _ => {
+                    report.warning(
+                        Some(line_number),
+                        raw_line,
+                        "Unlocked flag must be 0 or 1; treated as locked",
+                        Some(&name),
+                    );
+                    false
+                }
+            };
+            let unlock_time = parts[2].parse().unwrap_or_else(|_| {
+                report.error(
+                    Some(line_number),
+                    raw_line,
+                    "Invalid unlockTime",
+                    Some(&name),
+                );
+                0
+            });
+
+            achievements.push(AchievementEntry {
+                name,
+                achieved,
+                unlock_time,
+                cur_progress: None,
+                max_progress: None,
+            });
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut content = String::new();
+        for ach in achievements {
+            let flag = if ach.achieved { "1" } else { "0" };
+            content.push_str(&format!("{} {} {}\n", ach.name, flag, ach.unlock_time));
+        }
+
+        write_text(path, &content, "Razor1911")
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["achievement"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/.1911/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievement"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[(BasePathVar::AppData, ".1911/<objectId>/achievement")]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let lines: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if lines.is_empty() {
+            return 0.0;
+        }
+
+        let matching = lines
+            .iter()
+            .filter(|line| {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                parts.len() == 3
+                    && !line.contains('=')
+                    && !line.starts_with(['[', '{'])
+                    && matches!(parts[1], "0" | "1")
+                    && parts[2].chars().all(|c| c.is_ascii_digit())
+            })
+            .count();
+        0.85 * matching as f32 / lines.len() as f32
+    }
+}
@@ -0,0 +1,121 @@
+//! Diagnósticos de parse.
+//!
+//! Os parsers continuam tolerantes (valor inválido vira `0`/`false`), mas cada
+//! valor descartado é registrado aqui, permitindo distinguir "arquivo
+//! corrompido" de "conquista realmente bloqueada".
+
+use serde::Serialize;
+use std::path::Path;
+
+/// Gravidade de um problema encontrado no arquivo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Curiosidade sem efeito no resultado (ex.: linha ignorada).
+    Info,
+    /// Valor fora do padrão, interpretado com um fallback razoável.
+    Warning,
+    /// Valor ilegível: o dado da conquista foi perdido ou zerado.
+    Error,
+}
+
+/// Problema encontrado em uma linha (ou entrada JSON) do arquivo.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParseIssue {
+    pub severity: Severity,
+    /// Linha (1-based), quando o formato permite identificar.
+    pub line: Option<usize>,
+    /// Texto original da linha ou do valor problemático.
+    pub raw: String,
+    pub message: String,
+    /// Conquista afetada, se houver.
+    pub achievement: Option<String>,
+}
+
+/// Resultado dos diagnósticos de um parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ParseReport {
+    pub issues: Vec<ParseIssue>,
+}
+
+impl ParseReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(
+        &mut self,
+        severity: Severity,
+        line: Option<usize>,
+        raw: impl Into<String>,
+        message: impl Into<String>,
+        achievement: Option<&str>,
+    ) {
+        self.issues.push(ParseIssue {
+            severity,
+            line,
+            raw: raw.into(),
+            message: message.into(),
+            achievement: achievement.map(str::to_string),
+        });
+    }
+
+    pub fn info(
+        &mut self,
+        line: Option<usize>,
+        raw: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.push(Severity::Info, line, raw, message, None);
+    }
+
+    pub fn warning(
+        &mut self,
+        line: Option<usize>,
+        raw: impl Into<String>,
+        message: impl Into<String>,
+        achievement: Option<&str>,
+    ) {
+        self.push(Severity::Warning, line, raw, message, achievement);
+    }
+
+    pub fn error(
+        &mut self,
+        line: Option<usize>,
+        raw: impl Into<String>,
+        message: impl Into<String>,
+        achievement: Option<&str>,
+    ) {
+        self.push(Severity::Error, line, raw, message, achievement);
+    }
+
+    /// Maior gravidade encontrada, se houver algum problema.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues.iter().map(|issue| issue.severity).max()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+
+    /// Registra no log um resumo dos problemas (warnings e errors).
+    pub fn log_summary(&self, file_path: &Path) {
+        let relevant = self
+            .issues
+            .iter()
+            .filter(|issue| issue.severity >= Severity::Warning)
+            .count();
+        if relevant > 0 {
+            log::warn!(
+                "{} parse issue(s) in {} (first: {})",
+                relevant,
+                file_path.display(),
+                self.issues
+                    .iter()
+                    .find(|issue| issue.severity >= Severity::Warning)
+                    .map(|issue| issue.message.as_str())
+                    .unwrap_or_default()
+            );
+        }
+    }
+}
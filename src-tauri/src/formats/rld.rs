@@ -0,0 +1,126 @@
+//! Formato RLD! (valores em hexadecimal uint32 LE).
+//!
+//! ```ini
+//! [AchievementName]
+//! State=01000000
+//! Time=60E3A458
+//! ```
+
+use super::report::ParseReport;
+use super::{ini, read_ini_document, u32_to_hex_le, write_text, AchievementFormat, BasePathVar};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::path::Path;
+
+pub struct Rld;
+
+impl AchievementFormat for Rld {
+    fn cracker(&self) -> Cracker {
+        Cracker::Rld
+    }
+
+    fn display_name(&self) -> &'static str {
+        "RLD!"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let sections = ini::parse_sections_reported(content, report);
+        let mut achievements = Vec::new();
+
+        for section in sections {
+            let name = section.name;
+            let mut achieved = false;
+            let mut unlock_time: i64 = 0;
+
+            for pair in &section.pairs {
+                match pair.key.as_str() {
+                    "State" => {
+                        if let Some(state) = ini::hex_value(pair, &name, report) {
+                            achieved = state == 1;
+                        }
+                    }
+                    "Time" => {
+                        if let Some(time) = ini::hex_value(pair, &name, report) {
+                            unlock_time = time as i64;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            achievements.push(AchievementEntry {
+                name,
+                achieved,
+                unlock_time,
+                cur_progress: None,
+                max_progress: None,
+            });
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut doc = read_ini_document(path)?;
+        for ach in achievements {
+            let state_hex = if ach.achieved {
+                u32_to_hex_le(1)
+            } else {
+                u32_to_hex_le(0)
+            };
+            let time_hex = u32_to_hex_le(ach.unlock_time as u32);
+            doc.set(&ach.name, "State", &state_hex);
+            doc.set(&ach.name, "Time", &time_hex);
+        }
+
+        write_text(path, &doc.to_string(), "RLD!")
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["achievements.ini", "stats/achievements.ini"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &[
+            "/rld!",
+            "/programdata/steam/player/",
+            "/programdata/steam/dodi/",
+        ]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achievements.ini"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[
+            (BasePathVar::ProgramData, "RLD!/<objectId>/achievements.ini"),
+            (
+                BasePathVar::ProgramData,
+                "Steam/Player/<objectId>/stats/achievements.ini",
+            ),
+            (
+                BasePathVar::ProgramData,
+                "Steam/RLD!/<objectId>/stats/achievements.ini",
+            ),
+            (
+                BasePathVar::ProgramData,
+                "Steam/dodi/<objectId>/stats/achievements.ini",
+            ),
+        ]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let sections = ini::parse_sections(content);
+        let ratio = ini::section_ratio(&sections, |_, pairs| {
+            ini::value_of(pairs, "State").is_some_and(|v| ini::is_hex_of_len(v, 8))
+                && ini::value_of(pairs, "Time").is_some_and(|v| ini::is_hex_of_len(v, 8))
+        });
+        0.95 * ratio
+    }
+}
@@ -0,0 +1,139 @@
+//! Formato SKIDROW (seção `[Achievements]`).
+//!
+//! ```ini
+//! [Achievements]
+//! AchievementName=1@1234567890@DisplayName
+//! ```
+
+use super::report::ParseReport;
+use super::{ini, read_ini_document, write_text, AchievementFormat, BasePathVar};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::path::Path;
+
+pub struct Skidrow;
+
+impl AchievementFormat for Skidrow {
+    fn cracker(&self) -> Cracker {
+        Cracker::Skidrow
+    }
+
+    fn display_name(&self) -> &'static str {
+        "SKIDROW"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let sections = ini::parse_sections_reported(content, report);
+        let mut achievements = Vec::new();
+
+        for section in sections {
+            if section.name != "Achievements" {
+                continue;
+            }
+
+            for pair in section.pairs {
+                let parts: Vec<&str> = pair.value.split('@').collect();
+                let achieved = parts.first().map(|s| *s == "1").unwrap_or(false);
+                let unlock_time = match parts.get(1).map(|s| s.parse::<i64>()) {
+                    Some(Ok(time)) => time,
+                    Some(Err(_)) => {
+                        report.error(
+                            Some(pair.line),
+                            &pair.raw,
+                            "Invalid unlock time between '@' separators",
+                            Some(&pair.key),
+                        );
+                        0
+                    }
+                    None => {
+                        report.error(
+                            Some(pair.line),
+                            &pair.raw,
+                            "Expected <state>@<time>@<name>",
+                            Some(&pair.key),
+                        );
+                        0
+                    }
+                };
+                let name = pair.key;
+
+                achievements.push(AchievementEntry {
+                    name,
+                    achieved,
+                    unlock_time,
+                    cur_progress: None,
+                    max_progress: None,
+                });
+            }
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut doc = read_ini_document(path)?;
+        for ach in achievements {
+            let flag = if ach.achieved { "1" } else { "0" };
+            // Mantém o nome de exibição já gravado depois do segundo `@`
+            let display_name = doc
+                .get("Achievements", &ach.name)
+                .and_then(|value| value.splitn(3, '@').nth(2))
+                .unwrap_or(&ach.name)
+                .to_string();
+            doc.set(
+                "Achievements",
+                &ach.name,
+                &format!("{}@{}@{}", flag, ach.unlock_time, display_name),
+            );
+        }
+
+        write_text(path, &doc.to_string(), "SKIDROW")
+    }
+
+    fn candidate_files(&self) -> &'static [&'static str] {
+        &["SteamEmu/UserStats/achiev.ini"]
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/skidrow/"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["achiev.ini"]
+    }
+
+    fn launcher_paths(&self) -> &'static [(BasePathVar, &'static str)] {
+        &[
+            (
+                BasePathVar::Documents,
+                "SKIDROW/<objectId>/SteamEmu/UserStats/achiev.ini",
+            ),
+            (
+                BasePathVar::Documents,
+                "Player/<objectId>/SteamEmu/UserStats/achiev.ini",
+            ),
+            (
+                BasePathVar::LocalAppData,
+                "SKIDROW/<objectId>/SteamEmu/UserStats/achiev.ini",
+            ),
+        ]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let sections = ini::parse_sections(content);
+        let Some((_, pairs)) = sections.iter().find(|(name, _)| name == "Achievements") else {
+            return 0.0;
+        };
+        if pairs.is_empty() {
+            return 0.5;
+        }
+
+        let with_separator = pairs.iter().filter(|(_, v)| v.contains('@')).count();
+        0.95 * with_separator as f32 / pairs.len() as f32
+    }
+}
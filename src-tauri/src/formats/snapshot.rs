@@ -0,0 +1,362 @@
+//! Escrita atômica e snapshots `.ham-bak` dos arquivos de conquista.
+//!
+//! O jogo ou o emulador costuma estar com o arquivo aberto enquanto o HAM
+//! grava. Em vez de `fs::write` direto, o conteúdo vai para um `.ham-tmp` ao
+//! lado, recebe `fsync` e substitui o original com `rename`; uma queda no meio
+//! deixa o arquivo antigo intacto.
+//!
+//! Antes de substituir, o conteúdo anterior é copiado para
+//! `<arquivo>.<unix ms>.ham-bak` no mesmo diretório (`<unix ms>-<n>` se já
+//! houver um snapshot naquele milissegundo). Só os [`MAX_SNAPSHOTS`] mais
+//! recentes de cada arquivo são mantidos.
+//!
+//! Formatos de diretório (FLT, um arquivo vazio por conquista) guardam no
+//! snapshot a lista de nomes do diretório; restaurá-lo recria essa lista.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const SNAPSHOT_EXTENSION: &str = "ham-bak";
+const TEMP_EXTENSION: &str = "ham-tmp";
+/// Snapshots mantidos por arquivo; os mais antigos são apagados.
+pub const MAX_SNAPSHOTS: usize = 5;
+
+/// Cópia do conteúdo anterior de um arquivo de conquista.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSnapshot {
+    pub snapshot_path: String,
+    /// Arquivo que o snapshot restaura
+    pub file_path: String,
+    /// Unix ms em que o snapshot foi criado
+    pub created_at: i64,
+    pub size: u64,
+}
+
+/// Grava `bytes` em `path` de forma atômica, guardando antes um snapshot do
+/// conteúdo atual.
+pub fn write_with_snapshot(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Ok(previous) = fs::read(path) {
+        // Regravar o mesmo conteúdo não gera snapshot
+        if previous != bytes {
+            if let Err(e) = create_snapshot(path, &previous) {
+                log::warn!("Failed to snapshot {}: {:#}", path.display(), e);
+            }
+        }
+    }
+
+    write_atomic(path, bytes)
+}
+
+/// Grava `bytes` em um arquivo temporário, faz `fsync` e renomeia sobre `path`.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let temp_path = sibling_path(path, TEMP_EXTENSION);
+
+    let result = (|| -> Result<()> {
+        let mut file = File::create(&temp_path)
+            .with_context(|| format!("Failed to create temp file: {}", temp_path.display()))?;
+        file.write_all(bytes)
+            .with_context(|| format!("Failed to write temp file: {}", temp_path.display()))?;
+        file.sync_all()
+            .with_context(|| format!("Failed to sync temp file: {}", temp_path.display()))?;
+        drop(file);
+
+        fs::rename(&temp_path, path).with_context(|| {
+            format!(
+                "Failed to replace {} with {}",
+                path.display(),
+                temp_path.display()
+            )
+        })
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+
+    sync_parent_dir(path);
+    Ok(())
+}
+
+/// Snapshots de um arquivo, do mais recente para o mais antigo.
+pub fn list_snapshots(path: &Path) -> Result<Vec<FileSnapshot>> {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Ok(Vec::new());
+    };
+    let file_name = file_name.to_string_lossy();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut snapshots: Vec<(FileSnapshot, u32)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let snapshot_path = entry.path();
+            let (original, created_at, sequence) = parse_snapshot_name(&snapshot_path)?;
+            if original != file_name {
+                return None;
+            }
+            let snapshot = FileSnapshot {
+                snapshot_path: snapshot_path.to_string_lossy().to_string(),
+                file_path: path.to_string_lossy().to_string(),
+                created_at,
+                size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            };
+            Some((snapshot, sequence))
+        })
+        .collect();
+
+    snapshots
+        .sort_by_key(|(snapshot, sequence)| std::cmp::Reverse((snapshot.created_at, *sequence)));
+    Ok(snapshots
+        .into_iter()
+        .map(|(snapshot, _)| snapshot)
+        .collect())
+}
+
+/// Restaura um snapshot sobre o arquivo original e devolve o path restaurado.
+///
+/// O conteúdo atual vira um novo snapshot, então a restauração pode ser desfeita.
+pub fn restore_snapshot(snapshot_path: &Path) -> Result<PathBuf> {
+    let target = snapshot_target(snapshot_path)?;
+
+    let bytes = fs::read(snapshot_path)
+        .with_context(|| format!("Failed to read snapshot: {}", snapshot_path.display()))?;
+    if target.is_dir() {
+        let names = String::from_utf8_lossy(&bytes)
+            .lines()
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        set_directory_listing(&target, &names)?;
+    } else {
+        write_with_snapshot(&target, &bytes)?;
+    }
+
+    log::info!(
+        "Restored {} from snapshot {}",
+        target.display(),
+        snapshot_path.display()
+    );
+    Ok(target)
+}
+
+/// Arquivo que um snapshot restaura.
+pub fn snapshot_target(snapshot_path: &Path) -> Result<PathBuf> {
+    let (original, _, _) = parse_snapshot_name(snapshot_path)
+        .with_context(|| format!("Not a snapshot file: {}", snapshot_path.display()))?;
+    Ok(snapshot_path.with_file_name(original))
+}
+
+/// Nomes dos arquivos de um diretório de formato FLT.
+pub fn directory_listing(dir: &Path) -> BTreeSet<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return BTreeSet::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|ft| ft.is_file()))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.ends_with(&format!(".{}", TEMP_EXTENSION)))
+        .collect()
+}
+
+/// Deixa em `dir` exatamente os arquivos (vazios) de `names`, guardando antes
+/// um snapshot da lista atual.
+pub fn set_directory_listing(dir: &Path, names: &BTreeSet<String>) -> Result<()> {
+    let existed = dir.is_dir();
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+
+    let current = directory_listing(dir);
+    if current == *names {
+        return Ok(());
+    }
+    if existed {
+        let listing: String = current.iter().map(|name| format!("{}\n", name)).collect();
+        if let Err(e) = create_snapshot(dir, listing.as_bytes()) {
+            log::warn!("Failed to snapshot {}: {:#}", dir.display(), e);
+        }
+    }
+
+    for name in current.difference(names) {
+        let path = dir.join(name);
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    for name in names.difference(&current) {
+        write_atomic(&dir.join(name), b"")?;
+    }
+    Ok(())
+}
+
+fn create_snapshot(path: &Path, contents: &[u8]) -> Result<()> {
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    let snapshot_path = reserve_snapshot_path(path, created_at)?;
+    write_atomic(&snapshot_path, contents)?;
+
+    for old in list_snapshots(path)?.into_iter().skip(MAX_SNAPSHOTS) {
+        if let Err(e) = fs::remove_file(&old.snapshot_path) {
+            log::warn!("Failed to prune snapshot {}: {}", old.snapshot_path, e);
+        }
+    }
+    Ok(())
+}
+
+/// Cria vazio o próximo nome livre de snapshot para `created_at`.
+///
+/// `create_new` garante que dois snapshots no mesmo milissegundo não
+/// compartilhem o nome: o segundo vira `<arquivo>.<ms>-1.ham-bak`.
+fn reserve_snapshot_path(path: &Path, created_at: i64) -> Result<PathBuf> {
+    let mut sequence = 0u32;
+    loop {
+        let stamp = match sequence {
+            0 => created_at.to_string(),
+            _ => format!("{}-{}", created_at, sequence),
+        };
+        let snapshot_path = sibling_path(path, &format!("{}.{}", stamp, SNAPSHOT_EXTENSION));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&snapshot_path)
+        {
+            Ok(_) => return Ok(snapshot_path),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => sequence += 1,
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to create snapshot: {}", snapshot_path.display())
+                })
+            }
+        }
+    }
+}
+
+/// `achievements.ini.1700000000000-1.ham-bak` → (`achievements.ini`,
+/// 1700000000000, 1).
+fn parse_snapshot_name(path: &Path) -> Option<(String, i64, u32)> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name.strip_suffix(&format!(".{}", SNAPSHOT_EXTENSION))?;
+    let (original, stamp) = stem.rsplit_once('.')?;
+    if original.is_empty() {
+        return None;
+    }
+    let (created_at, sequence) = match stamp.split_once('-') {
+        Some((created_at, sequence)) => (created_at, sequence.parse().ok()?),
+        None => (stamp, 0),
+    };
+    Some((original.to_string(), created_at.parse().ok()?, sequence))
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Garante que o `rename` sobreviva a uma queda de energia (no Windows o
+/// diretório não pode ser aberto para `fsync`).
+fn sync_parent_dir(path: &Path) {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn keeps_rolling_snapshots_and_restores_them() {
+        let dir = unique_temp_dir("snapshot");
+        fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("achievements.ini");
+
+        for version in 0..=MAX_SNAPSHOTS + 1 {
+            write_with_snapshot(&path, format!("v{version}").as_bytes()).expect("write");
+            // Regravar o mesmo conteúdo não cria snapshot
+            write_with_snapshot(&path, format!("v{version}").as_bytes()).expect("write");
+        }
+
+        let snapshots = list_snapshots(&path).expect("list");
+        assert_eq!(snapshots.len(), MAX_SNAPSHOTS);
+        assert_eq!(
+            fs::read(&snapshots[0].snapshot_path).expect("read"),
+            format!("v{}", MAX_SNAPSHOTS).into_bytes()
+        );
+        assert!(!sibling_path(&path, TEMP_EXTENSION).exists());
+
+        let restored = restore_snapshot(Path::new(&snapshots[0].snapshot_path)).expect("restore");
+        assert_eq!(restored, path);
+        assert_eq!(
+            fs::read(&path).expect("read"),
+            format!("v{}", MAX_SNAPSHOTS).into_bytes()
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn snapshots_and_restores_directory_listings() {
+        let dir = unique_temp_dir("snapshot_dir").join("stats");
+        let names = |list: &[&str]| list.iter().map(|n| n.to_string()).collect();
+
+        set_directory_listing(&dir, &names(&["ACH_ONE", "ACH_TWO"])).expect("write");
+        // Diretório novo: nada a guardar
+        assert!(list_snapshots(&dir).expect("list").is_empty());
+
+        set_directory_listing(&dir, &names(&["ACH_TWO", "ACH_THREE"])).expect("write");
+        assert_eq!(directory_listing(&dir), names(&["ACH_THREE", "ACH_TWO"]));
+        let snapshots = list_snapshots(&dir).expect("list");
+        assert_eq!(snapshots.len(), 1);
+
+        restore_snapshot(Path::new(&snapshots[0].snapshot_path)).expect("restore");
+        assert_eq!(directory_listing(&dir), names(&["ACH_ONE", "ACH_TWO"]));
+        assert_eq!(list_snapshots(&dir).expect("list").len(), 2);
+
+        let _ = fs::remove_dir_all(dir.parent().expect("parent"));
+    }
+
+    #[test]
+    fn back_to_back_snapshots_get_distinct_names() {
+        let dir = unique_temp_dir("snapshot_same_ms");
+        fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("achievements.ini");
+        let created_at = 1_700_000_000_000;
+
+        let first = reserve_snapshot_path(&path, created_at).expect("reserve");
+        let second = reserve_snapshot_path(&path, created_at).expect("reserve");
+        assert_ne!(first, second);
+        assert_eq!(
+            parse_snapshot_name(&second),
+            Some(("achievements.ini".to_string(), created_at, 1))
+        );
+
+        write_with_snapshot(&path, b"v0").expect("write");
+        write_with_snapshot(&path, b"v1").expect("write");
+        write_with_snapshot(&path, b"v2").expect("write");
+        let snapshots = list_snapshots(&path).expect("list");
+        let contents: Vec<Vec<u8>> = snapshots
+            .iter()
+            .filter(|snapshot| snapshot.created_at != created_at)
+            .map(|snapshot| fs::read(&snapshot.snapshot_path).expect("read"))
+            .collect();
+        assert_eq!(contents, vec![b"v1".to_vec(), b"v0".to_vec()]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
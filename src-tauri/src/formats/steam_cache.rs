@@ -0,0 +1,121 @@
+//! Formato Steam Cache (`librarycache/<objectId>.json`, somente leitura).
+//!
+//! ```json
+//! [
+//!   {
+//!     "strID": "AchievementName",
+//!     "bAchieved": true,
+//!     "rtUnlocked": 1234567890,
+//!     "vecHighlight": [...]
+//!   }
+//! ]
+//! ```
+
+use super::report::ParseReport;
+use super::AchievementFormat;
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+
+pub struct SteamCache;
+
+impl AchievementFormat for SteamCache {
+    fn cracker(&self) -> Cracker {
+        Cracker::SteamCache
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Steam"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        file_path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let json: Value = match serde_json::from_str(content) {
+            Ok(json) => json,
+            Err(e) => {
+                report.error(Some(e.line()), "", e.to_string(), None);
+                return Err(e).with_context(|| {
+                    format!("Failed to parse Steam cache JSON: {}", file_path.display())
+                });
+            }
+        };
+
+        let mut achievements = Vec::new();
+
+        if let Some(arr) = json.as_array() {
+            for item in arr {
+                if let Some(obj) = item.as_object() {
+                    let name = obj
+                        .get("strID")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string();
+                    let achieved = obj
+                        .get("bAchieved")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let unlock_time = match obj.get("rtUnlocked") {
+                        Some(value) => value.as_i64().unwrap_or_else(|| {
+                            report.error(
+                                None,
+                                value.to_string(),
+                                "rtUnlocked is not an integer",
+                                Some(&name),
+                            );
+                            0
+                        }),
+                        None => 0,
+                    };
+
+                    // Ignora entradas sem nome
+                    if name.is_empty() {
+                        report.info(None, item.to_string(), "Entry without strID was ignored");
+                    } else {
+                        achievements.push(AchievementEntry {
+                            name,
+                            achieved,
+                            unlock_time,
+                            cur_progress: None,
+                            max_progress: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, _achievements: &[AchievementEntry]) -> Result<()> {
+        // Steam cache é read-only (gerenciado pelo cliente Steam)
+        anyhow::bail!("Steam cache is read-only: {}", path.display())
+    }
+
+    fn is_writable(&self) -> bool {
+        false
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["librarycache"]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let Ok(Value::Array(arr)) = serde_json::from_str::<Value>(content) else {
+            return 0.0;
+        };
+        if arr.is_empty() {
+            return 0.0;
+        }
+
+        let matching = arr
+            .iter()
+            .filter(|item| item.get("strID").is_some() && item.get("bAchieved").is_some())
+            .count();
+        0.95 * matching as f32 / arr.len() as f32
+    }
+}
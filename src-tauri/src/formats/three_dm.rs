@@ -0,0 +1,120 @@
+//! Formato 3DM (diretório do executável, seções `[State]` e `[Time]`).
+//!
+//! ```ini
+//! [State]
+//! AchievementName=0101
+//! [Time]
+//! AchievementName=60E3A458
+//! ```
+//! Valores em hex. `0101` = desbloqueado.
+
+use super::report::ParseReport;
+use super::{ini, read_ini_document, u32_to_hex_le, write_text, AchievementFormat};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub struct ThreeDm;
+
+impl AchievementFormat for ThreeDm {
+    fn cracker(&self) -> Cracker {
+        Cracker::ThreeDm
+    }
+
+    fn display_name(&self) -> &'static str {
+        "3DM"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let sections = ini::parse_sections_reported(content, report);
+        let mut states: Vec<ini::IniPair> = Vec::new();
+        let mut times: HashMap<String, ini::IniPair> = HashMap::new();
+
+        for section in sections {
+            match section.name.as_str() {
+                "State" => states.extend(section.pairs),
+                "Time" => {
+                    for pair in section.pairs {
+                        times.insert(pair.key.clone(), pair);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut achievements = Vec::new();
+        for state in &states {
+            let name = &state.key;
+            let achieved = match state.value.as_str() {
+                "0101" => true,
+                "0000" => false,
+                _ => {
+                    report.warning(
+                        Some(state.line),
+                        &state.raw,
+                        "State must be 0101 or 0000; treated as locked",
+                        Some(name),
+                    );
+                    false
+                }
+            };
+            let unlock_time = match times.get(name) {
+                Some(pair) => ini::hex_value(pair, name, report).unwrap_or(0) as i64,
+                None => {
+                    report.warning(
+                        Some(state.line),
+                        &state.raw,
+                        "No matching entry in [Time]",
+                        Some(name),
+                    );
+                    0
+                }
+            };
+
+            achievements.push(AchievementEntry {
+                name: name.clone(),
+                achieved,
+                unlock_time,
+                cur_progress: None,
+                max_progress: None,
+            });
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut doc = read_ini_document(path)?;
+        for ach in achievements {
+            let state = if ach.achieved { "0101" } else { "0000" };
+            doc.set("State", &ach.name, state);
+        }
+        for ach in achievements {
+            let time_hex = u32_to_hex_le(ach.unlock_time as u32);
+            doc.set("Time", &ach.name, &time_hex);
+        }
+
+        write_text(path, &doc.to_string(), "3DM")
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/3dmgame/"]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let sections = ini::parse_sections(content);
+        let has_state = sections.iter().any(|(name, _)| name == "State");
+        let has_time = sections.iter().any(|(name, _)| name == "Time");
+        match (has_state, has_time) {
+            (true, true) => 0.9,
+            (true, false) => 0.5,
+            _ => 0.0,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Formato user_stats (diretório do executável, seção `[ACHIEVEMENTS]`).
+//!
+//! ```ini
+//! [ACHIEVEMENTS]
+//! "AchievementName" = "{unlocked = true, time = 1234567890}"
+//! ```
+
+use super::report::ParseReport;
+use super::{ini, read_ini_document, write_text, AchievementFormat};
+use crate::models::{AchievementEntry, Cracker};
+use anyhow::Result;
+use std::path::Path;
+
+pub struct UserStats;
+
+/// Extrai o time de dentro de chaves: `{unlocked = true, time = 1234567890}`
+fn extract_time_from_braces(value: &str) -> Option<i64> {
+    let start = value.find("time")?;
+    let after_time = &value[start..];
+    let eq_pos = after_time.find('=')?;
+    let after_eq = &after_time[eq_pos + 1..];
+    let time_str: String = after_eq
+        .chars()
+        .skip_while(|c| c.is_whitespace())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    time_str.parse().ok()
+}
+
+impl AchievementFormat for UserStats {
+    fn cracker(&self) -> Cracker {
+        Cracker::UserStats
+    }
+
+    fn display_name(&self) -> &'static str {
+        "user_stats"
+    }
+
+    fn parse_content(
+        &self,
+        content: &str,
+        _path: &Path,
+        report: &mut ParseReport,
+    ) -> Result<Vec<AchievementEntry>> {
+        let sections = ini::parse_sections_reported(content, report);
+        let mut achievements = Vec::new();
+
+        for section in sections {
+            if section.name != "ACHIEVEMENTS" {
+                continue;
+            }
+
+            for pair in section.pairs {
+                // Remove aspas do nome
+                let clean_name = pair.key.trim_matches('"').to_string();
+
+                let achieved =
+                    pair.value.contains("unlocked = true") || pair.value.contains("unlocked=true");
+                let time = extract_time_from_braces(&pair.value).unwrap_or_else(|| {
+                    report.error(
+                        Some(pair.line),
+                        &pair.raw,
+                        "Missing or invalid time inside braces",
+                        Some(&clean_name),
+                    );
+                    0
+                });
+
+                achievements.push(AchievementEntry {
+                    name: clean_name,
+                    achieved,
+                    unlock_time: time,
+                    cur_progress: None,
+                    max_progress: None,
+                });
+            }
+        }
+
+        Ok(achievements)
+    }
+
+    fn write(&self, path: &Path, achievements: &[AchievementEntry]) -> Result<()> {
+        let mut doc = read_ini_document(path)?.with_default_separator(" = ");
+        for ach in achievements {
+            let unlocked = if ach.achieved { "true" } else { "false" };
+            doc.set(
+                "ACHIEVEMENTS",
+                &format!("\"{}\"", ach.name),
+                &format!(
+                    "\"{{unlocked = {}, time = {}}}\"",
+                    unlocked, ach.unlock_time
+                ),
+            );
+        }
+
+        write_text(path, &doc.to_string(), "user_stats")
+    }
+
+    fn path_signatures(&self) -> &'static [&'static str] {
+        &["/user_stats.ini"]
+    }
+
+    fn monitor_patterns(&self) -> &'static [&'static str] {
+        &["user_stats.ini"]
+    }
+
+    fn sniff(&self, content: &str) -> f32 {
+        let sections = ini::parse_sections(content);
+        let Some((_, pairs)) = sections.iter().find(|(name, _)| name == "ACHIEVEMENTS") else {
+            return 0.0;
+        };
+        if pairs.is_empty() {
+            return 0.5;
+        }
+
+        let with_braces = pairs.iter().filter(|(_, v)| v.contains("unlocked")).count();
+        0.95 * with_braces as f32 / pairs.len() as f32
+    }
+}
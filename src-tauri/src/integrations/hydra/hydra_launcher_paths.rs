@@ -1,4 +1,5 @@
-use crate::models::{DirectoryConfig, DirectoryDetectionPreset};
+use crate::formats::{self, BasePathVar};
+use crate::models::{DirectoryConfig, DirectoryDetectionPreset, WatchMode};
 use crate::wine::Wine;
 use std::path::{Path, PathBuf};
 
@@ -354,6 +355,7 @@ fn make_config(path: &str, name: &str, is_default: bool) -> DirectoryConfig {
         enabled: true,
         is_default,
         detection_preset: DirectoryDetectionPreset::Auto,
+        watch_mode: WatchMode::Auto,
     }
 }
 
@@ -364,6 +366,14 @@ fn get_windows_username() -> String {
         .unwrap_or_else(|_| "steamuser".to_string())
 }
 
+/// Diretório de dados do Hydra (`userData` do Electron): `%APPDATA%` no
+/// Windows, `~/Library/Application Support` no macOS e `~/.config` no Linux.
+pub fn hydra_user_data_dir() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join("hydralauncher"))
+        .unwrap_or_else(|| crate::parser::expand_path("~/.config/hydralauncher"))
+}
+
 /// Resolve todos os paths de conquistas para um jogo específico.
 ///
 /// Retorna uma lista de (cracker, path_completo) para todos os arquivos
@@ -387,21 +397,14 @@ pub fn find_achievement_files_for_game(
     // IDs alternativos para o jogo
     let alt_ids = crate::parser::get_alternative_object_ids(object_id);
 
-    for &cracker in crate::models::Cracker::all() {
-        let cracker_paths = get_cracker_paths(cracker);
-
-        for (base_var, file_pattern) in cracker_paths {
+    for format in formats::registry() {
+        for (base_var, file_pattern) in format.launcher_paths() {
             for oid in &alt_ids {
-                let base_path = resolve_base_path(&base_var, effective_prefix.as_deref());
-
-                let full_path = if file_pattern.contains("<objectId>") {
-                    base_path.join(file_pattern.replace("<objectId>", oid))
-                } else {
-                    base_path.join(&file_pattern)
-                };
+                let base_path = resolve_base_path(base_var, effective_prefix.as_deref());
+                let full_path = base_path.join(file_pattern.replace("<objectId>", oid));
 
                 if full_path.exists() {
-                    found.push((cracker, full_path));
+                    found.push((format.cracker(), full_path));
                 }
             }
         }
@@ -421,141 +424,6 @@ pub fn find_achievement_files_for_game(
     found
 }
 
-/// Variável de base para paths
-enum BasePathVar {
-    AppData,
-    Documents,
-    PublicDocuments,
-    LocalAppData,
-    ProgramData,
-}
-
-/// Retorna os paths de cada cracker como (variável_base, padrão_de_arquivo).
-///
-/// Conforme documentação oficial do Hydra:
-/// - CODEX: `<publicDocuments>/Steam/CODEX/<objectId>/achievements.ini`, `<appData>/Steam/CODEX/<objectId>/achievements.ini`
-/// - RUNE: `<publicDocuments>/Steam/RUNE/<objectId>/achievements.ini`
-/// - OnlineFix: `<publicDocuments>/OnlineFix/<objectId>/Stats/Achievements.ini`, `<publicDocuments>/OnlineFix/<objectId>/Achievements.ini`
-/// - Goldberg: `<appData>/Goldberg SteamEmu Saves/<objectId>/achievements.json`, `<appData>/GSE Saves/<objectId>/achievements.json`
-/// - RLD!: `<programData>/RLD!/<objectId>/achievements.ini`, etc.
-/// - EMPRESS: `<appData>/EMPRESS/remote/<objectId>/achievements.json`, `<publicDocuments>/EMPRESS/<objectId>/remote/<objectId>/achievements.json`
-/// - SKIDROW: `<documents>/SKIDROW/<objectId>/SteamEmu/UserStats/achiev.ini`, etc.
-/// - CreamAPI: `<appData>/CreamAPI/<objectId>/stats/CreamAPI.Achievements.cfg`
-/// - SmartSteamEmu: `<appData>/SmartSteamEmu/<objectId>/User/Achievements.ini`
-/// - RLE: `<appData>/RLE/<objectId>/achievements.ini`
-/// - Razor1911: `<appData>/.1911/<objectId>/achievement`
-fn get_cracker_paths(cracker: crate::models::Cracker) -> Vec<(BasePathVar, String)> {
-    use crate::models::Cracker;
-
-    match cracker {
-        Cracker::Codex => vec![
-            (
-                BasePathVar::PublicDocuments,
-                "Steam/CODEX/<objectId>/achievements.ini".into(),
-            ),
-            (
-                BasePathVar::AppData,
-                "Steam/CODEX/<objectId>/achievements.ini".into(),
-            ),
-        ],
-        Cracker::Rune => vec![(
-            BasePathVar::PublicDocuments,
-            "Steam/RUNE/<objectId>/achievements.ini".into(),
-        )],
-        Cracker::OnlineFix => vec![
-            (
-                BasePathVar::PublicDocuments,
-                "OnlineFix/<objectId>/Stats/Achievements.ini".into(),
-            ),
-            (
-                BasePathVar::PublicDocuments,
-                "OnlineFix/<objectId>/Achievements.ini".into(),
-            ),
-        ],
-        Cracker::Goldberg => vec![
-            (
-                BasePathVar::AppData,
-                "Goldberg SteamEmu Saves/<objectId>/achievements.json".into(),
-            ),
-            (
-                BasePathVar::AppData,
-                "GSE Saves/<objectId>/achievements.json".into(),
-            ),
-        ],
-        Cracker::Rld => vec![
-            (
-                BasePathVar::ProgramData,
-                "RLD!/<objectId>/achievements.ini".into(),
-            ),
-            (
-                BasePathVar::ProgramData,
-                "Steam/Player/<objectId>/stats/achievements.ini".into(),
-            ),
-            (
-                BasePathVar::ProgramData,
-                "Steam/RLD!/<objectId>/stats/achievements.ini".into(),
-            ),
-            (
-                BasePathVar::ProgramData,
-                "Steam/dodi/<objectId>/stats/achievements.ini".into(),
-            ),
-        ],
-        Cracker::Empress => vec![
-            (
-                BasePathVar::AppData,
-                "EMPRESS/remote/<objectId>/achievements.json".into(),
-            ),
-            (
-                BasePathVar::PublicDocuments,
-                "EMPRESS/<objectId>/remote/<objectId>/achievements.json".into(),
-            ),
-        ],
-        Cracker::Skidrow => vec![
-            (
-                BasePathVar::Documents,
-                "SKIDROW/<objectId>/SteamEmu/UserStats/achiev.ini".into(),
-            ),
-            (
-                BasePathVar::Documents,
-                "Player/<objectId>/SteamEmu/UserStats/achiev.ini".into(),
-            ),
-            (
-                BasePathVar::LocalAppData,
-                "SKIDROW/<objectId>/SteamEmu/UserStats/achiev.ini".into(),
-            ),
-        ],
-        Cracker::CreamApi => vec![(
-            BasePathVar::AppData,
-            "CreamAPI/<objectId>/stats/CreamAPI.Achievements.cfg".into(),
-        )],
-        Cracker::SmartSteamEmu => vec![(
-            BasePathVar::AppData,
-            "SmartSteamEmu/<objectId>/User/Achievements.ini".into(),
-        )],
-        Cracker::Rle => vec![
-            (
-                BasePathVar::AppData,
-                "RLE/<objectId>/achievements.ini".into(),
-            ),
-            (
-                BasePathVar::AppData,
-                "RLE/<objectId>/Achievements.ini".into(),
-            ),
-        ],
-        Cracker::Razor1911 => vec![(BasePathVar::AppData, ".1911/<objectId>/achievement".into())],
-        Cracker::Flt => {
-            // Comentado no código original do Hydra - não é buscado automaticamente
-            vec![
-                // (BasePathVar::AppData, "FLT/stats".into()),
-            ]
-        }
-        Cracker::UserStats | Cracker::ThreeDm | Cracker::SteamCache => {
-            // Esses são buscados por paths relativos (executável ou Steam userdata)
-            vec![]
-        }
-    }
-}
-
 /// Resolve o path base de acordo com a variável.
 ///
 /// No Windows: usa as variáveis de sistema reais.
@@ -623,10 +491,10 @@ pub fn find_existing_achievement_dirs_for_game_in_prefix(
     let mut seen = std::collections::HashSet::new();
     let alternative_ids = crate::parser::get_alternative_object_ids(game_id);
 
-    for &cracker in crate::models::Cracker::all() {
-        for (base_var, file_pattern) in get_cracker_paths(cracker) {
-            let base_path = resolve_wine_base_path(&base_var, prefix);
-            let monitored_dir = base_path.join(monitored_directory_suffix(&file_pattern));
+    for format in formats::registry() {
+        for (base_var, file_pattern) in format.launcher_paths() {
+            let base_path = resolve_wine_base_path(base_var, prefix);
+            let monitored_dir = base_path.join(monitored_directory_suffix(file_pattern));
 
             for object_id in &alternative_ids {
                 let file_path = base_path.join(file_pattern.replace("<objectId>", object_id));
@@ -635,10 +503,11 @@ pub fn find_existing_achievement_dirs_for_game_in_prefix(
                     if seen.insert(path.clone()) {
                         dirs.push(DirectoryConfig {
                             path,
-                            name: format!("{} / {}", label, cracker.display_name()),
+                            name: format!("{} / {}", label, format.display_name()),
                             enabled: true,
                             is_default: true,
                             detection_preset: DirectoryDetectionPreset::Auto,
+                            watch_mode: WatchMode::Auto,
                         });
                     }
                 }
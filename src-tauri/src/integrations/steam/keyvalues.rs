@@ -0,0 +1,701 @@
+//! KeyValues do Valve, usados por todos os arquivos do cliente Steam.
+//!
+//! - Texto (`.vdf`/`.acf`): `"chave" "valor"` e `"chave" { ... }`, com
+//!   escapes, comentários `//`, condicionais `[$WIN32]` e `#include`/`#base`.
+//! - Binário (`appcache`): cada nó começa com um byte de tipo e uma chave
+//!   terminada em `\0`; `0x00` abre uma seção, `0x08` fecha.
+//!
+//! As duas formas resultam na mesma árvore [`KeyValue`], que pode ser lida por
+//! acessores (`get`, `path`, `as_i64`...) ou desserializada com serde.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, IntoDeserializer, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fs;
+use std::path::Path;
+
+const TYPE_SECTION: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_WIDE_STRING: u8 = 0x05;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0A;
+const TYPE_END_ALT: u8 = 0x0B;
+
+/// Limite de aninhamento, para arquivos corrompidos não estourarem a pilha.
+const MAX_DEPTH: usize = 64;
+
+/// Nó de uma árvore KeyValues.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyValue {
+    /// Filhos na ordem do arquivo (chaves podem se repetir).
+    Section(Vec<(String, KeyValue)>),
+    String(String),
+    Int32(i32),
+    Float32(f32),
+    UInt64(u64),
+    Int64(i64),
+}
+
+impl KeyValue {
+    /// Primeiro filho com a chave informada (KeyValues ignora maiúsculas/minúsculas).
+    pub fn get(&self, key: &str) -> Option<&KeyValue> {
+        self.entries()
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Desce pelas chaves informadas: `kv.path(&["stats", "1", "bits"])`.
+    pub fn path(&self, keys: &[&str]) -> Option<&KeyValue> {
+        keys.iter().try_fold(self, |node, key| node.get(key))
+    }
+
+    /// Filhos de uma seção; vazio para valores simples.
+    pub fn entries(&self) -> &[(String, KeyValue)] {
+        match self {
+            Self::Section(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Valor inteiro; strings numéricas também são aceitas, como no cliente Steam.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int32(value) => Some(i64::from(*value)),
+            Self::Int64(value) => Some(*value),
+            Self::UInt64(value) => i64::try_from(*value).ok(),
+            Self::Float32(value) => Some(*value as i64),
+            Self::String(value) => value.trim().parse().ok(),
+            Self::Section(_) => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::UInt64(value) => Some(*value),
+            Self::String(value) => value.trim().parse().ok(),
+            other => other.as_i64().and_then(|value| u64::try_from(value).ok()),
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Float32(value) => Some(f64::from(*value)),
+            Self::String(value) => value.trim().parse().ok(),
+            other => other.as_i64().map(|value| value as f64),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_i64().map(|value| value != 0)
+    }
+
+    /// Primeira ocorrência da chave em qualquer nível (busca em profundidade).
+    pub fn find(&self, key: &str) -> Option<&KeyValue> {
+        self.entries().iter().find_map(|(name, value)| {
+            if name.eq_ignore_ascii_case(key) {
+                Some(value)
+            } else {
+                value.find(key)
+            }
+        })
+    }
+
+    /// Desserializa o nó em um tipo serde. Valores de texto são convertidos
+    /// para números/bool conforme o campo pede.
+    pub fn deserialize<'de, T: Deserialize<'de>>(&'de self) -> Result<T> {
+        T::deserialize(self).map_err(|e| anyhow!("Invalid KeyValues data: {}", e))
+    }
+}
+
+// ── Texto ───────────────────────────────────────────────────────────
+
+/// Faz o parse de KeyValues em texto. `#include`/`#base` são ignorados, pois
+/// precisam do diretório do arquivo (ver [`parse_text_file`]).
+pub fn parse_text(content: &str) -> Result<KeyValue> {
+    parse_text_with(content, 0, &mut |directive, target| {
+        log::debug!(
+            "Ignoring KeyValues {} \"{}\" without a base path",
+            directive,
+            target
+        );
+        Ok(None)
+    })
+}
+
+/// Lê e faz o parse de um arquivo KeyValues em texto, resolvendo
+/// `#include`/`#base` relativos ao diretório do arquivo.
+pub fn parse_text_file(path: &Path) -> Result<KeyValue> {
+    parse_text_file_at_depth(path, 0)
+}
+
+fn parse_text_file_at_depth(path: &Path, depth: usize) -> Result<KeyValue> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read KeyValues file: {}", path.display()))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    parse_text_with(&content, depth, &mut |_, target| {
+        let included = base_dir.join(target);
+        if !included.is_file() {
+            log::warn!("KeyValues include not found: {}", included.display());
+            return Ok(None);
+        }
+        parse_text_file_at_depth(&included, depth + 1).map(Some)
+    })
+    .with_context(|| format!("Failed to parse KeyValues file: {}", path.display()))
+}
+
+type IncludeResolver<'a> = dyn FnMut(&str, &str) -> Result<Option<KeyValue>> + 'a;
+
+fn parse_text_with(content: &str, depth: usize, resolve: &mut IncludeResolver) -> Result<KeyValue> {
+    if depth > MAX_DEPTH {
+        bail!(
+            "KeyValues #include/#base nested deeper than {} levels",
+            MAX_DEPTH
+        );
+    }
+
+    let mut lexer = TextLexer::new(content);
+    let mut directives = Vec::new();
+    let mut entries = parse_text_entries(&mut lexer, 0, &mut directives)?;
+
+    for (directive, target) in directives {
+        let Some(KeyValue::Section(included)) = resolve(&directive, &target)? else {
+            continue;
+        };
+        if directive.eq_ignore_ascii_case("#base") {
+            merge_missing(&mut entries, included);
+        } else {
+            entries.extend(included);
+        }
+    }
+
+    Ok(KeyValue::Section(entries))
+}
+
+/// `#base`: o arquivo atual tem prioridade; só entra o que ainda não existe.
+fn merge_missing(entries: &mut Vec<(String, KeyValue)>, base: Vec<(String, KeyValue)>) {
+    for (key, base_value) in base {
+        let existing = entries
+            .iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&key));
+        match (existing, base_value) {
+            (Some((_, KeyValue::Section(current))), KeyValue::Section(base_children)) => {
+                merge_missing(current, base_children)
+            }
+            (Some(_), _) => {}
+            (None, base_value) => entries.push((key, base_value)),
+        }
+    }
+}
+
+fn parse_text_entries(
+    lexer: &mut TextLexer,
+    depth: usize,
+    directives: &mut Vec<(String, String)>,
+) -> Result<Vec<(String, KeyValue)>> {
+    if depth > MAX_DEPTH {
+        bail!("KeyValues nesting deeper than {} levels", MAX_DEPTH);
+    }
+
+    let mut entries = Vec::new();
+    loop {
+        let token = match lexer.next_token()? {
+            None if depth == 0 => return Ok(entries),
+            None => bail!("Unexpected end of KeyValues text: missing '}}'"),
+            Some(TextToken::Close) if depth > 0 => return Ok(entries),
+            Some(TextToken::Close) => bail!("Unexpected '}}' at line {}", lexer.line),
+            Some(TextToken::Open) => bail!("Section without a key at line {}", lexer.line),
+            Some(TextToken::Conditional) => continue,
+            Some(TextToken::Text { value, quoted }) => (value, quoted),
+        };
+        let (key, quoted) = token;
+
+        let value = loop {
+            match lexer.next_token()? {
+                Some(TextToken::Conditional) => continue,
+                Some(TextToken::Open) => {
+                    break KeyValue::Section(parse_text_entries(lexer, depth + 1, directives)?)
+                }
+                Some(TextToken::Text { value, .. }) => break KeyValue::String(value),
+                _ => bail!("Key \"{}\" has no value at line {}", key, lexer.line),
+            }
+        };
+
+        let is_directive =
+            !quoted && (key.eq_ignore_ascii_case("#include") || key.eq_ignore_ascii_case("#base"));
+        match value {
+            KeyValue::String(target) if is_directive => directives.push((key, target)),
+            value => entries.push((key, value)),
+        }
+    }
+}
+
+enum TextToken {
+    Text { value: String, quoted: bool },
+    Open,
+    Close,
+    Conditional,
+}
+
+struct TextLexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> TextLexer<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            chars: content.trim_start_matches('\u{FEFF}').chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let character = self.chars.next()?;
+        if character == '\n' {
+            self.line += 1;
+        }
+        Some(character)
+    }
+
+    fn next_token(&mut self) -> Result<Option<TextToken>> {
+        loop {
+            let Some(&character) = self.chars.peek() else {
+                return Ok(None);
+            };
+
+            match character {
+                c if c.is_whitespace() => {
+                    self.bump();
+                }
+                '/' => {
+                    self.bump();
+                    if self.chars.peek() == Some(&'/') {
+                        while self.chars.peek().is_some_and(|c| *c != '\n') {
+                            self.bump();
+                        }
+                    } else {
+                        return Ok(Some(self.read_unquoted(String::from("/"))));
+                    }
+                }
+                '{' => {
+                    self.bump();
+                    return Ok(Some(TextToken::Open));
+                }
+                '}' => {
+                    self.bump();
+                    return Ok(Some(TextToken::Close));
+                }
+                '[' => {
+                    while let Some(c) = self.bump() {
+                        if c == ']' {
+                            break;
+                        }
+                    }
+                    return Ok(Some(TextToken::Conditional));
+                }
+                '"' => {
+                    self.bump();
+                    return self.read_quoted().map(Some);
+                }
+                _ => return Ok(Some(self.read_unquoted(String::new()))),
+            }
+        }
+    }
+
+    fn read_quoted(&mut self) -> Result<TextToken> {
+        let start_line = self.line;
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => bail!("Unterminated string starting at line {}", start_line),
+                Some('"') => break,
+                Some('\\') => match self.bump() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('\\') => value.push('\\'),
+                    Some('"') => value.push('"'),
+                    Some(other) => {
+                        value.push('\\');
+                        value.push(other);
+                    }
+                    None => bail!("Unterminated string starting at line {}", start_line),
+                },
+                Some(character) => value.push(character),
+            }
+        }
+        Ok(TextToken::Text {
+            value,
+            quoted: true,
+        })
+    }
+
+    fn read_unquoted(&mut self, mut value: String) -> TextToken {
+        while let Some(&character) = self.chars.peek() {
+            if character.is_whitespace() || matches!(character, '{' | '}' | '"') {
+                break;
+            }
+            value.push(character);
+            self.bump();
+        }
+        TextToken::Text {
+            value,
+            quoted: false,
+        }
+    }
+}
+
+// ── Binário ─────────────────────────────────────────────────────────
+
+/// Faz o parse de um KeyValues binário. O resultado é sempre uma seção raiz.
+pub fn parse_binary(bytes: &[u8]) -> Result<KeyValue> {
+    let mut reader = BinaryReader { bytes, offset: 0 };
+    let entries = reader.read_section(0)?;
+    Ok(KeyValue::Section(entries))
+}
+
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl BinaryReader<'_> {
+    fn read_section(&mut self, depth: usize) -> Result<Vec<(String, KeyValue)>> {
+        if depth > MAX_DEPTH {
+            bail!("KeyValues nesting deeper than {} levels", MAX_DEPTH);
+        }
+
+        let mut entries = Vec::new();
+        loop {
+            // Fim do arquivo fecha a raiz; alguns arquivos omitem o último 0x08
+            let Some(kind) = self.next_byte() else {
+                if depth == 0 {
+                    return Ok(entries);
+                }
+                bail!("Unexpected end of KeyValues data inside a section");
+            };
+
+            if kind == TYPE_END || kind == TYPE_END_ALT {
+                return Ok(entries);
+            }
+
+            let start = self.offset - 1;
+            let key = self.read_string()?;
+            let value = match kind {
+                TYPE_SECTION => KeyValue::Section(self.read_section(depth + 1)?),
+                TYPE_STRING => KeyValue::String(self.read_string()?),
+                TYPE_WIDE_STRING => KeyValue::String(self.read_wide_string()?),
+                TYPE_INT32 | TYPE_POINTER | TYPE_COLOR => {
+                    KeyValue::Int32(i32::from_le_bytes(self.read_array()?))
+                }
+                TYPE_FLOAT32 => KeyValue::Float32(f32::from_le_bytes(self.read_array()?)),
+                TYPE_UINT64 => KeyValue::UInt64(u64::from_le_bytes(self.read_array()?)),
+                TYPE_INT64 => KeyValue::Int64(i64::from_le_bytes(self.read_array()?)),
+                other => bail!("Unknown KeyValues type 0x{:02X} at offset {}", other, start),
+            };
+            entries.push((key, value));
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.offset)?;
+        self.offset += 1;
+        Some(byte)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let Some(slice) = self.bytes.get(self.offset..self.offset + N) else {
+            bail!("Truncated KeyValues value at offset {}", self.offset);
+        };
+        self.offset += N;
+        Ok(slice.try_into().expect("slice has exactly N bytes"))
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let rest = &self.bytes[self.offset..];
+        let Some(len) = rest.iter().position(|byte| *byte == 0) else {
+            bail!("Unterminated KeyValues string at offset {}", self.offset);
+        };
+        self.offset += len + 1;
+        Ok(String::from_utf8_lossy(&rest[..len]).into_owned())
+    }
+
+    fn read_wide_string(&mut self) -> Result<String> {
+        let mut units = Vec::new();
+        loop {
+            let unit = u16::from_le_bytes(self.read_array()?);
+            if unit == 0 {
+                break;
+            }
+            units.push(unit);
+        }
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
+
+// ── serde ───────────────────────────────────────────────────────────
+
+/// Seções viram mapas (na ordem do arquivo); valores mantêm o tipo.
+impl Serialize for KeyValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Section(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Self::String(value) => serializer.serialize_str(value),
+            Self::Int32(value) => serializer.serialize_i32(*value),
+            Self::Float32(value) => serializer.serialize_f32(*value),
+            Self::UInt64(value) => serializer.serialize_u64(*value),
+            Self::Int64(value) => serializer.serialize_i64(*value),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, de::value::Error> for &'de KeyValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident => $accessor:ident, $visit:ident;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.$accessor() {
+                    Some(value) => visitor.$visit(value),
+                    None => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+/// KeyValues em texto só tem strings: números e bools são convertidos
+/// conforme o tipo pedido pelo campo.
+impl<'de> de::Deserializer<'de> for &'de KeyValue {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            KeyValue::Section(entries) => visitor.visit_map(MapDeserializer::new(
+                entries.iter().map(|(key, value)| (key.as_str(), value)),
+            )),
+            KeyValue::String(value) => visitor.visit_borrowed_str(value),
+            KeyValue::Int32(value) => visitor.visit_i32(*value),
+            KeyValue::Float32(value) => visitor.visit_f32(*value),
+            KeyValue::UInt64(value) => visitor.visit_u64(*value),
+            KeyValue::Int64(value) => visitor.visit_i64(*value),
+        }
+    }
+
+    deserialize_number! {
+        deserialize_bool => as_bool, visit_bool;
+        deserialize_i8 => as_i64, visit_i64;
+        deserialize_i16 => as_i64, visit_i64;
+        deserialize_i32 => as_i64, visit_i64;
+        deserialize_i64 => as_i64, visit_i64;
+        deserialize_u8 => as_u64, visit_u64;
+        deserialize_u16 => as_u64, visit_u64;
+        deserialize_u32 => as_u64, visit_u64;
+        deserialize_u64 => as_u64, visit_u64;
+        deserialize_f32 => as_f64, visit_f64;
+        deserialize_f64 => as_f64, visit_f64;
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    /// Listas do Valve são seções com chaves `"0"`, `"1"`...
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            KeyValue::Section(entries) => {
+                visitor.visit_seq(SeqDeserializer::new(entries.iter().map(|(_, value)| value)))
+            }
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    /// Atalho para montar uma `KeyValue::Section` nos testes.
+    pub(crate) fn section(entries: &[(&str, KeyValue)]) -> KeyValue {
+        KeyValue::Section(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+
+    /// Serializa uma seção raiz em KeyValues binário, para os testes.
+    pub(crate) fn to_binary(root: &KeyValue) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (key, value) in root.entries() {
+            let kind = match value {
+                KeyValue::Section(_) => TYPE_SECTION,
+                KeyValue::String(_) => TYPE_STRING,
+                KeyValue::Int32(_) => TYPE_INT32,
+                KeyValue::Float32(_) => TYPE_FLOAT32,
+                KeyValue::UInt64(_) => TYPE_UINT64,
+                KeyValue::Int64(_) => TYPE_INT64,
+            };
+            out.push(kind);
+            out.extend_from_slice(key.as_bytes());
+            out.push(0);
+            match value {
+                KeyValue::Section(_) => out.extend(to_binary(value)),
+                KeyValue::String(text) => {
+                    out.extend_from_slice(text.as_bytes());
+                    out.push(0);
+                }
+                KeyValue::Int32(value) => out.extend_from_slice(&value.to_le_bytes()),
+                KeyValue::Float32(value) => out.extend_from_slice(&value.to_le_bytes()),
+                KeyValue::UInt64(value) => out.extend_from_slice(&value.to_le_bytes()),
+                KeyValue::Int64(value) => out.extend_from_slice(&value.to_le_bytes()),
+            }
+        }
+        out.push(TYPE_END);
+        out
+    }
+
+    #[test]
+    fn parses_nested_binary_sections_and_value_types() {
+        let tree = section(&[(
+            "480",
+            section(&[
+                ("gamename", KeyValue::String("Spacewar".to_string())),
+                ("version", KeyValue::Int32(7)),
+                ("ratio", KeyValue::Float32(0.5)),
+                (
+                    "stats",
+                    section(&[("1", section(&[("type", KeyValue::String("4".into()))]))]),
+                ),
+            ]),
+        )]);
+        let bytes = to_binary(&tree);
+        let parsed = parse_binary(&bytes).expect("parse");
+        assert_eq!(parsed, tree);
+
+        let game = parsed.get("480").expect("root");
+        assert_eq!(
+            game.get("GameName").and_then(KeyValue::as_str),
+            Some("Spacewar")
+        );
+        assert_eq!(
+            game.path(&["stats", "1", "type"])
+                .and_then(KeyValue::as_i64),
+            Some(4)
+        );
+
+        assert!(parse_binary(&bytes[..bytes.len() - 6]).is_err());
+    }
+
+    #[test]
+    fn parses_text_with_escapes_comments_and_includes() {
+        let temp_dir = unique_temp_dir("kv");
+        fs::create_dir_all(&temp_dir).expect("create dir");
+        fs::write(
+            temp_dir.join("base.vdf"),
+            "\"AppState\" { \"name\" \"Base\" \"StateFlags\" \"4\" }",
+        )
+        .expect("write base");
+        fs::write(
+            temp_dir.join("appmanifest_480.acf"),
+            r#"#base "base.vdf"
+// comentário
+"AppState"
+{
+    "appid"       "480"
+    "name"        "Space \"war\""
+    "installdir"  "C:\\Games\\Spacewar"   [$WIN32]
+    "InstalledDepots" { "481" { "manifest" "1" } }
+    "name"        "duplicate"
+}
+"#,
+        )
+        .expect("write manifest");
+
+        let root = parse_text_file(&temp_dir.join("appmanifest_480.acf")).expect("parse");
+        let app = root.get("appstate").expect("AppState");
+        assert_eq!(
+            app.get("name").and_then(KeyValue::as_str),
+            Some("Space \"war\"")
+        );
+        assert_eq!(
+            app.get("installdir").and_then(KeyValue::as_str),
+            Some("C:\\Games\\Spacewar")
+        );
+        assert_eq!(app.get("StateFlags").and_then(KeyValue::as_i64), Some(4));
+        assert_eq!(
+            app.path(&["InstalledDepots", "481", "manifest"])
+                .and_then(KeyValue::as_i64),
+            Some(1)
+        );
+
+        #[derive(Deserialize)]
+        struct Depot {
+            manifest: u64,
+        }
+        #[derive(Deserialize)]
+        struct Manifest {
+            appid: u32,
+            #[serde(rename = "StateFlags")]
+            state_flags: Option<u8>,
+            #[serde(rename = "InstalledDepots")]
+            installed_depots: std::collections::BTreeMap<String, Depot>,
+        }
+        let manifest: Manifest = parse_text(
+            r#""AppState" { "appid" "480" "StateFlags" "4" "InstalledDepots" { "481" { "manifest" "9" } } }"#,
+        )
+        .expect("parse")
+        .get("AppState")
+        .expect("AppState")
+        .deserialize()
+        .expect("deserialize");
+        assert_eq!(manifest.appid, 480);
+        assert_eq!(manifest.state_flags, Some(4));
+        assert_eq!(manifest.installed_depots["481"].manifest, 9);
+
+        assert!(parse_text("\"a\" { \"b\" \"c\"").is_err());
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -1,13 +1,18 @@
+pub mod keyvalues;
 pub mod monitor;
 pub mod steam_library;
 pub mod steam_local_profile;
+pub mod steam_player_stats;
 pub mod steam_profile_types;
+pub mod steam_schema;
 pub mod steam_types;
 pub mod steam_web_api;
 pub mod steamworks_client;
 
 pub use monitor::SteamMonitor;
 pub use steam_local_profile::get_steam_profile;
+pub use steam_player_stats::{LocalSteamState, SteamPlayerStats};
 pub use steam_profile_types::{SteamConnectionProfile, SteamSubAccount};
+pub use steam_schema::SteamSchema;
 pub use steam_types::{SteamAchievementData, SteamGame};
 pub use steam_web_api::SteamWebApi;
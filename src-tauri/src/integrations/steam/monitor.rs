@@ -1,6 +1,7 @@
 use super::steam_library;
 use super::steam_types::{SteamAchievementData, SteamGame};
 use super::steamworks_client::SteamworksClient;
+use crate::models::{StatValue, UserStat};
 use anyhow::Result;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
@@ -142,6 +143,22 @@ impl SteamMonitor {
         steamworks.get_game_achievements()
     }
 
+    pub fn get_stat(&self, stat_name: &str) -> Result<StatValue> {
+        let steamworks = self
+            .steamworks
+            .lock()
+            .map_err(|error| anyhow::anyhow!("Lock error: {}", error))?;
+        steamworks.get_stat(stat_name)
+    }
+
+    pub fn set_stats(&self, stats: &[UserStat]) -> Result<()> {
+        let steamworks = self
+            .steamworks
+            .lock()
+            .map_err(|error| anyhow::anyhow!("Lock error: {}", error))?;
+        steamworks.set_stats(stats)
+    }
+
     pub fn get_achievement_status(&self, achievement_name: &str) -> Result<bool> {
         let steamworks = self
             .steamworks
@@ -1,6 +1,18 @@
+use super::keyvalues::{self, KeyValue};
 use super::steam_types::SteamGame;
 use anyhow::Result;
-use std::path::PathBuf;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Campos usados de `appmanifest_<appid>.acf` (seção `AppState`).
+#[derive(Debug, Deserialize)]
+struct AppManifest {
+    #[serde(alias = "appID")]
+    appid: String,
+    name: String,
+    #[serde(default)]
+    installdir: String,
+}
 
 pub fn detect_installed_games() -> Result<Vec<PathBuf>> {
     let mut game_paths = Vec::new();
@@ -34,39 +46,39 @@ pub fn get_owned_games() -> Result<Vec<SteamGame>> {
             for entry in entries.flatten() {
                 let path = entry.path();
                 if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("acf") {
-                    if let Ok(content) = std::fs::read_to_string(&path) {
-                        if let (Some(appid), Some(name)) = (
-                            parse_acf_value(&content, "appid"),
-                            parse_acf_value(&content, "name"),
-                        ) {
-                            if games.iter().any(|g: &SteamGame| g.game_id == appid) {
-                                continue;
-                            }
-
-                            let installdir =
-                                parse_acf_value(&content, "installdir").unwrap_or_default();
-                            let install_path = if installdir.is_empty() {
-                                folder.join("steamapps").join("common")
-                            } else {
-                                folder.join("steamapps").join("common").join(installdir)
-                            };
-
-                            games.push(SteamGame {
-                                game_id: appid,
-                                name,
-                                achievements_total: 0,
-                                achievements_current: 0,
-                                source: "steam".to_string(),
-                                library_path: folder.to_string_lossy().to_string(),
-                                install_path: install_path.to_string_lossy().to_string(),
-                                installed: Some(true),
-                                playtime_forever: None,
-                                playtime_2weeks: None,
-                                rtime_last_played: None,
-                                img_icon_url: None,
-                            });
-                        }
+                    let Some(manifest) = read_app_manifest(&path) else {
+                        continue;
+                    };
+                    if games
+                        .iter()
+                        .any(|g: &SteamGame| g.game_id == manifest.appid)
+                    {
+                        continue;
                     }
+
+                    let install_path = if manifest.installdir.is_empty() {
+                        folder.join("steamapps").join("common")
+                    } else {
+                        folder
+                            .join("steamapps")
+                            .join("common")
+                            .join(&manifest.installdir)
+                    };
+
+                    games.push(SteamGame {
+                        game_id: manifest.appid,
+                        name: manifest.name,
+                        achievements_total: 0,
+                        achievements_current: 0,
+                        source: "steam".to_string(),
+                        library_path: folder.to_string_lossy().to_string(),
+                        install_path: install_path.to_string_lossy().to_string(),
+                        installed: Some(true),
+                        playtime_forever: None,
+                        playtime_2weeks: None,
+                        rtime_last_played: None,
+                        img_icon_url: None,
+                    });
                 }
             }
         }
@@ -126,43 +138,85 @@ fn read_library_vdf(folders: &mut Vec<PathBuf>, library_vdf: PathBuf) {
         return;
     }
 
-    if let Ok(content) = std::fs::read_to_string(&library_vdf) {
-        for line in content.lines() {
-            if line.contains("\"path\"") {
-                if let Some(path_str) = line.split('"').nth(3) {
-                    #[cfg(target_os = "windows")]
-                    let path = PathBuf::from(path_str.replace("\\\\", "\\"));
-                    #[cfg(not(target_os = "windows"))]
-                    let path = PathBuf::from(path_str);
-
-                    if path.exists() {
-                        push_unique(folders, path);
-                    }
-                }
-            }
+    let root = match keyvalues::parse_text_file(&library_vdf) {
+        Ok(root) => root,
+        Err(e) => {
+            log::warn!("Failed to read {}: {:#}", library_vdf.display(), e);
+            return;
+        }
+    };
+
+    for path in library_paths(&root) {
+        if path.exists() {
+            push_unique(folders, path);
         }
     }
 }
 
+/// Bibliotecas de `libraryfolders.vdf`: `"0" { "path" "..." }` no formato atual,
+/// ou `"1" "D:\\SteamLibrary"` no formato antigo.
+fn library_paths(root: &KeyValue) -> Vec<PathBuf> {
+    let Some(libraries) = root.get("libraryfolders") else {
+        return Vec::new();
+    };
+
+    libraries
+        .entries()
+        .iter()
+        .filter(|(key, _)| key.parse::<u32>().is_ok())
+        .filter_map(|(_, entry)| match entry {
+            KeyValue::Section(_) => entry.get("path").and_then(KeyValue::as_str),
+            other => other.as_str(),
+        })
+        .map(PathBuf::from)
+        .collect()
+}
+
 fn push_unique(paths: &mut Vec<PathBuf>, path: PathBuf) {
     if !paths.contains(&path) {
         paths.push(path);
     }
 }
 
-fn parse_acf_value(content: &str, key: &str) -> Option<String> {
-    let key_with_quotes = format!("\"{}\"", key);
-    for line in content.lines() {
-        let line = line.trim();
-        if line
-            .to_lowercase()
-            .contains(&key_with_quotes.to_lowercase())
-        {
-            let parts: Vec<&str> = line.split('"').collect();
-            if parts.len() >= 4 {
-                return Some(parts[3].to_string());
-            }
-        }
+fn read_app_manifest(path: &Path) -> Option<AppManifest> {
+    let root = keyvalues::parse_text_file(path)
+        .map_err(|e| log::debug!("Skipping unreadable app manifest: {:#}", e))
+        .ok()?;
+    root.get("AppState")?
+        .deserialize()
+        .map_err(|e| log::debug!("Skipping app manifest {}: {}", path.display(), e))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_current_and_legacy_library_folders() {
+        let current = keyvalues::parse_text(
+            r#""libraryfolders"
+{
+    "0" { "path" "C:\\Program Files (x86)\\Steam" "apps" { "480" "1234" } }
+    "1" { "path" "D:\\SteamLibrary" }
+}"#,
+        )
+        .expect("parse");
+        assert_eq!(
+            library_paths(&current),
+            vec![
+                PathBuf::from(r"C:\Program Files (x86)\Steam"),
+                PathBuf::from(r"D:\SteamLibrary"),
+            ]
+        );
+
+        let legacy = keyvalues::parse_text(
+            r#""LibraryFolders" { "TimeNextStatsReport" "1700000000" "1" "/mnt/games/Steam" }"#,
+        )
+        .expect("parse");
+        assert_eq!(
+            library_paths(&legacy),
+            vec![PathBuf::from("/mnt/games/Steam")]
+        );
     }
-    None
 }
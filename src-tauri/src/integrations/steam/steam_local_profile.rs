@@ -1,7 +1,7 @@
+use super::keyvalues::{self, KeyValue};
 use super::steam_profile_types::{SteamConnectionProfile, SteamSubAccount};
-use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 
 const STEAM_ID64_BASE: u64 = 76_561_197_960_265_728;
 
@@ -13,6 +13,17 @@ struct LoginUser {
     most_recent: bool,
 }
 
+/// Entrada de `loginusers.vdf` (`"users" { "<steamid64>" { ... } }`).
+#[derive(Debug, Deserialize)]
+struct LoginUserEntry {
+    #[serde(rename = "AccountName", alias = "accountname")]
+    account_name: Option<String>,
+    #[serde(rename = "PersonaName", alias = "personaname")]
+    persona_name: Option<String>,
+    #[serde(rename = "MostRecent", alias = "mostrecent", default)]
+    most_recent: bool,
+}
+
 pub fn get_steam_profile() -> Result<Option<SteamConnectionProfile>, String> {
     let Some(steam_path) = steam_install_path() else {
         return Ok(None);
@@ -23,8 +34,8 @@ pub fn get_steam_profile() -> Result<Option<SteamConnectionProfile>, String> {
         return Ok(None);
     }
 
-    let loginusers = fs::read_to_string(&loginusers_path)
-        .map_err(|e| format!("Failed to read Steam loginusers.vdf: {}", e))?;
+    let loginusers = keyvalues::parse_text_file(&loginusers_path)
+        .map_err(|e| format!("Failed to read Steam loginusers.vdf: {:#}", e))?;
 
     let all_users = parse_loginusers(&loginusers);
     if all_users.is_empty() {
@@ -36,29 +47,23 @@ pub fn get_steam_profile() -> Result<Option<SteamConnectionProfile>, String> {
     let user = &all_users[primary_idx];
     let steam_id64 = user.steam_id64.clone();
     let account_id = steam_id64_to_account_id(&steam_id64)?;
-    let localconfig_path = steam_path
-        .join("userdata")
-        .join(account_id.to_string())
-        .join("config")
-        .join("localconfig.vdf");
-
-    let localconfig = fs::read_to_string(&localconfig_path).ok();
+    let localconfig = read_localconfig(&steam_path, account_id);
     let avatar_hash = localconfig
-        .as_deref()
-        .and_then(|text| find_vdf_value_near_account(text, &account_id.to_string(), "avatar"))
+        .as_ref()
+        .and_then(|config| own_friend_value(config, account_id, "avatar"))
         .or_else(|| {
             localconfig
-                .as_deref()
-                .and_then(|text| find_vdf_value(text, "avatar"))
+                .as_ref()
+                .and_then(|config| find_string(config, "avatar"))
         });
 
     let persona_name = localconfig
-        .as_deref()
-        .and_then(|text| find_vdf_value_near_account(text, &account_id.to_string(), "name"))
+        .as_ref()
+        .and_then(|config| own_friend_value(config, account_id, "name"))
         .or_else(|| {
             localconfig
-                .as_deref()
-                .and_then(|text| find_vdf_value(text, "PersonaName"))
+                .as_ref()
+                .and_then(|config| find_string(config, "PersonaName"))
         })
         .or(user.persona_name.clone())
         .unwrap_or_else(|| {
@@ -89,15 +94,10 @@ pub fn get_steam_profile() -> Result<Option<SteamConnectionProfile>, String> {
                 .or_else(|| u.account_name.clone())
                 .unwrap_or_else(|| "Steam Account".to_string());
 
-            let sub_localconfig_path = steam_path
-                .join("userdata")
-                .join(acc_id.to_string())
-                .join("config")
-                .join("localconfig.vdf");
-            let sub_localconfig = fs::read_to_string(&sub_localconfig_path).ok();
-            let sub_avatar_hash = sub_localconfig
-                .as_deref()
-                .and_then(|text| find_vdf_value(text, "avatar"));
+            let sub_localconfig = read_localconfig(&steam_path, acc_id);
+            let sub_avatar_hash = sub_localconfig.as_ref().and_then(|config| {
+                own_friend_value(config, acc_id, "avatar").or_else(|| find_string(config, "avatar"))
+            });
             let sub_avatar_url = sub_avatar_hash
                 .map(|hash| format!("https://avatars.akamai.steamstatic.com/{}_full.jpg", hash));
 
@@ -129,7 +129,7 @@ pub fn get_steam_profile() -> Result<Option<SteamConnectionProfile>, String> {
     }))
 }
 
-fn steam_install_path() -> Option<PathBuf> {
+pub(crate) fn steam_install_path() -> Option<PathBuf> {
     let home = dirs::home_dir();
 
     #[cfg(target_os = "linux")]
@@ -157,115 +157,85 @@ fn steam_install_path() -> Option<PathBuf> {
     None
 }
 
-fn parse_loginusers(text: &str) -> Vec<LoginUser> {
-    let mut users = Vec::new();
-    let lines: Vec<&str> = text.lines().collect();
-    let mut index = 0usize;
-
-    while index < lines.len() {
-        let line = lines[index].trim();
-        let Some(steam_id64) = vdf_standalone_key(line) else {
-            index += 1;
-            continue;
-        };
-
-        if steam_id64.len() != 17 || !steam_id64.chars().all(|c| c.is_ascii_digit()) {
-            index += 1;
-            continue;
-        }
-
-        let mut values = HashMap::new();
-        let mut depth = 0i32;
-        index += 1;
-
-        while index < lines.len() {
-            let current = lines[index].trim();
-
-            if current == "{" {
-                depth += 1;
-                index += 1;
-                continue;
-            }
-
-            if current == "}" {
-                depth -= 1;
-                index += 1;
-                if depth <= 0 {
-                    break;
-                }
-                continue;
-            }
-
-            if let Some((key, value)) = vdf_key_value(current) {
-                values.insert(key, value);
-            }
-
-            index += 1;
-        }
-
-        users.push(LoginUser {
-            steam_id64,
-            account_name: values.get("AccountName").cloned(),
-            persona_name: values.get("PersonaName").cloned(),
-            most_recent: values.get("MostRecent").is_some_and(|value| value == "1"),
-        });
-    }
-
-    users
+/// Última sessão (unix) e tempo total de jogo (minutos) de um app na conta
+/// mais recente, de `UserLocalConfigStore/Software/Valve/Steam/apps/<appid>`.
+pub fn get_local_playtime(app_id: u32) -> Option<(i64, u64)> {
+    let steam_path = steam_install_path()?;
+    let account_id = get_steam_profile().ok().flatten()?.account_id;
+    let config = read_localconfig(&steam_path, account_id)?;
+    let app = config.path(&[
+        "UserLocalConfigStore",
+        "Software",
+        "Valve",
+        "Steam",
+        "apps",
+        &app_id.to_string(),
+    ])?;
+
+    let last_played = app.get("LastPlayed").and_then(KeyValue::as_i64)?;
+    let playtime = app.get("Playtime").and_then(KeyValue::as_u64).unwrap_or(0);
+    Some((last_played, playtime))
 }
 
-fn find_vdf_value(text: &str, wanted_key: &str) -> Option<String> {
-    text.lines()
-        .filter_map(|line| vdf_key_value(line.trim()))
-        .find_map(|(key, value)| (key == wanted_key).then_some(value))
-}
+fn parse_loginusers(root: &KeyValue) -> Vec<LoginUser> {
+    let Some(users) = root.get("users") else {
+        return Vec::new();
+    };
 
-fn find_vdf_value_near_account(text: &str, account_id: &str, wanted_key: &str) -> Option<String> {
-    let account_index = text.find(&format!("\"{}\"", account_id))?;
-    let end = text.len().min(account_index + 4096);
-    let end = text.floor_char_boundary(end);
-    let slice = &text[account_index..end];
-    find_vdf_value(slice, wanted_key)
+    users
+        .entries()
+        .iter()
+        .filter(|(steam_id64, _)| {
+            steam_id64.len() == 17 && steam_id64.chars().all(|c| c.is_ascii_digit())
+        })
+        .filter_map(|(steam_id64, entry)| {
+            let entry: LoginUserEntry = entry
+                .deserialize()
+                .map_err(|e| log::debug!("Skipping Steam login user {}: {}", steam_id64, e))
+                .ok()?;
+            Some(LoginUser {
+                steam_id64: steam_id64.clone(),
+                account_name: entry.account_name,
+                persona_name: entry.persona_name,
+                most_recent: entry.most_recent,
+            })
+        })
+        .collect()
 }
 
-fn vdf_standalone_key(line: &str) -> Option<String> {
-    if !line.starts_with('"') || !line.ends_with('"') {
+fn read_localconfig(steam_path: &Path, account_id: u64) -> Option<KeyValue> {
+    let localconfig_path = steam_path
+        .join("userdata")
+        .join(account_id.to_string())
+        .join("config")
+        .join("localconfig.vdf");
+    if !localconfig_path.exists() {
         return None;
     }
 
-    Some(line.trim_matches('"').to_string())
+    keyvalues::parse_text_file(&localconfig_path)
+        .map_err(|e| log::debug!("Failed to read Steam localconfig.vdf: {:#}", e))
+        .ok()
 }
 
-fn vdf_key_value(line: &str) -> Option<(String, String)> {
-    let mut values = Vec::new();
-    let mut current = String::new();
-    let mut in_string = false;
-    let mut escaped = false;
-
-    for character in line.chars() {
-        if in_string {
-            if escaped {
-                current.push(character);
-                escaped = false;
-            } else if character == '\\' {
-                escaped = true;
-            } else if character == '"' {
-                values.push(current.clone());
-                current.clear();
-                in_string = false;
-            } else {
-                current.push(character);
-            }
-        } else if character == '"' {
-            in_string = true;
-        }
-    }
+/// Dados da própria conta em `UserLocalConfigStore/friends/<accountid>`.
+fn own_friend_value(config: &KeyValue, account_id: u64, key: &str) -> Option<String> {
+    config
+        .path(&[
+            "UserLocalConfigStore",
+            "friends",
+            &account_id.to_string(),
+            key,
+        ])
+        .and_then(KeyValue::as_str)
+        .map(str::to_string)
+}
 
-    if values.len() >= 2 {
-        Some((values[0].clone(), values[1].clone()))
-    } else {
-        None
-    }
+fn find_string(config: &KeyValue, key: &str) -> Option<String> {
+    config
+        .find(key)
+        .and_then(KeyValue::as_str)
+        .map(str::to_string)
 }
 
 fn steam_id64_to_account_id(steam_id64: &str) -> Result<u64, String> {
@@ -0,0 +1,290 @@
+//! Stats do jogador em cache pelo cliente Steam.
+//!
+//! `<steam>/appcache/stats/UserGameStats_<accountid>_<appid>.bin` é um
+//! KeyValues binário:
+//!
+//! ```text
+//! "cache" {
+//!   "crc" ...
+//!   "<statid>" {
+//!     "data" <i32: bits das conquistas ou valor do stat>
+//!     "AchievementTimes" { "<bit>" <unix time> }
+//!   }
+//! }
+//! ```
+//!
+//! Cruzado com o [`SteamSchema`], fornece o estado real das conquistas sem
+//! Steamworks nem Web API.
+
+use super::keyvalues::{self, KeyValue};
+use super::steam_local_profile::get_steam_profile;
+use super::steam_schema::SteamSchema;
+use crate::models::{AchievementEntry, StatValue, UserStat};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Um stat gravado no cache do jogador.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerStat {
+    pub data: KeyValue,
+    /// Bit da conquista → unix time do desbloqueio.
+    pub achievement_times: HashMap<u32, i64>,
+}
+
+/// Estado local de um jogo Steam, pronto para exibir ou salvar em backup.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalSteamState {
+    pub game_id: String,
+    pub achievements: Vec<AchievementEntry>,
+    pub stats: Vec<UserStat>,
+}
+
+impl LocalSteamState {
+    /// Lê schema + stats do cache do cliente Steam para a conta mais recente
+    /// do `loginusers.vdf` (sem perfil, usa o cache mais recente do app).
+    pub fn load(app_id: u32) -> Result<Option<Self>> {
+        let Some(schema) = SteamSchema::load(app_id)? else {
+            return Ok(None);
+        };
+
+        let account_id = get_steam_profile()
+            .ok()
+            .flatten()
+            .map(|profile| profile.account_id);
+        let Some(player_stats) = SteamPlayerStats::load(app_id, account_id)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            game_id: app_id.to_string(),
+            achievements: player_stats.achievements(&schema),
+            stats: player_stats.user_stats(&schema),
+        }))
+    }
+}
+
+/// Stats e conquistas do jogador para um app.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SteamPlayerStats {
+    pub stats: HashMap<u32, PlayerStat>,
+}
+
+impl SteamPlayerStats {
+    /// Cache da conta informada ou, sem conta, o mais recente para o app.
+    pub fn find_file(app_id: u32, account_id: Option<u64>) -> Option<PathBuf> {
+        let stats_dir = SteamSchema::stats_cache_dir()?;
+
+        if let Some(account_id) = account_id {
+            let path = stats_dir.join(format!("UserGameStats_{}_{}.bin", account_id, app_id));
+            return path.is_file().then_some(path);
+        }
+
+        let suffix = format!("_{}.bin", app_id);
+        fs::read_dir(&stats_dir)
+            .ok()?
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.starts_with("UserGameStats_") && name.ends_with(&suffix)
+            })
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+            .map(|entry| entry.path())
+    }
+
+    /// Lê o cache do app, se existir.
+    pub fn load(app_id: u32, account_id: Option<u64>) -> Result<Option<Self>> {
+        match Self::find_file(app_id, account_id) {
+            Some(path) => Self::load_from(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read Steam player stats: {}", path.display()))?;
+        Self::parse(&bytes)
+            .with_context(|| format!("Failed to parse Steam player stats: {}", path.display()))
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let root = keyvalues::parse_binary(bytes)?;
+        let cache = root
+            .get("cache")
+            .or_else(|| root.entries().first().map(|(_, value)| value))
+            .context("Steam player stats have no root section")?;
+
+        let stats = cache
+            .entries()
+            .iter()
+            .filter_map(|(key, stat)| {
+                let stat_id = key.parse::<u32>().ok()?;
+                let data = stat.get("data")?.clone();
+                let achievement_times = stat
+                    .get("AchievementTimes")
+                    .map(KeyValue::entries)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|(bit, time)| Some((bit.parse().ok()?, time.as_i64()?)))
+                    .collect();
+
+                Some((
+                    stat_id,
+                    PlayerStat {
+                        data,
+                        achievement_times,
+                    },
+                ))
+            })
+            .collect();
+
+        Ok(Self { stats })
+    }
+
+    /// Estado de cada conquista do schema (bloqueadas incluídas).
+    pub fn achievements(&self, schema: &SteamSchema) -> Vec<AchievementEntry> {
+        schema
+            .achievements
+            .iter()
+            .map(|achievement| {
+                let stat = self.stats.get(&achievement.stat_id);
+                let bits = stat.and_then(|stat| stat.data.as_i64()).unwrap_or(0) as u32;
+                let achieved = achievement.bit < 32 && bits & (1 << achievement.bit) != 0;
+                let unlock_time = stat
+                    .and_then(|stat| stat.achievement_times.get(&achievement.bit))
+                    .copied()
+                    .filter(|_| achieved)
+                    .unwrap_or(0);
+
+                AchievementEntry {
+                    name: achievement.api_name.clone(),
+                    achieved,
+                    unlock_time,
+                    cur_progress: None,
+                    max_progress: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Stats comuns com nome do schema; os que nunca foram gravados ficam de fora.
+    pub fn user_stats(&self, schema: &SteamSchema) -> Vec<UserStat> {
+        schema
+            .stats
+            .iter()
+            .filter_map(|schema_stat| {
+                let data = &self.stats.get(&schema_stat.id)?.data;
+                let value = match (data, schema_stat.is_float) {
+                    (KeyValue::Float32(value), _) => StatValue::Float(*value),
+                    (data, true) => StatValue::Float(f32::from_bits(data.as_i64()? as u32)),
+                    (data, false) => StatValue::Int(data.as_i64()? as i32),
+                };
+                Some(UserStat {
+                    name: schema_stat.name.clone(),
+                    value,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::steam::keyvalues::tests::{section, to_binary};
+    use crate::integrations::steam::steam_schema::{SchemaAchievement, SchemaStat};
+
+    fn schema_achievement(name: &str, bit: u32) -> SchemaAchievement {
+        SchemaAchievement {
+            api_name: name.to_string(),
+            stat_id: 1,
+            bit,
+            display_names: HashMap::new(),
+            descriptions: HashMap::new(),
+            hidden: false,
+            icon: None,
+            icon_gray: None,
+        }
+    }
+
+    #[test]
+    fn maps_achievement_bits_and_stats_through_the_schema() {
+        let cache = section(&[(
+            "cache",
+            section(&[
+                ("crc", KeyValue::Int32(123)),
+                (
+                    "1",
+                    section(&[
+                        ("data", KeyValue::Int32(0b101)),
+                        (
+                            "AchievementTimes",
+                            section(&[
+                                ("0", KeyValue::Int32(1_700_000_000)),
+                                ("2", KeyValue::Int32(1_700_000_100)),
+                            ]),
+                        ),
+                    ]),
+                ),
+                ("7", section(&[("data", KeyValue::Int32(42))])),
+                (
+                    "8",
+                    section(&[("data", KeyValue::Int32(2.5f32.to_bits() as i32))]),
+                ),
+            ]),
+        )]);
+        let schema = SteamSchema {
+            app_id: 480,
+            game_name: None,
+            achievements: vec![
+                schema_achievement("ACH_A", 0),
+                schema_achievement("ACH_B", 1),
+                schema_achievement("ACH_C", 2),
+            ],
+            stats: vec![
+                SchemaStat {
+                    id: 7,
+                    name: "KILLS".to_string(),
+                    is_float: false,
+                },
+                SchemaStat {
+                    id: 8,
+                    name: "DISTANCE".to_string(),
+                    is_float: true,
+                },
+            ],
+        };
+
+        let stats = SteamPlayerStats::parse(&to_binary(&cache)).expect("parse");
+        let achievements = stats.achievements(&schema);
+        let states: Vec<(&str, bool, i64)> = achievements
+            .iter()
+            .map(|a| (a.name.as_str(), a.achieved, a.unlock_time))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("ACH_A", true, 1_700_000_000),
+                ("ACH_B", false, 0),
+                ("ACH_C", true, 1_700_000_100),
+            ]
+        );
+
+        assert_eq!(
+            stats.user_stats(&schema),
+            vec![
+                UserStat {
+                    name: "KILLS".to_string(),
+                    value: StatValue::Int(42),
+                },
+                UserStat {
+                    name: "DISTANCE".to_string(),
+                    value: StatValue::Float(2.5),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Schema de conquistas em cache pelo cliente Steam.
+//!
+//! `<steam>/appcache/stats/UserGameStatsSchema_<appid>.bin` é um KeyValues
+//! binário com nomes, descrições (em todos os idiomas), flag de oculta e
+//! ícones de cada conquista. Permite montar a lista sem Hydra nem Web API.
+
+use super::keyvalues::{self, KeyValue};
+use super::steam_local_profile::steam_install_path;
+use crate::models::SteamAchievement;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ICON_BASE_URL: &str = "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/apps";
+const FALLBACK_LANGUAGE: &str = "english";
+
+/// Conquista descrita no schema local.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaAchievement {
+    pub api_name: String,
+    /// Stat que guarda os bits de conquista (ver `UserGameStats_*.bin`).
+    pub stat_id: u32,
+    pub bit: u32,
+    /// Idioma Steam (`english`, `brazilian`...) → texto.
+    pub display_names: HashMap<String, String>,
+    pub descriptions: HashMap<String, String>,
+    pub hidden: bool,
+    pub icon: Option<String>,
+    pub icon_gray: Option<String>,
+}
+
+impl SchemaAchievement {
+    pub fn display_name(&self, language: &str) -> Option<&str> {
+        localized(&self.display_names, language)
+    }
+
+    pub fn description(&self, language: &str) -> Option<&str> {
+        localized(&self.descriptions, language)
+    }
+}
+
+/// Estatística comum (não-conquista) descrita no schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaStat {
+    pub id: u32,
+    pub name: String,
+    /// `type` 2 (float) e 3 (média) guardam f32; os demais, i32.
+    pub is_float: bool,
+}
+
+/// Schema de um jogo lido de `UserGameStatsSchema_<appid>.bin`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SteamSchema {
+    pub app_id: u32,
+    pub game_name: Option<String>,
+    pub achievements: Vec<SchemaAchievement>,
+    pub stats: Vec<SchemaStat>,
+}
+
+impl SteamSchema {
+    /// Diretório `appcache/stats` da instalação Steam detectada.
+    pub fn stats_cache_dir() -> Option<PathBuf> {
+        steam_install_path()
+            .map(|path| path.join("appcache").join("stats"))
+            .filter(|path| path.is_dir())
+    }
+
+    /// Schema em cache para o app, se o cliente Steam já o baixou.
+    pub fn load(app_id: u32) -> Result<Option<Self>> {
+        let Some(stats_dir) = Self::stats_cache_dir() else {
+            return Ok(None);
+        };
+        let path = stats_dir.join(format!("UserGameStatsSchema_{}.bin", app_id));
+        if !path.is_file() {
+            return Ok(None);
+        }
+        Self::load_from(&path, app_id).map(Some)
+    }
+
+    pub fn load_from(path: &Path, app_id: u32) -> Result<Self> {
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read Steam schema: {}", path.display()))?;
+        Self::parse(&bytes, app_id)
+            .with_context(|| format!("Failed to parse Steam schema: {}", path.display()))
+    }
+
+    pub fn parse(bytes: &[u8], app_id: u32) -> Result<Self> {
+        let root = keyvalues::parse_binary(bytes)?;
+        // A raiz é `"<appid>" { ... }`; aceita qualquer nome se o appid divergir
+        let game = root
+            .get(&app_id.to_string())
+            .or_else(|| root.entries().first().map(|(_, value)| value))
+            .context("Steam schema has no root section")?;
+
+        let mut achievements = Vec::new();
+        let mut stats = Vec::new();
+        for (stat_key, stat) in game.get("stats").map(KeyValue::entries).unwrap_or_default() {
+            let Some(stat_id) = stat
+                .get("id")
+                .and_then(KeyValue::as_i64)
+                .or_else(|| stat_key.parse().ok())
+            else {
+                continue;
+            };
+            let Some(bits) = stat.get("bits") else {
+                if let Some(name) = stat.get("name").and_then(KeyValue::as_str) {
+                    stats.push(SchemaStat {
+                        id: stat_id as u32,
+                        name: name.to_string(),
+                        is_float: is_float_stat(stat),
+                    });
+                }
+                continue;
+            };
+
+            for (bit_key, entry) in bits.entries() {
+                if let Some(achievement) = parse_achievement(stat_id as u32, bit_key, entry) {
+                    achievements.push(achievement);
+                }
+            }
+        }
+
+        achievements.sort_by_key(|achievement| (achievement.stat_id, achievement.bit));
+
+        Ok(Self {
+            app_id,
+            game_name: game
+                .get("gamename")
+                .and_then(KeyValue::as_str)
+                .map(str::to_string),
+            achievements,
+            stats,
+        })
+    }
+
+    /// Converte para o mesmo formato da Steam Web API (`GetSchemaForGame`).
+    pub fn to_steam_achievements(&self, language: &str) -> Vec<SteamAchievement> {
+        self.achievements
+            .iter()
+            .map(|achievement| SteamAchievement {
+                apiname: achievement.api_name.clone(),
+                achieved: 0,
+                unlocktime: 0,
+                name: achievement.display_name(language).map(str::to_string),
+                description: achievement.description(language).map(str::to_string),
+                icon: achievement.icon.as_deref().map(|icon| self.icon_url(icon)),
+                icongray: achievement
+                    .icon_gray
+                    .as_deref()
+                    .map(|icon| self.icon_url(icon)),
+                percent: 0.0,
+                hidden: achievement.hidden,
+            })
+            .collect()
+    }
+
+    fn icon_url(&self, icon: &str) -> String {
+        format!("{}/{}/{}", ICON_BASE_URL, self.app_id, icon)
+    }
+}
+
+fn parse_achievement(stat_id: u32, bit_key: &str, entry: &KeyValue) -> Option<SchemaAchievement> {
+    let api_name = entry.get("name").and_then(KeyValue::as_str)?.to_string();
+    let bit = entry
+        .get("bit")
+        .and_then(KeyValue::as_i64)
+        .or_else(|| bit_key.parse().ok())? as u32;
+    let display = entry.get("display");
+    let display_value = |key: &str| display.and_then(|display| display.get(key));
+
+    Some(SchemaAchievement {
+        api_name,
+        stat_id,
+        bit,
+        display_names: localized_map(display_value("name")),
+        descriptions: localized_map(display_value("desc")),
+        hidden: display_value("hidden")
+            .and_then(KeyValue::as_bool)
+            .unwrap_or(false),
+        icon: display_value("icon")
+            .and_then(KeyValue::as_str)
+            .filter(|icon| !icon.is_empty())
+            .map(str::to_string),
+        icon_gray: display_value("icon_gray")
+            .and_then(KeyValue::as_str)
+            .filter(|icon| !icon.is_empty())
+            .map(str::to_string),
+    })
+}
+
+/// `type` numérico (`2`/`3`) ou nomeado (`FLOAT`/`AVGRATE`).
+fn is_float_stat(stat: &KeyValue) -> bool {
+    match stat.get("type") {
+        Some(KeyValue::String(kind)) if kind.parse::<i64>().is_err() => {
+            kind.eq_ignore_ascii_case("float") || kind.eq_ignore_ascii_case("avgrate")
+        }
+        Some(kind) => matches!(kind.as_i64(), Some(2) | Some(3)),
+        None => false,
+    }
+}
+
+/// `{ "english" "...", "german" "...", "token" "..." }` ou uma string simples.
+fn localized_map(value: Option<&KeyValue>) -> HashMap<String, String> {
+    match value {
+        Some(KeyValue::String(text)) => {
+            HashMap::from([(FALLBACK_LANGUAGE.to_string(), text.clone())])
+        }
+        Some(section) => section
+            .entries()
+            .iter()
+            .filter(|(language, _)| !language.eq_ignore_ascii_case("token"))
+            .filter_map(|(language, text)| {
+                text.as_str()
+                    .map(|text| (language.to_lowercase(), text.to_string()))
+            })
+            .collect(),
+        None => HashMap::new(),
+    }
+}
+
+fn localized<'a>(texts: &'a HashMap<String, String>, language: &str) -> Option<&'a str> {
+    texts
+        .get(language)
+        .or_else(|| texts.get(FALLBACK_LANGUAGE))
+        .or_else(|| texts.values().next())
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::steam::keyvalues::tests::{section, to_binary};
+
+    #[test]
+    fn reads_achievements_from_binary_schema() {
+        let text = |value: &str| KeyValue::String(value.to_string());
+        let schema = section(&[(
+            "480",
+            section(&[
+                ("gamename", text("Spacewar")),
+                (
+                    "stats",
+                    section(&[(
+                        "1",
+                        section(&[
+                            ("type", text("4")),
+                            ("id", KeyValue::Int32(1)),
+                            (
+                                "bits",
+                                section(&[(
+                                    "0",
+                                    section(&[
+                                        ("name", text("ACH_WIN_ONE_GAME")),
+                                        ("bit", KeyValue::Int32(0)),
+                                        (
+                                            "display",
+                                            section(&[
+                                                (
+                                                    "name",
+                                                    section(&[
+                                                        ("english", text("Winner")),
+                                                        ("brazilian", text("Vencedor")),
+                                                        ("token", text("NEW_ACHIEVEMENT_1_0")),
+                                                    ]),
+                                                ),
+                                                ("desc", section(&[("english", text("Win"))])),
+                                                ("hidden", text("1")),
+                                                ("icon", text("abc.jpg")),
+                                            ]),
+                                        ),
+                                    ]),
+                                )]),
+                            ),
+                        ]),
+                    )]),
+                ),
+            ]),
+        )]);
+
+        let parsed = SteamSchema::parse(&to_binary(&schema), 480).expect("parse");
+        assert_eq!(parsed.game_name.as_deref(), Some("Spacewar"));
+
+        let achievements = parsed.to_steam_achievements("brazilian");
+        assert_eq!(achievements.len(), 1);
+        assert_eq!(achievements[0].apiname, "ACH_WIN_ONE_GAME");
+        assert_eq!(achievements[0].name.as_deref(), Some("Vencedor"));
+        assert_eq!(achievements[0].description.as_deref(), Some("Win"));
+        assert!(achievements[0].hidden);
+        assert_eq!(
+            achievements[0].icon.as_deref(),
+            Some(
+                "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/apps/480/abc.jpg"
+            )
+        );
+        assert_eq!(achievements[0].icongray, None);
+    }
+}
@@ -1,4 +1,5 @@
 use super::steam_types::SteamAchievementData;
+use crate::models::{StatValue, UserStat};
 use anyhow::Context;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
@@ -239,6 +240,61 @@ impl SteamworksClient {
         .map_err(|_| anyhow::anyhow!("Steamworks panicked while reading achievement status"))?
     }
 
+    /// Lê uma estatística do app atual. O Steamworks não expõe o tipo, então
+    /// tenta i32 e depois f32.
+    pub fn get_stat(&self, stat_name: &str) -> anyhow::Result<StatValue> {
+        if !self.enabled {
+            return Err(anyhow::anyhow!("Steam integration not enabled"));
+        }
+
+        let client = self
+            .client
+            .as_ref()
+            .context("Steam client not initialized")?;
+
+        catch_unwind(AssertUnwindSafe(|| {
+            let user_stats = client.user_stats();
+            if let Ok(value) = user_stats.get_stat_i32(stat_name) {
+                return Ok(StatValue::Int(value));
+            }
+            user_stats
+                .get_stat_f32(stat_name)
+                .map(StatValue::Float)
+                .map_err(|_| anyhow::anyhow!("Failed to get stat {}", stat_name))
+        }))
+        .map_err(|_| anyhow::anyhow!("Steamworks panicked while reading stat"))?
+    }
+
+    pub fn set_stats(&self, stats: &[UserStat]) -> anyhow::Result<()> {
+        if !self.enabled {
+            return Err(anyhow::anyhow!("Steam integration not enabled"));
+        }
+
+        let client = self
+            .client
+            .as_ref()
+            .context("Steam client not initialized")?;
+
+        catch_unwind(AssertUnwindSafe(|| -> anyhow::Result<()> {
+            let user_stats = client.user_stats();
+            for stat in stats {
+                let result = match stat.value {
+                    StatValue::Int(value) => user_stats.set_stat_i32(&stat.name, value),
+                    StatValue::Float(value) => user_stats.set_stat_f32(&stat.name, value),
+                };
+                result.map_err(|_| anyhow::anyhow!("Failed to set stat {}", stat.name))?;
+            }
+
+            user_stats
+                .store_stats()
+                .map_err(|_| anyhow::anyhow!("Failed to store stats"))
+        }))
+        .map_err(|_| anyhow::anyhow!("Steamworks panicked while updating stats"))??;
+
+        Self::pump_callbacks(client, 5);
+        Ok(())
+    }
+
     pub fn run_callbacks(&self) {
         if !self.enabled {
             return;
@@ -0,0 +1,374 @@
+//! Jobs em segundo plano para operações em lote.
+//!
+//! Unlock, exportação e backup de vários jogos rodam como um job: recebem um
+//! ID, emitem progresso a cada item, podem ser cancelados entre um item e
+//! outro e terminam com um relatório por item. O [`JobManager`] fica no
+//! `AppState`; o laço de cada tipo de job vive no comando correspondente.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Relatórios de jobs concluídos mantidos para consulta.
+const MAX_FINISHED_JOBS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobKind {
+    Unlock,
+    Export,
+    Backup,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobItemStatus {
+    Succeeded,
+    Failed,
+    /// Não processado por causa do cancelamento
+    Skipped,
+}
+
+/// Resultado de um item (em geral, um jogo)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobItemResult {
+    pub item: String,
+    pub status: JobItemStatus,
+    pub message: Option<String>,
+}
+
+/// Evento `job-progress`, emitido antes de cada item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobProgress {
+    pub job_id: String,
+    pub kind: JobKind,
+    /// Posição do item atual, a partir de 1
+    pub current: usize,
+    pub total: usize,
+    pub item: String,
+}
+
+/// Evento `job-finished` e resultado de `get_job_report`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobReport {
+    pub job_id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    /// Unix ms
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    pub items: Vec<JobItemResult>,
+    /// Motivo da falha do job como um todo
+    pub error: Option<String>,
+}
+
+/// Sinal de cancelamento compartilhado entre o job e quem o cancela.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Relatório compartilhado entre o job e o [`JobManager`], para que
+/// `get_job_report` veja os itens já processados.
+type SharedReport = Arc<Mutex<JobReport>>;
+
+/// Execução de um job: acumula os resultados até [`JobManager::finish`].
+pub struct JobRun {
+    job_id: String,
+    report: SharedReport,
+    token: CancellationToken,
+}
+
+impl JobRun {
+    pub fn id(&self) -> &str {
+        &self.job_id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Progresso do próximo item.
+    pub fn progress(&self, total: usize, item: &str) -> JobProgress {
+        let (kind, done) = self.with_report(|report| (report.kind, report.items.len()));
+        JobProgress {
+            job_id: self.job_id.clone(),
+            kind,
+            current: done + 1,
+            total,
+            item: item.to_string(),
+        }
+    }
+
+    pub fn record(&mut self, item: &str, result: Result<Option<String>, String>) {
+        let (status, message) = match result {
+            Ok(message) => (JobItemStatus::Succeeded, message),
+            Err(message) => (JobItemStatus::Failed, Some(message)),
+        };
+        self.with_report(|report| {
+            report.items.push(JobItemResult {
+                item: item.to_string(),
+                status,
+                message,
+            })
+        });
+    }
+
+    /// Marca como pulados os itens que o cancelamento impediu.
+    pub fn skip<'a>(&mut self, items: impl IntoIterator<Item = &'a str>) {
+        self.with_report(|report| {
+            for item in items {
+                report.items.push(JobItemResult {
+                    item: item.to_string(),
+                    status: JobItemStatus::Skipped,
+                    message: None,
+                });
+            }
+        });
+    }
+
+    fn with_report<T>(&self, f: impl FnOnce(&mut JobReport) -> T) -> T {
+        let mut report = self
+            .report
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut report)
+    }
+}
+
+#[derive(Default)]
+pub struct JobManager {
+    running: Mutex<HashMap<String, (SharedReport, CancellationToken)>>,
+    finished: Mutex<VecDeque<JobReport>>,
+}
+
+impl JobManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra um novo job; não inicia (devolve `None`) se já houver um job
+    /// do mesmo tipo em andamento.
+    pub fn try_start(&self, kind: JobKind) -> Option<JobRun> {
+        let mut running = self.running.lock().ok()?;
+        if running
+            .values()
+            .any(|(report, _)| report_kind(report) == kind)
+        {
+            return None;
+        }
+
+        let run = Self::new_run(kind);
+        running.insert(
+            run.id().to_string(),
+            (run.report.clone(), run.token.clone()),
+        );
+        log::info!("Started {:?} job {}", kind, run.id());
+        Some(run)
+    }
+
+    fn new_run(kind: JobKind) -> JobRun {
+        let started_at = now_millis();
+        let job_id =
+            format!("{:?}-{:x}-{:04x}", kind, started_at, rand::random::<u16>()).to_lowercase();
+        let report = JobReport {
+            job_id: job_id.clone(),
+            kind,
+            status: JobStatus::Running,
+            started_at,
+            finished_at: None,
+            items: Vec::new(),
+            error: None,
+        };
+        JobRun {
+            job_id,
+            report: Arc::new(Mutex::new(report)),
+            token: CancellationToken::default(),
+        }
+    }
+
+    /// Pede o cancelamento; o job para antes do próximo item.
+    pub fn cancel(&self, job_id: &str) -> bool {
+        let Ok(running) = self.running.lock() else {
+            return false;
+        };
+        match running.get(job_id) {
+            Some((_, token)) => {
+                token.cancel();
+                log::info!("Cancellation requested for job {}", job_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Se já há um job do tipo em andamento.
+    pub fn is_running(&self, kind: JobKind) -> bool {
+        self.running
+            .lock()
+            .map(|running| {
+                running
+                    .values()
+                    .any(|(report, _)| report_kind(report) == kind)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Encerra o job e guarda o relatório final.
+    ///
+    /// `error` marca o job como falho (ex.: o backup não pôde ser gravado);
+    /// sem ele, o status é `cancelled` se o cancelamento foi pedido.
+    pub fn finish(&self, run: JobRun, error: Option<String>) -> JobReport {
+        let report = run.with_report(|report| {
+            report.finished_at = Some(now_millis());
+            report.status = match &error {
+                Some(e) => {
+                    log::warn!("Job {} failed: {}", report.job_id, e);
+                    JobStatus::Failed
+                }
+                None if run.is_cancelled() => JobStatus::Cancelled,
+                None => JobStatus::Completed,
+            };
+            report.error = error;
+            report.clone()
+        });
+
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(&report.job_id);
+        }
+        if let Ok(mut finished) = self.finished.lock() {
+            finished.push_front(report.clone());
+            finished.truncate(MAX_FINISHED_JOBS);
+        }
+        log::info!("Job {} finished: {:?}", report.job_id, report.status);
+        report
+    }
+
+    /// Relatório de um job em andamento (com os itens já processados) ou
+    /// concluído.
+    pub fn report(&self, job_id: &str) -> Option<JobReport> {
+        let running = self
+            .running
+            .lock()
+            .ok()
+            .and_then(|running| running.get(job_id).map(|(report, _)| snapshot(report)));
+        running.or_else(|| {
+            self.finished
+                .lock()
+                .ok()?
+                .iter()
+                .find(|report| report.job_id == job_id)
+                .cloned()
+        })
+    }
+
+    /// Jobs em andamento e concluídos recentemente, dos mais novos aos mais antigos.
+    pub fn list(&self) -> Vec<JobReport> {
+        let mut reports: Vec<JobReport> = self
+            .running
+            .lock()
+            .map(|running| {
+                running
+                    .values()
+                    .map(|(report, _)| snapshot(report))
+                    .collect()
+            })
+            .unwrap_or_default();
+        reports.sort_by_key(|report| std::cmp::Reverse(report.started_at));
+        if let Ok(finished) = self.finished.lock() {
+            reports.extend(finished.iter().cloned());
+        }
+        reports
+    }
+}
+
+fn snapshot(report: &SharedReport) -> JobReport {
+    report
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+fn report_kind(report: &SharedReport) -> JobKind {
+    report
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .kind
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelled_jobs_report_processed_and_skipped_items() {
+        let manager = JobManager::new();
+        let items = ["480", "570", "730"];
+
+        let mut run = manager.try_start(JobKind::Unlock).expect("start");
+        let job_id = run.id().to_string();
+        assert!(manager.is_running(JobKind::Unlock));
+        assert!(!manager.is_running(JobKind::Export));
+        assert!(manager.try_start(JobKind::Unlock).is_none());
+
+        for (index, item) in items.iter().enumerate() {
+            if run.is_cancelled() {
+                run.skip(items[index..].iter().copied());
+                break;
+            }
+            assert_eq!(run.progress(items.len(), item).current, index + 1);
+            run.record(item, Ok(None));
+            let live = manager.report(&job_id).expect("running report");
+            assert_eq!(live.status, JobStatus::Running);
+            assert_eq!(live.items.len(), index + 1);
+            assert!(manager.cancel(&job_id));
+        }
+
+        let report = manager.finish(run, None);
+        assert_eq!(report.status, JobStatus::Cancelled);
+        let statuses: Vec<JobItemStatus> = report.items.iter().map(|item| item.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                JobItemStatus::Succeeded,
+                JobItemStatus::Skipped,
+                JobItemStatus::Skipped
+            ]
+        );
+        assert!(!manager.cancel(&job_id));
+        assert!(!manager.is_running(JobKind::Unlock));
+        assert_eq!(
+            manager.report(&job_id).map(|report| report.status),
+            Some(JobStatus::Cancelled)
+        );
+    }
+}
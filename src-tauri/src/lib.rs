@@ -1,10 +1,16 @@
 // Módulos
+pub mod background;
 pub mod commands;
+pub mod formats;
 pub mod integrations;
+pub mod jobs;
 pub mod logger;
 pub mod models;
 pub mod monitor;
 pub mod parser;
+pub mod stats;
+#[cfg(test)]
+mod test_support;
 pub mod unlocker;
 pub mod utils;
 pub mod wine;
@@ -18,6 +24,7 @@ use tauri::Manager;
 pub struct AppState {
     pub monitor: Mutex<Option<AchievementMonitor>>,
     pub steam_monitor: Mutex<Option<SteamMonitor>>,
+    pub jobs: jobs::JobManager,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -30,6 +37,11 @@ pub fn run() {
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            Some(vec![background::BACKGROUND_ARG]),
+        ))
         .setup(|app| {
             log::info!("Application initializing...");
 
@@ -212,8 +224,25 @@ pub fn run() {
             app.manage(AppState {
                 monitor: Mutex::new(Some(monitor)),
                 steam_monitor: Mutex::new(Some(steam_monitor)),
+                jobs: jobs::JobManager::new(),
             });
 
+            // Bandeja e início em segundo plano
+            let background_settings = background::BackgroundSettings::from_settings(
+                loaded_settings.as_ref().unwrap_or(&serde_json::Value::Null),
+            );
+            if let Err(e) = background::setup_tray(app.handle(), background_settings) {
+                log::error!("Failed to create tray icon: {}", e);
+            }
+            if background_settings.background_mode
+                && background::started_in_background(std::env::args())
+            {
+                log::info!("Started at login, keeping the window hidden in the tray");
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.hide();
+                }
+            }
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -222,19 +251,48 @@ pub fn run() {
             commands::game_lookup::search_steam_games,
             commands::achievements::get_game_achievements,
             commands::achievements::reload_achievements,
+            commands::achievements::detect_achievement_format,
+            commands::achievements::inspect_achievement_file,
             commands::monitoring::request_achievements,
             commands::achievements::unlock_achievements,
+            commands::achievements::preview_unlock_achievements,
+            commands::achievements::validate_unlock_timestamp,
+            commands::achievements::apply_achievement_changes,
+            commands::achievements::mirror_achievement_changes,
+            commands::achievements::convert_achievement_file,
             commands::achievements::export_achievements,
             commands::backup::create_achievements_backup,
             commands::backup::preview_achievements_restore,
             commands::backup::apply_achievements_restore,
+            commands::backup::list_file_snapshots,
+            commands::backup::restore_file_snapshot,
+            commands::backup::list_operations,
+            commands::backup::undo_operation,
+            commands::jobs::start_bulk_unlock,
+            commands::jobs::start_bulk_export,
+            commands::jobs::start_backup_job,
+            commands::jobs::cancel_job,
+            commands::jobs::get_job_report,
+            commands::jobs::list_jobs,
+            commands::stats::get_game_stats,
+            commands::stats::set_game_stats,
             commands::settings::save_settings,
             commands::settings::load_settings,
+            commands::settings::get_background_settings,
+            commands::settings::set_background_settings,
+            commands::settings::set_game_notifications_muted,
             commands::monitoring::get_monitored_directories,
             commands::monitoring::get_achievement_ini_last_modified,
             commands::monitoring::add_monitored_directory,
             commands::monitoring::remove_monitored_directory,
             commands::monitoring::toggle_monitored_directory,
+            commands::monitoring::set_directory_watch_mode,
+            commands::monitoring::get_monitor_health,
+            commands::monitoring::get_monitoring_paused,
+            commands::monitoring::set_monitoring_paused,
+            commands::history::get_game_unlock_history,
+            commands::history::get_unlock_history_by_day,
+            commands::history::get_latest_unlocks,
             commands::monitoring::set_wine_prefix_path,
             commands::monitoring::get_game_wine_paths,
             commands::ui::pick_folder,
@@ -249,6 +307,7 @@ pub fn run() {
             commands::steam::get_steam_user_info,
             commands::steam::get_steam_games,
             commands::steam::get_steam_game_achievements,
+            commands::steam::get_steam_local_state,
             commands::steam::set_steam_achievement,
             commands::steam::detect_steam_games,
             commands::steam::get_steam_dll_path,
@@ -279,6 +338,15 @@ pub fn run() {
             commands::connections::get_steam_connection_profile,
         ])
         .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                // No modo em segundo plano a janela só é escondida e o monitor segue ativo
+                if background::keeps_running_on_close(window.app_handle()) {
+                    log::info!("Window closed, monitoring continues in the tray");
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+
             if let tauri::WindowEvent::Destroyed = event {
                 log::info!("Window destroyed, cleaning up resources...");
 
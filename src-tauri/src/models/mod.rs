@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Todos os crackers/emuladores suportados conforme documentação oficial do Hydra
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -22,46 +24,20 @@ pub enum Cracker {
 }
 
 impl Cracker {
-    /// Retorna todos os crackers suportados
+    /// Retorna todos os crackers suportados, na ordem do registro de formatos
     pub fn all() -> &'static [Cracker] {
-        &[
-            Cracker::Codex,
-            Cracker::Rune,
-            Cracker::OnlineFix,
-            Cracker::Goldberg,
-            Cracker::Rld,
-            Cracker::Empress,
-            Cracker::Skidrow,
-            Cracker::CreamApi,
-            Cracker::SmartSteamEmu,
-            Cracker::Rle,
-            Cracker::Razor1911,
-            Cracker::UserStats,
-            Cracker::ThreeDm,
-            Cracker::Flt,
-            Cracker::SteamCache,
-        ]
+        static ALL: OnceLock<Vec<Cracker>> = OnceLock::new();
+        ALL.get_or_init(|| {
+            crate::formats::registry()
+                .iter()
+                .map(|format| format.cracker())
+                .collect()
+        })
     }
 
     /// Nome legível do cracker
     pub fn display_name(&self) -> &'static str {
-        match self {
-            Cracker::Codex => "CODEX",
-            Cracker::Rune => "RUNE",
-            Cracker::OnlineFix => "OnlineFix",
-            Cracker::Goldberg => "Goldberg",
-            Cracker::Rld => "RLD!",
-            Cracker::Empress => "EMPRESS",
-            Cracker::Skidrow => "SKIDROW",
-            Cracker::CreamApi => "CreamAPI",
-            Cracker::SmartSteamEmu => "SmartSteamEmu",
-            Cracker::Rle => "RLE",
-            Cracker::Razor1911 => "Razor1911",
-            Cracker::UserStats => "user_stats",
-            Cracker::ThreeDm => "3DM",
-            Cracker::Flt => "FLT",
-            Cracker::SteamCache => "Steam",
-        }
+        crate::formats::format_for(*self).display_name()
     }
 }
 
@@ -90,6 +66,12 @@ pub struct AchievementEntry {
     pub achieved: bool,
     #[serde(rename = "unlockTime")]
     pub unlock_time: i64,
+    /// Progresso atual (ex.: 40 de 100 inimigos), quando o formato suporta
+    #[serde(rename = "curProgress", default)]
+    pub cur_progress: Option<u32>,
+    /// Progresso necessário para desbloquear
+    #[serde(rename = "maxProgress", default)]
+    pub max_progress: Option<u32>,
 }
 
 /// Representa achievements de um jogo
@@ -103,6 +85,71 @@ pub struct GameAchievements {
     pub directory: String,
 }
 
+/// Mudanças em um jogo detectadas pelo monitor (evento `achievements-changed`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameAchievementDiff {
+    pub game_id: String,
+    pub directory: String,
+    /// Arquivo de conquista lido; `None` quando ele sumiu
+    pub file_path: Option<String>,
+    pub cracker: Option<Cracker>,
+    pub unlocked: Vec<AchievementEntry>,
+    pub relocked: Vec<AchievementEntry>,
+    /// Já estavam desbloqueadas; só o horário mudou
+    pub time_changed: Vec<AchievementEntry>,
+}
+
+impl GameAchievementDiff {
+    pub fn is_empty(&self) -> bool {
+        self.unlocked.is_empty() && self.relocked.is_empty() && self.time_changed.is_empty()
+    }
+}
+
+/// Conquista desbloqueada durante o jogo (evento `achievement-unlocked`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AchievementUnlockedEvent {
+    pub game_id: String,
+    pub game_name: Option<String>,
+    /// Nome de API da conquista
+    pub name: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    /// Porcentagem global de jogadores que a desbloquearam
+    pub rarity: Option<f64>,
+    pub unlock_time: i64,
+}
+
+/// Valor tipado de uma estatística de usuário
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum StatValue {
+    Int(i32),
+    Float(f32),
+}
+
+/// Estatística de usuário (não-conquista), ex.: inimigos derrotados
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserStat {
+    pub name: String,
+    #[serde(flatten)]
+    pub value: StatValue,
+}
+
+/// Representa as estatísticas de um jogo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameStats {
+    #[serde(rename = "gameId")]
+    pub game_id: String,
+    pub stats: Vec<UserStat>,
+    pub directory: String,
+    /// Arquivo ou diretório de origem; `None` quando ainda não existe
+    #[serde(rename = "sourcePath")]
+    pub source_path: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum DirectoryDetectionPreset {
@@ -127,6 +174,45 @@ pub struct DirectoryConfig {
     pub is_default: bool,
     #[serde(default, rename = "detectionPreset")]
     pub detection_preset: DirectoryDetectionPreset,
+    #[serde(default, rename = "watchMode")]
+    pub watch_mode: WatchMode,
+}
+
+/// Como o monitor acompanha um diretório
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchMode {
+    /// Watcher nativo, ou polling em montagens de rede/FUSE e quando o
+    /// watcher nativo falha
+    #[default]
+    Auto,
+    Native,
+    Polling,
+}
+
+/// Situação do monitoramento de um diretório
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchStatus {
+    Native,
+    Polling,
+    Disabled,
+    /// O diretório ainda não existe; passa a ser monitorado quando for criado
+    Missing,
+    /// Não foi possível monitorar (ver `message`)
+    Failed,
+}
+
+/// Evento `monitor-health` e resultado de `get_monitor_health`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryHealth {
+    pub path: String,
+    pub name: String,
+    pub mode: WatchMode,
+    pub status: WatchStatus,
+    /// Motivo do polling ou da falha
+    pub message: Option<String>,
 }
 
 /// Achievement da API Hydra
@@ -166,14 +252,31 @@ pub struct SteamAchievement {
 }
 
 /// Timestamp para unlock
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `unix` e `iso`, quando presentes, têm prioridade sobre os campos separados.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Timestamp {
+    #[serde(default)]
     pub day: String,
+    #[serde(default)]
     pub month: String,
+    #[serde(default)]
     pub year: String,
+    #[serde(default)]
     pub hour: String,
+    #[serde(default)]
     pub minute: String,
+    #[serde(default)]
     pub ampm: Option<String>,
+    /// Data ISO-8601/RFC3339 (`2024-05-01T20:30`, `2024-05-01T23:30:00Z`)
+    #[serde(default)]
+    pub iso: Option<String>,
+    /// Unix em segundos
+    #[serde(default)]
+    pub unix: Option<i64>,
+    /// Fuso IANA (`America/Sao_Paulo`); sem ele, usa o fuso local
+    #[serde(rename = "timeZone", default)]
+    pub time_zone: Option<String>,
 }
 
 /// Modo de unlock
@@ -200,6 +303,11 @@ pub struct AchievementToUnlock {
     pub name: String,
     pub completed: bool,
     pub timestamp: Timestamp,
+    /// Progresso parcial a gravar; permite editar sem desbloquear
+    #[serde(default)]
+    pub progress: Option<u32>,
+    #[serde(rename = "maxProgress", default)]
+    pub max_progress: Option<u32>,
 }
 
 /// Opções para unlock de achievements
@@ -215,6 +323,156 @@ pub struct UnlockOptions {
     pub custom_timestamp: Option<Timestamp>,
     #[serde(rename = "timeFormat")]
     pub time_format: TimeFormat,
+    /// Gera os horários em conjunto, em vez de um por conquista pelo `mode`
+    #[serde(default)]
+    pub strategy: Option<TimestampStrategy>,
+    /// Desbloqueia as conquistas mais raras por último
+    #[serde(rename = "orderByRarity", default)]
+    pub order_by_rarity: bool,
+    /// Porcentagem global por conquista; preenchida do cache ou da Steam se vazia
+    #[serde(default)]
+    pub rarity: HashMap<String, f64>,
+}
+
+/// Estratégia de geração de horários para desbloqueios em massa
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum TimestampStrategy {
+    /// Espalha entre duas datas (unix segundos)
+    Spread { start: i64, end: i64 },
+    /// Agrupa em sessões de jogo que começam nos horários do dia informados
+    #[serde(rename_all = "camelCase")]
+    Sessions {
+        start: i64,
+        end: i64,
+        session_hours: Vec<u32>,
+        #[serde(default = "default_session_minutes")]
+        session_minutes: u32,
+    },
+    /// Sessões terminando na última vez jogada, proporcionais ao tempo de
+    /// jogo. Campos ausentes vêm da Steam local ou da biblioteca do Hydra
+    #[serde(rename_all = "camelCase")]
+    Playtime {
+        #[serde(default)]
+        last_played: Option<i64>,
+        #[serde(default)]
+        playtime_minutes: Option<u64>,
+    },
+}
+
+fn default_session_minutes() -> u32 {
+    120
+}
+
+/// O que aconteceu com uma conquista ao aplicar mudanças
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AchievementChangeKind {
+    Unlocked,
+    Relocked,
+    /// Já estava desbloqueada; só o horário mudou
+    TimeChanged,
+    ProgressChanged,
+    Unchanged,
+}
+
+/// Mudança aplicada a uma conquista, com o estado antes e depois
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AchievementChange {
+    pub name: String,
+    pub kind: AchievementChangeKind,
+    /// `None` quando a conquista não existia no arquivo
+    pub before: Option<AchievementEntry>,
+    pub after: AchievementEntry,
+}
+
+/// Resultado de aplicar mudanças a um arquivo de conquistas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeReport {
+    #[serde(rename = "gameId")]
+    pub game_id: String,
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    pub cracker: Cracker,
+    pub changes: Vec<AchievementChange>,
+    /// `false` quando nada mudou e o arquivo não foi tocado
+    pub written: bool,
+}
+
+impl ChangeReport {
+    /// Mudanças efetivas (sem as `Unchanged`)
+    pub fn changed(&self) -> impl Iterator<Item = &AchievementChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.kind != AchievementChangeKind::Unchanged)
+    }
+}
+
+/// Resultado de espelhar mudanças em um dos arquivos detectados do jogo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorTargetResult {
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    pub cracker: Cracker,
+    /// Presente quando o destino foi aplicado com sucesso
+    pub report: Option<ChangeReport>,
+    pub error: Option<String>,
+}
+
+/// O que `unlock_achievements` faria com o arquivo de destino
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileAction {
+    Create,
+    Modify,
+}
+
+/// Prévia de `unlock_achievements`, calculada sem gravar nada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockPreview {
+    /// As opções recebidas com os horários da prévia fixados; enviadas a
+    /// `unlock_achievements`, gravam exatamente esta prévia
+    pub options: UnlockOptions,
+    pub game_id: String,
+    pub file_path: String,
+    pub cracker: Cracker,
+    pub action: FileAction,
+    /// Uma linha por conquista do arquivo atual ou da escrita
+    pub changes: Vec<AchievementChange>,
+    pub warnings: Vec<String>,
+}
+
+/// Conversão do arquivo de um emulador para o formato de outro
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionRequest {
+    /// Arquivo (ou diretório FLT) de origem
+    pub source_path: String,
+    /// Formato de origem; detectado pelo conteúdo quando ausente
+    #[serde(default)]
+    pub source_cracker: Option<Cracker>,
+    pub game_id: String,
+    /// Diretório base de destino (o jogo fica em `<targetDir>/<gameId>`)
+    pub target_dir: String,
+    pub target_cracker: Cracker,
+    /// Caminho exato de destino, em vez do padrão do formato
+    #[serde(default)]
+    pub target_path: Option<String>,
+}
+
+/// Prévia (ou resultado) de uma conversão entre formatos
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionPreview {
+    pub source_path: String,
+    pub source_cracker: Cracker,
+    pub target_path: String,
+    pub target_cracker: Cracker,
+    pub changes: Vec<AchievementChange>,
+    /// Perdas de informação, como horários que o formato de destino não guarda
+    pub warnings: Vec<String>,
+    pub written: bool,
 }
 
 /// Resultado de busca de jogo Steam
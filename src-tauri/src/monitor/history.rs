@@ -0,0 +1,270 @@
+//! Histórico persistente de desbloqueios observados pelo monitor.
+//!
+//! Cada conquista que o monitor vê passar de bloqueada para desbloqueada vira
+//! uma linha JSON em `<app data>/unlock_history.jsonl`, com jogo, arquivo de
+//! origem, emulador, momento da observação e horário gravado no arquivo. O
+//! histórico sobrevive entre execuções e responde a consultas por jogo, por
+//! dia e das mais recentes.
+
+use crate::models::{Cracker, GameAchievementDiff};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Um desbloqueio observado
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockRecord {
+    pub game_id: String,
+    /// Nome de API da conquista
+    pub achievement: String,
+    /// Diretório monitorado
+    pub directory: String,
+    pub source_file: Option<String>,
+    pub emulator: Option<Cracker>,
+    /// Unix (s) em que o monitor viu o desbloqueio
+    pub observed_at: i64,
+    /// Horário gravado no arquivo (unix s); 0 quando o formato não guarda
+    pub unlock_time: i64,
+}
+
+impl UnlockRecord {
+    /// Horário do arquivo ou, sem ele, o da observação.
+    pub fn unlocked_at(&self) -> i64 {
+        if self.unlock_time > 0 {
+            self.unlock_time
+        } else {
+            self.observed_at
+        }
+    }
+}
+
+/// Desbloqueios de um dia (`YYYY-MM-DD`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockDay {
+    pub date: String,
+    pub unlocks: Vec<UnlockRecord>,
+}
+
+pub struct UnlockHistory {
+    path: PathBuf,
+}
+
+impl UnlockHistory {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Registra os desbloqueios dos diffs e devolve quantos foram gravados.
+    pub fn record(&self, diffs: &[GameAchievementDiff], observed_at: i64) -> Result<usize> {
+        let lines: Vec<String> = diffs
+            .iter()
+            .flat_map(|diff| {
+                diff.unlocked.iter().map(move |entry| UnlockRecord {
+                    game_id: diff.game_id.clone(),
+                    achievement: entry.name.clone(),
+                    directory: diff.directory.clone(),
+                    source_file: diff.file_path.clone(),
+                    emulator: diff.cracker,
+                    observed_at,
+                    unlock_time: entry.unlock_time,
+                })
+            })
+            .map(|record| serde_json::to_string(&record))
+            .collect::<serde_json::Result<_>>()?;
+        if lines.is_empty() {
+            return Ok(0);
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open unlock history: {}", self.path.display()))?;
+        file.write_all(format!("{}\n", lines.join("\n")).as_bytes())
+            .with_context(|| format!("Failed to write unlock history: {}", self.path.display()))?;
+        file.sync_data()?;
+        Ok(lines.len())
+    }
+
+    /// Todos os desbloqueios, dos mais recentes aos mais antigos.
+    pub fn list(&self) -> Result<Vec<UnlockRecord>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to read unlock history: {}", self.path.display())
+                })
+            }
+        };
+
+        let mut records: Vec<UnlockRecord> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    log::warn!("Skipping malformed unlock history line: {}", e);
+                    None
+                }
+            })
+            .collect();
+        records.sort_by_key(|record| std::cmp::Reverse(record.unlocked_at()));
+        Ok(records)
+    }
+
+    /// Desbloqueios de um jogo, dos mais recentes aos mais antigos.
+    pub fn for_game(&self, game_id: &str) -> Result<Vec<UnlockRecord>> {
+        let mut records = self.list()?;
+        records.retain(|record| record.game_id == game_id);
+        Ok(records)
+    }
+
+    /// Os `limit` desbloqueios mais recentes.
+    pub fn latest(&self, limit: usize) -> Result<Vec<UnlockRecord>> {
+        let mut records = self.list()?;
+        records.truncate(limit);
+        Ok(records)
+    }
+
+    /// Desbloqueios entre `from` e `to` (unix s, inclusivos) agrupados por
+    /// dia no fuso IANA `time_zone` ou, sem ele, no fuso local. Dias mais
+    /// recentes primeiro.
+    pub fn by_day(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+        time_zone: Option<&str>,
+    ) -> Result<Vec<UnlockDay>> {
+        let tz = time_zone
+            .map(|name| {
+                name.parse::<Tz>()
+                    .map_err(|_| anyhow::anyhow!("Unknown time zone: {}", name))
+            })
+            .transpose()?;
+
+        let mut days: BTreeMap<String, Vec<UnlockRecord>> = BTreeMap::new();
+        for record in self.list()? {
+            let at = record.unlocked_at();
+            if from.is_some_and(|from| at < from) || to.is_some_and(|to| at > to) {
+                continue;
+            }
+            let Some(date) = local_date(at, tz) else {
+                continue;
+            };
+            days.entry(date).or_default().push(record);
+        }
+
+        Ok(days
+            .into_iter()
+            .rev()
+            .map(|(date, unlocks)| UnlockDay { date, unlocks })
+            .collect())
+    }
+}
+
+fn local_date(timestamp: i64, tz: Option<Tz>) -> Option<String> {
+    let utc = DateTime::from_timestamp(timestamp, 0)?;
+    Some(match tz {
+        Some(tz) => tz
+            .from_utc_datetime(&utc.naive_utc())
+            .format("%Y-%m-%d")
+            .to_string(),
+        None => Local
+            .from_utc_datetime(&utc.naive_utc())
+            .format("%Y-%m-%d")
+            .to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AchievementEntry;
+    use crate::test_support::unique_temp_dir;
+
+    fn diff(game_id: &str, unlocked: &[(&str, i64)]) -> GameAchievementDiff {
+        GameAchievementDiff {
+            game_id: game_id.to_string(),
+            directory: "/games".to_string(),
+            file_path: Some(format!("/games/{game_id}/achievements.ini")),
+            cracker: Some(Cracker::Codex),
+            unlocked: unlocked
+                .iter()
+                .map(|(name, unlock_time)| AchievementEntry {
+                    name: name.to_string(),
+                    achieved: true,
+                    unlock_time: *unlock_time,
+                    cur_progress: None,
+                    max_progress: None,
+                })
+                .collect(),
+            relocked: Vec::new(),
+            time_changed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn queries_unlocks_by_game_day_and_recency() {
+        let dir = unique_temp_dir("history");
+        let history = UnlockHistory::new(dir.join("unlock_history.jsonl"));
+        assert!(history.list().expect("empty").is_empty());
+
+        // 2024-03-10 23:30 UTC e 2024-03-11 01:00 UTC
+        let first = 1_710_113_400;
+        let second = 1_710_118_800;
+        assert_eq!(
+            history
+                .record(&[diff("480", &[("ACH_ONE", first)])], first + 5)
+                .expect("record"),
+            1
+        );
+        history
+            .record(
+                &[
+                    diff("480", &[("ACH_TWO", second)]),
+                    diff("570", &[("ACH_OTHER", 0)]),
+                ],
+                second + 60,
+            )
+            .expect("record");
+
+        let latest = history.latest(2).expect("latest");
+        let names: Vec<&str> = latest.iter().map(|r| r.achievement.as_str()).collect();
+        assert_eq!(names, vec!["ACH_OTHER", "ACH_TWO"]);
+        assert_eq!(latest[0].unlocked_at(), second + 60);
+
+        let game = history.for_game("480").expect("game");
+        assert_eq!(game.len(), 2);
+        assert_eq!(game[0].emulator, Some(Cracker::Codex));
+
+        let utc = history.by_day(None, None, Some("UTC")).expect("by day");
+        let dates: Vec<(&str, usize)> = utc
+            .iter()
+            .map(|day| (day.date.as_str(), day.unlocks.len()))
+            .collect();
+        assert_eq!(dates, vec![("2024-03-11", 2), ("2024-03-10", 1)]);
+
+        // Em São Paulo (UTC-3) tudo cai em 10/03
+        let sao_paulo = history
+            .by_day(Some(first), Some(second), Some("America/Sao_Paulo"))
+            .expect("by day");
+        assert_eq!(sao_paulo.len(), 1);
+        assert_eq!(sao_paulo[0].date, "2024-03-10");
+        assert_eq!(sao_paulo[0].unlocks.len(), 2);
+
+        assert!(history.by_day(None, None, Some("Mars/Base")).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
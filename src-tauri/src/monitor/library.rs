@@ -0,0 +1,254 @@
+//! Estado conhecido dos jogos monitorados.
+//!
+//! Depois do debounce, o monitor só reparseia os jogos cujos arquivos
+//! mudaram e compara com o estado anterior de cada um, em vez de varrer todos
+//! os diretórios de novo. As diferenças alimentam o evento
+//! `achievements-changed`.
+
+use crate::models::{AchievementEntry, DirectoryConfig, GameAchievementDiff, GameAchievements};
+use crate::parser::{expand_path, AchievementParser};
+use std::collections::BTreeMap;
+use std::path::{Component, PathBuf};
+
+/// Jogos indexados por (diretório monitorado, ID do jogo)
+#[derive(Debug, Default)]
+pub struct MonitoredLibrary {
+    games: BTreeMap<(String, String), GameAchievements>,
+}
+
+impl MonitoredLibrary {
+    pub fn new(games: Vec<GameAchievements>) -> Self {
+        Self {
+            games: games
+                .into_iter()
+                .map(|game| ((game.directory.clone(), game.game_id.clone()), game))
+                .collect(),
+        }
+    }
+
+    pub fn games(&self) -> Vec<GameAchievements> {
+        self.games.values().cloned().collect()
+    }
+
+    /// Reparseia só os jogos que contêm algum dos `paths` e devolve o que
+    /// mudou em cada um.
+    pub fn refresh_paths(
+        &mut self,
+        configs: &[DirectoryConfig],
+        paths: &[PathBuf],
+    ) -> Vec<GameAchievementDiff> {
+        let mut diffs = Vec::new();
+
+        for (config, game_id) in affected_games(configs, paths) {
+            let source = AchievementParser::game_achievement_file(config, &game_id);
+            let current = match &source {
+                Some((file, cracker)) => {
+                    match AchievementParser::parse_game(config, &game_id, file, *cracker) {
+                        Ok(current) => current,
+                        Err(e) => {
+                            // Arquivo no meio de uma escrita: mantém o estado anterior
+                            log::warn!("Failed to reparse game {}: {:#}", game_id, e);
+                            continue;
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            let key = (config.path.clone(), game_id.clone());
+            let previous = match current {
+                Some(current) => self.games.insert(key, current),
+                None => self.games.remove(&key),
+            };
+
+            let mut diff = diff_game(
+                &game_id,
+                &config.path,
+                previous.as_ref().map(|g| g.achievements.as_slice()),
+                self.games
+                    .get(&(config.path.clone(), game_id.clone()))
+                    .map(|g| g.achievements.as_slice()),
+            );
+            if !diff.is_empty() {
+                if let Some((file, cracker)) = source {
+                    diff.file_path = Some(file.to_string_lossy().to_string());
+                    diff.cracker = Some(cracker);
+                }
+                diffs.push(diff);
+            }
+        }
+
+        diffs
+    }
+}
+
+/// Jogos (diretório monitorado e ID) que contêm algum dos `paths`.
+fn affected_games<'a>(
+    configs: &'a [DirectoryConfig],
+    paths: &[PathBuf],
+) -> Vec<(&'a DirectoryConfig, String)> {
+    let mut affected: Vec<(&DirectoryConfig, String)> = Vec::new();
+
+    for path in paths {
+        for config in configs.iter().filter(|c| c.enabled) {
+            let Ok(relative) = path.strip_prefix(expand_path(&config.path)) else {
+                continue;
+            };
+            let Some(Component::Normal(game_id)) = relative.components().next() else {
+                continue;
+            };
+            let game_id = game_id.to_string_lossy().to_string();
+            if !affected
+                .iter()
+                .any(|(c, id)| c.path == config.path && *id == game_id)
+            {
+                affected.push((config, game_id));
+            }
+        }
+    }
+
+    affected
+}
+
+fn diff_game(
+    game_id: &str,
+    directory: &str,
+    previous: Option<&[AchievementEntry]>,
+    current: Option<&[AchievementEntry]>,
+) -> GameAchievementDiff {
+    let previous = previous.unwrap_or_default();
+    let current = current.unwrap_or_default();
+    fn find<'a>(list: &'a [AchievementEntry], name: &str) -> Option<&'a AchievementEntry> {
+        list.iter().find(|e| e.name == name)
+    }
+
+    let mut diff = GameAchievementDiff {
+        game_id: game_id.to_string(),
+        directory: directory.to_string(),
+        file_path: None,
+        cracker: None,
+        unlocked: Vec::new(),
+        relocked: Vec::new(),
+        time_changed: Vec::new(),
+    };
+
+    for entry in current {
+        match find(previous, &entry.name) {
+            Some(before)
+                if before.achieved && entry.achieved && before.unlock_time != entry.unlock_time =>
+            {
+                diff.time_changed.push(entry.clone())
+            }
+            Some(before) if before.achieved && entry.achieved => {}
+            Some(before) if before.achieved => diff.relocked.push(entry.clone()),
+            _ if entry.achieved => diff.unlocked.push(entry.clone()),
+            _ => {}
+        }
+    }
+
+    // Conquistas (ou o arquivo inteiro) que sumiram deixam de estar desbloqueadas
+    for before in previous {
+        if before.achieved && find(current, &before.name).is_none() {
+            diff.relocked.push(AchievementEntry {
+                achieved: false,
+                unlock_time: 0,
+                ..before.clone()
+            });
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Cracker, DirectoryDetectionPreset, WatchMode};
+    use crate::test_support::unique_temp_dir;
+    use crate::unlocker::AchievementWriter;
+    use std::fs;
+
+    fn entry(name: &str, achieved: bool, unlock_time: i64) -> AchievementEntry {
+        AchievementEntry {
+            name: name.to_string(),
+            achieved,
+            unlock_time,
+            cur_progress: None,
+            max_progress: None,
+        }
+    }
+
+    #[test]
+    fn refresh_reparses_only_changed_games_and_reports_diffs() {
+        let root = unique_temp_dir("library");
+        let config = DirectoryConfig {
+            path: root.to_string_lossy().to_string(),
+            name: "Test".to_string(),
+            enabled: true,
+            is_default: false,
+            detection_preset: DirectoryDetectionPreset::CodexIni,
+            watch_mode: WatchMode::Auto,
+        };
+        let file_for = |game_id: &str| root.join(game_id).join("achievements.ini");
+        for game_id in ["480", "570"] {
+            fs::create_dir_all(root.join(game_id)).expect("create game dir");
+            AchievementWriter::write_achievements(
+                file_for(game_id),
+                &[
+                    entry("ACH_ONE", true, 1_700_000_000),
+                    entry("ACH_TWO", false, 0),
+                ],
+                Cracker::Codex,
+            )
+            .expect("write");
+        }
+
+        let configs = vec![config];
+        let mut library =
+            MonitoredLibrary::new(AchievementParser::parse_directory_configs(&configs));
+        assert_eq!(library.games().len(), 2);
+
+        AchievementWriter::write_achievements(
+            file_for("480"),
+            &[
+                entry("ACH_ONE", false, 0),
+                entry("ACH_TWO", true, 1_700_000_100),
+            ],
+            Cracker::Codex,
+        )
+        .expect("write");
+        // 570 também mudou em disco, mas não foi notificado: não é relido
+        AchievementWriter::write_achievements(
+            file_for("570"),
+            &[entry("ACH_TWO", true, 1_700_000_200)],
+            Cracker::Codex,
+        )
+        .expect("write");
+
+        let diffs = library.refresh_paths(&configs, &[file_for("480")]);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].game_id, "480");
+        assert_eq!(diffs[0].cracker, Some(Cracker::Codex));
+        assert_eq!(
+            diffs[0].unlocked,
+            vec![entry("ACH_TWO", true, 1_700_000_100)]
+        );
+        assert_eq!(diffs[0].relocked.len(), 1);
+        assert_eq!(diffs[0].relocked[0].name, "ACH_ONE");
+        assert!(diffs[0].time_changed.is_empty());
+
+        let untouched = library
+            .games()
+            .into_iter()
+            .find(|game| game.game_id == "570")
+            .expect("570 cached");
+        assert!(!untouched.achievements[1].achieved);
+
+        // Sem mudança de conteúdo, nenhum diff
+        assert!(library
+            .refresh_paths(&configs, &[file_for("480")])
+            .is_empty());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
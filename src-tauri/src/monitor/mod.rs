@@ -1,38 +1,38 @@
-use crate::models::{DirectoryConfig, GameAchievements};
-use crate::parser::AchievementParser;
+pub mod history;
+pub mod library;
+pub mod notifications;
+pub mod pending;
+pub mod polling;
+pub mod processes;
+
+use crate::models::{
+    DirectoryConfig, DirectoryHealth, GameAchievementDiff, GameAchievements, WatchMode, WatchStatus,
+};
+use crate::parser::{expand_path, AchievementParser};
 use anyhow::Result;
+use library::MonitoredLibrary;
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use pending::PendingDirectories;
+use polling::DirectoryPoller;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, Weak};
 use std::thread;
 use tauri::Emitter;
 
-/// Todos os nomes de arquivo de conquista que devem ser monitorados.
-///
-/// Conforme documentação do Hydra, inclui:
-/// - `achievements.ini` / `Achievements.ini` (CODEX, RUNE, RLE, SmartSteamEmu, RLD!, OnlineFix)
-/// - `achievements.json` (Goldberg, EMPRESS)
-/// - `achiev.ini` (SKIDROW)
-/// - `achievement` (Razor1911)
-/// - `user_stats.ini` (user_stats)
-/// - `CreamAPI.Achievements.cfg` (CreamAPI)
-const ACHIEVEMENT_FILE_PATTERNS: &[&str] = &[
-    "achievements.ini",
-    "Achievements.ini",
-    "achievements.json",
-    "achiev.ini",
-    "achievement",
-    "user_stats.ini",
-    "CreamAPI.Achievements.cfg",
-];
-
 pub struct AchievementMonitor {
     directories: Vec<DirectoryConfig>,
-    watcher: Option<RecommendedWatcher>,
+    /// A thread de eventos guarda só uma referência fraca, para que soltar o
+    /// watcher encerre a thread.
+    watcher: Option<Arc<Mutex<RecommendedWatcher>>>,
     app_handle: Option<tauri::AppHandle>,
-    /// Cache de mtime por arquivo para detectar mudanças.
-    file_mtimes: HashMap<String, u64>,
+    /// Mantém a thread de eventos viva mesmo sem watcher nativo (só polling).
+    events_tx: Option<Sender<notify::Result<Event>>>,
+    /// Atualizado pela thread quando um diretório pendente é criado.
+    health: Arc<Mutex<Vec<DirectoryHealth>>>,
+    /// Pausado pelo usuário: reinícios não religam o monitoramento.
+    paused: bool,
 }
 
 impl AchievementMonitor {
@@ -42,7 +42,9 @@ impl AchievementMonitor {
             directories: configs,
             watcher: None,
             app_handle: None,
-            file_mtimes: HashMap::new(),
+            events_tx: None,
+            health: Arc::default(),
+            paused: false,
         }
     }
 
@@ -63,65 +65,151 @@ impl AchievementMonitor {
             Receiver<notify::Result<Event>>,
         ) = channel();
 
-        let mut watcher = RecommendedWatcher::new(tx, Config::default())?;
+        // Sem watcher nativo (ex.: limite de instâncias do inotify), os
+        // diretórios em modo auto caem todos no polling
+        let (mut watcher, watcher_error) =
+            match RecommendedWatcher::new(tx.clone(), Config::default()) {
+                Ok(watcher) => (Some(watcher), None),
+                Err(e) => {
+                    log::warn!("Native file watcher unavailable: {}", e);
+                    (None, Some(describe_watch_error(&e)))
+                }
+            };
+
+        // Cada diretório é monitorado (ou falha) independentemente dos demais
+        let mut polled_roots = Vec::new();
+        let mut pending = PendingDirectories::default();
+        let mut health: Vec<DirectoryHealth> = self
+            .directories
+            .iter()
+            .map(|dir| {
+                let health = watch_directory(dir, watcher.as_mut(), watcher_error.as_deref());
+                if health.status == WatchStatus::Polling {
+                    polled_roots.push(expand_path(&dir.path));
+                }
+                health
+            })
+            .collect();
 
-        // Adiciona watchers para cada diretório ativado
-        for dir in &self.directories {
-            if !dir.enabled {
-                log::info!("Skipping disabled directory: {}", dir.path);
+        // Diretórios que ainda não existem: observa o ancestral mais próximo
+        for index in 0..health.len() {
+            if health[index].status != WatchStatus::Missing {
                 continue;
             }
-            let path = PathBuf::from(&dir.path);
-            if path.exists() {
-                watcher.watch(&path, RecursiveMode::Recursive)?;
-                log::info!("Monitoring active for {}: {}", dir.name, dir.path);
-            } else {
-                log::warn!("Monitoring FAILED - Directory does not exist: {}", dir.path);
+            let Some(ancestor) = pending.add(self.directories[index].clone()) else {
+                continue;
+            };
+            if let Some(watcher) = watcher.as_mut() {
+                watch_ancestor(watcher, &ancestor, &health);
             }
+            health[index].message = Some(format!(
+                "Waiting for it to be created in {}",
+                ancestor.display()
+            ));
         }
 
-        self.watcher = Some(watcher);
+        let watcher = watcher.map(|watcher| Arc::new(Mutex::new(watcher)));
+        let shared_watcher = watcher.as_ref().map(Arc::downgrade).unwrap_or_default();
+        self.watcher = watcher;
+        self.events_tx = Some(tx);
+        self.health = Arc::new(Mutex::new(health));
+
+        let initial_games = self.get_current_achievements();
+        log::info!(
+            "Initial scan complete. Total games found: {}",
+            initial_games.len()
+        );
 
         // Spawn thread para processar eventos com debounce
         if let Some(app_handle) = self.app_handle.clone() {
             let directories = self.directories.clone();
+            let mut library = MonitoredLibrary::new(initial_games.clone());
+            let mut poller = DirectoryPoller::new(polled_roots);
+            let health = self.health.clone();
 
             thread::spawn(move || {
                 let mut last_event_time = std::time::Instant::now();
+                let mut last_poll_time = std::time::Instant::now();
                 let debounce_duration = std::time::Duration::from_millis(500);
-                let mut pending_update = false;
+                // Arquivos alterados desde o último refresh
+                let mut changed_paths: HashSet<PathBuf> = HashSet::new();
+                // Algum evento pode ter criado um diretório pendente
+                let mut pending_touched = false;
 
                 loop {
                     match rx.recv_timeout(std::time::Duration::from_millis(100)) {
                         Ok(Ok(event)) => {
-                            if event.paths.iter().any(|p| Self::is_achievement_file(p)) {
+                            pending_touched |= pending.is_affected_by(&event.paths);
+                            let before = changed_paths.len();
+                            changed_paths.extend(
+                                event
+                                    .paths
+                                    .into_iter()
+                                    .filter(|p| Self::is_achievement_file(p)),
+                            );
+                            if changed_paths.len() > before {
                                 last_event_time = std::time::Instant::now();
-                                pending_update = true;
                             }
                         }
                         Ok(Err(e)) => log::error!("System Watcher error: {:?}", e),
                         Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                            if pending_update && last_event_time.elapsed() >= debounce_duration {
+                            let poll_due = last_poll_time.elapsed() >= polling::POLL_INTERVAL;
+
+                            // Sem eventos (ex.: sem watcher nativo), confere a cada varredura
+                            if !pending.is_empty() && (pending_touched || poll_due) {
+                                let created = attach_created_directories(
+                                    &mut pending,
+                                    &shared_watcher,
+                                    &mut poller,
+                                    &health,
+                                    &app_handle,
+                                );
+                                if !created.is_empty() {
+                                    last_event_time = std::time::Instant::now();
+                                    changed_paths.extend(created);
+                                }
+                                pending_touched = false;
+                            }
+
+                            if poll_due {
+                                let polled = poller.poll();
+                                if !polled.is_empty() {
+                                    last_event_time = std::time::Instant::now();
+                                    changed_paths.extend(polled);
+                                }
+                                last_poll_time = std::time::Instant::now();
+                            }
+
+                            if !changed_paths.is_empty()
+                                && last_event_time.elapsed() >= debounce_duration
+                            {
                                 log::info!(
-                                    "Debounce period finished. Refreshing achievement data..."
+                                    "Debounce period finished. Refreshing {} changed file(s)...",
+                                    changed_paths.len()
                                 );
 
                                 let enabled_directories: Vec<DirectoryConfig> =
                                     directories.iter().filter(|d| d.enabled).cloned().collect();
+                                let paths: Vec<PathBuf> = changed_paths.drain().collect();
 
-                                let games = AchievementParser::parse_directory_configs(
-                                    &enabled_directories,
-                                );
+                                let diffs = library.refresh_paths(&enabled_directories, &paths);
+                                let games = library.games();
                                 log::info!(
-                                    "Refresh complete (debounced). Found {} games.",
+                                    "Refresh complete (debounced). {} game(s) changed, {} games total.",
+                                    diffs.len(),
                                     games.len()
                                 );
 
                                 if let Err(e) = app_handle.emit("achievements-update", games) {
                                     log::error!("Failed to emit update: {}", e);
                                 }
-
-                                pending_update = false;
+                                if !diffs.is_empty() {
+                                    record_unlocks(&app_handle, &diffs);
+                                    notifications::notify_unlocks(&app_handle, &diffs);
+                                    if let Err(e) = app_handle.emit("achievements-changed", diffs) {
+                                        log::error!("Failed to emit achievement changes: {}", e);
+                                    }
+                                }
                             }
                         }
                         Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
@@ -131,16 +219,13 @@ impl AchievementMonitor {
         }
 
         // Emite achievements iniciais
-        let initial_games = self.get_current_achievements();
-        log::info!(
-            "Initial scan complete. Total games found: {}",
-            initial_games.len()
-        );
-
         if let Some(app_handle) = &self.app_handle {
             if let Err(e) = app_handle.emit("achievements-update", initial_games) {
                 log::error!("Failed to emit initial achievements: {}", e);
             }
+            if let Err(e) = app_handle.emit("monitor-health", self.get_health()) {
+                log::error!("Failed to emit monitor health: {}", e);
+            }
         }
 
         Ok(())
@@ -150,7 +235,16 @@ impl AchievementMonitor {
     pub fn stop_monitoring(&mut self) {
         log::info!("Stopping achievement monitoring...");
         self.watcher = None;
-        self.file_mtimes.clear();
+        self.events_tx = None;
+        self.health = Arc::default();
+    }
+
+    /// Situação do monitoramento de cada diretório
+    pub fn get_health(&self) -> Vec<DirectoryHealth> {
+        self.health
+            .lock()
+            .map(|health| health.clone())
+            .unwrap_or_default()
     }
 
     /// Obtém achievements atuais
@@ -177,48 +271,37 @@ impl AchievementMonitor {
     /// Reinicia o monitoramento
     pub fn restart_monitoring(&mut self) -> Result<()> {
         self.stop_monitoring();
+        if self.paused {
+            return Ok(());
+        }
         self.start_monitoring()
     }
 
-    /// Verifica se um arquivo é um arquivo de conquista baseado no nome.
-    ///
-    /// Suporta todos os padrões de arquivo documentados:
-    /// - `achievements.ini` / `Achievements.ini`
-    /// - `achievements.json`
-    /// - `achiev.ini`
-    /// - `achievement`
-    /// - `user_stats.ini`
-    /// - `CreamAPI.Achievements.cfg`
-    fn is_achievement_file(path: &std::path::Path) -> bool {
-        if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-            ACHIEVEMENT_FILE_PATTERNS
-                .iter()
-                .any(|pattern| filename.eq_ignore_ascii_case(pattern))
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pausa ou retoma o monitoramento
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        if paused == self.paused {
+            return Ok(());
+        }
+        self.paused = paused;
+        if paused {
+            self.stop_monitoring();
+            Ok(())
         } else {
-            false
+            self.start_monitoring()
         }
     }
 
-    /// Compara o mtime de um arquivo com o cache para detectar mudanças.
+    /// Verifica se um arquivo é um arquivo de conquista baseado no nome.
     ///
-    /// Retorna `true` se o arquivo mudou desde a última verificação.
-    pub fn has_file_changed(&mut self, file_path: &str) -> bool {
-        let current_mtime = std::fs::metadata(file_path)
-            .ok()
-            .and_then(|m| m.modified().ok())
-            .and_then(|t| {
-                t.duration_since(std::time::UNIX_EPOCH)
-                    .ok()
-                    .map(|d| d.as_secs())
-            })
-            .unwrap_or(0);
-
-        let previous_mtime = self.file_mtimes.get(file_path).copied().unwrap_or(0);
-
-        self.file_mtimes
-            .insert(file_path.to_string(), current_mtime);
-
-        current_mtime != previous_mtime
+    /// Os padrões vêm de `monitor_patterns` de cada formato registrado.
+    fn is_achievement_file(path: &std::path::Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(crate::formats::is_monitored_file_name)
     }
 }
 
@@ -226,6 +309,172 @@ impl Drop for AchievementMonitor {
     fn drop(&mut self) {
         log::info!("[AchievementMonitor] Drop: stopping file watcher");
         self.watcher = None;
-        self.file_mtimes.clear();
+        self.events_tx = None;
+    }
+}
+
+/// Começa a monitorar um diretório conforme o `watch_mode` e descreve o
+/// resultado. Falhas do watcher nativo em modo auto caem no polling.
+fn watch_directory(
+    dir: &DirectoryConfig,
+    watcher: Option<&mut RecommendedWatcher>,
+    watcher_error: Option<&str>,
+) -> DirectoryHealth {
+    let health = |status: WatchStatus, message: Option<String>| DirectoryHealth {
+        path: dir.path.clone(),
+        name: dir.name.clone(),
+        mode: dir.watch_mode,
+        status,
+        message,
+    };
+
+    if !dir.enabled {
+        log::info!("Skipping disabled directory: {}", dir.path);
+        return health(WatchStatus::Disabled, None);
+    }
+    let path = expand_path(&dir.path);
+    if !path.exists() {
+        log::info!("Directory does not exist yet, waiting for it: {}", dir.path);
+        return health(WatchStatus::Missing, None);
+    }
+
+    match dir.watch_mode {
+        WatchMode::Polling => {
+            log::info!("Polling active for {}: {}", dir.name, dir.path);
+            return health(WatchStatus::Polling, None);
+        }
+        WatchMode::Auto if polling::is_remote_mount(&path) => {
+            log::info!("Polling network/FUSE mount for {}: {}", dir.name, dir.path);
+            return health(
+                WatchStatus::Polling,
+                Some("Network or FUSE mount".to_string()),
+            );
+        }
+        _ => {}
+    }
+
+    let error = match watcher {
+        Some(watcher) => match watcher.watch(&path, RecursiveMode::Recursive) {
+            Ok(()) => {
+                log::info!("Monitoring active for {}: {}", dir.name, dir.path);
+                return health(WatchStatus::Native, None);
+            }
+            Err(e) => {
+                // Um watch recursivo pode ter ficado pela metade
+                let _ = watcher.unwatch(&path);
+                describe_watch_error(&e)
+            }
+        },
+        None => watcher_error
+            .unwrap_or("Native file watcher unavailable")
+            .to_string(),
+    };
+
+    if dir.watch_mode == WatchMode::Native {
+        log::error!("Monitoring FAILED for {}: {}", dir.path, error);
+        health(WatchStatus::Failed, Some(error))
+    } else {
+        log::warn!(
+            "Native watcher failed for {} ({}); falling back to polling",
+            dir.path,
+            error
+        );
+        health(WatchStatus::Polling, Some(error))
+    }
+}
+
+/// Guarda no histórico os desbloqueios observados.
+fn record_unlocks(app_handle: &tauri::AppHandle, diffs: &[GameAchievementDiff]) {
+    let result = crate::commands::history::unlock_history(app_handle).and_then(|history| {
+        history
+            .record(diffs, chrono::Utc::now().timestamp())
+            .map_err(|e| format!("{:#}", e))
+    });
+    match result {
+        Ok(0) => {}
+        Ok(count) => log::info!("Recorded {} unlock(s) in history", count),
+        Err(e) => log::warn!("Failed to record unlock history: {}", e),
+    }
+}
+
+/// Observa (sem recursão) o ancestral de um diretório pendente. Ancestrais
+/// dentro de um diretório já monitorado de forma recursiva já geram eventos,
+/// e um watch não recursivo no mesmo caminho desligaria a recursão.
+fn watch_ancestor(watcher: &mut RecommendedWatcher, ancestor: &Path, health: &[DirectoryHealth]) {
+    let covered = health.iter().any(|dir| {
+        dir.status == WatchStatus::Native && ancestor.starts_with(expand_path(&dir.path))
+    });
+    if covered {
+        return;
+    }
+
+    match watcher.watch(ancestor, RecursiveMode::NonRecursive) {
+        Ok(()) => log::info!("Watching {} for missing directories", ancestor.display()),
+        Err(e) => log::warn!("Failed to watch {}: {}", ancestor.display(), e),
+    }
+}
+
+/// Passa a monitorar os diretórios pendentes que já foram criados e devolve
+/// os arquivos de conquista que eles já contêm.
+fn attach_created_directories(
+    pending: &mut PendingDirectories,
+    watcher: &Weak<Mutex<RecommendedWatcher>>,
+    poller: &mut DirectoryPoller,
+    health: &Mutex<Vec<DirectoryHealth>>,
+    app_handle: &tauri::AppHandle,
+) -> Vec<PathBuf> {
+    let resolved = pending.resolve();
+    if resolved.created.is_empty() && resolved.ancestors.is_empty() {
+        return Vec::new();
+    }
+
+    let watcher = watcher.upgrade();
+    let mut watcher = watcher.as_ref().and_then(|watcher| watcher.lock().ok());
+    let Ok(mut health) = health.lock() else {
+        return Vec::new();
+    };
+
+    if let Some(watcher) = watcher.as_deref_mut() {
+        for ancestor in &resolved.ancestors {
+            watch_ancestor(watcher, ancestor, &health);
+        }
+    }
+
+    let mut files = Vec::new();
+    for dir in &resolved.created {
+        log::info!("Monitored directory appeared: {}", dir.path);
+        let dir_health = watch_directory(dir, watcher.as_deref_mut(), None);
+        let root = expand_path(&dir.path);
+        if dir_health.status == WatchStatus::Polling {
+            poller.add_root(root.clone());
+        }
+        files.extend(polling::achievement_files(&root));
+
+        match health.iter_mut().find(|entry| entry.path == dir.path) {
+            Some(entry) => *entry = dir_health,
+            None => health.push(dir_health),
+        }
+    }
+
+    if !resolved.created.is_empty() {
+        if let Err(e) = app_handle.emit("monitor-health", health.clone()) {
+            log::error!("Failed to emit monitor health: {}", e);
+        }
+    }
+    files
+}
+
+fn describe_watch_error(error: &notify::Error) -> String {
+    let watch_limit = match &error.kind {
+        notify::ErrorKind::MaxFilesWatch => true,
+        // ENOSPC: inotify sem watches disponíveis
+        notify::ErrorKind::Io(e) => e.raw_os_error() == Some(28),
+        _ => false,
+    };
+
+    if watch_limit {
+        "inotify watch limit reached (raise fs.inotify.max_user_watches)".to_string()
+    } else {
+        error.to_string()
     }
 }
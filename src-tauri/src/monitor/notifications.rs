@@ -0,0 +1,297 @@
+//! Notificações de conquistas desbloqueadas durante o jogo.
+//!
+//! A partir dos diffs do monitor, só contam as conquistas que passaram de
+//! bloqueadas para desbloqueadas enquanto o jogo roda (ver
+//! [`super::processes`]) e que não vieram de uma escrita do próprio HAM.
+//! Com horário gravado, ele também precisa ser recente, o que descarta saves
+//! copiados ou sincronizados com o jogo aberto; formatos sem horário (FLT)
+//! contam só pelo processo. Cada uma vira o evento `achievement-unlocked`,
+//! com nome, descrição, ícone e raridade do schema em cache, e uma
+//! notificação do sistema.
+
+use crate::integrations::steam::SteamSchema;
+use crate::models::{
+    AchievementEntry, AchievementUnlockedEvent, GameAchievementDiff, SteamAchievement,
+};
+use crate::unlocker::journal::JournalRecord;
+use crate::utils::CacheManager;
+use serde_json::Value;
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+/// Chave das configurações que liga ou desliga as notificações.
+pub const ENABLED_KEY: &str = "unlockNotifications";
+/// Chave das configurações com os IDs dos jogos silenciados.
+pub const MUTED_GAMES_KEY: &str = "mutedNotificationGames";
+
+/// Diferença máxima entre o horário do desbloqueio e o momento da detecção.
+const LIVE_UNLOCK_WINDOW_SECS: i64 = 5 * 60;
+/// Registros do diário mais novos que isso são escritas recentes do HAM.
+const LOCAL_WRITE_WINDOW_MS: i64 = 60 * 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    pub muted_games: Vec<String>,
+}
+
+impl NotificationSettings {
+    pub fn from_settings(settings: &Value) -> Self {
+        Self {
+            enabled: settings
+                .get(ENABLED_KEY)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+            muted_games: muted_games(settings),
+        }
+    }
+
+    pub fn is_muted(&self, game_id: &str) -> bool {
+        self.muted_games.iter().any(|id| id == game_id)
+    }
+}
+
+/// IDs dos jogos silenciados nas configurações.
+pub fn muted_games(settings: &Value) -> Vec<String> {
+    settings
+        .get(MUTED_GAMES_KEY)
+        .and_then(|v| v.as_array())
+        .map(|ids| {
+            ids.iter()
+                .filter_map(|id| id.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Conquistas desbloqueadas pelo jogo em execução, por jogo.
+///
+/// `running` são os AppIDs com processo ativo; `local_writes` são os
+/// registros do diário de operações; `now` é unix em segundos.
+pub fn live_unlocks<'a>(
+    diffs: &'a [GameAchievementDiff],
+    settings: &NotificationSettings,
+    running: &HashSet<String>,
+    local_writes: &[JournalRecord],
+    now: i64,
+) -> Vec<(&'a str, &'a AchievementEntry)> {
+    if !settings.enabled {
+        return Vec::new();
+    }
+
+    let written_by_ham = |game_id: &str, entry: &AchievementEntry| {
+        local_writes.iter().any(|record| {
+            record.game_id == game_id
+                && record.created_at >= now * 1000 - LOCAL_WRITE_WINDOW_MS
+                && record.entries.iter().any(|written| {
+                    written.name == entry.name
+                        && written.achieved
+                        && written.unlock_time == entry.unlock_time
+                })
+        })
+    };
+
+    diffs
+        .iter()
+        .filter(|diff| !settings.is_muted(&diff.game_id) && running.contains(&diff.game_id))
+        .flat_map(|diff| {
+            diff.unlocked
+                .iter()
+                .map(move |entry| (diff.game_id.as_str(), entry))
+        })
+        .filter(|(_, entry)| {
+            entry.unlock_time == 0 || (now - entry.unlock_time).abs() <= LIVE_UNLOCK_WINDOW_SECS
+        })
+        .filter(|(game_id, entry)| !written_by_ham(game_id, entry))
+        .collect()
+}
+
+/// Emite `achievement-unlocked` e mostra uma notificação para cada
+/// desbloqueio ao vivo nos diffs.
+pub fn notify_unlocks(app_handle: &AppHandle, diffs: &[GameAchievementDiff]) {
+    if diffs.iter().all(|diff| diff.unlocked.is_empty()) {
+        return;
+    }
+
+    let settings_value = crate::utils::settings::load_settings_or_default(app_handle);
+    let settings = NotificationSettings::from_settings(&settings_value);
+    let now = chrono::Utc::now().timestamp();
+    let local_writes = crate::commands::backup::operation_journal(app_handle)
+        .and_then(|journal| {
+            journal
+                .since(now * 1000 - LOCAL_WRITE_WINDOW_MS)
+                .map_err(|e| format!("{:#}", e))
+        })
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to read operation journal for notifications: {}", e);
+            Vec::new()
+        });
+    let language = crate::commands::language::map_ui_language_to_steam_store_lang(
+        settings_value
+            .get("language")
+            .and_then(|v| v.as_str())
+            .unwrap_or("en-US"),
+    );
+
+    let running = super::processes::running_game_ids();
+    for (game_id, entry) in live_unlocks(diffs, &settings, &running, &local_writes, now) {
+        let event = unlock_event(app_handle, game_id, entry, language);
+        log::info!("Achievement unlocked in game {}: {}", game_id, event.name);
+
+        if let Err(e) = app_handle.emit("achievement-unlocked", &event) {
+            log::error!("Failed to emit achievement-unlocked: {}", e);
+        }
+        show_notification(app_handle, &event);
+    }
+}
+
+/// Completa o desbloqueio com os metadados do cache (Hydra/Steam) ou, sem
+/// eles, do schema local do cliente Steam.
+fn unlock_event(
+    app_handle: &AppHandle,
+    game_id: &str,
+    entry: &AchievementEntry,
+    language: &str,
+) -> AchievementUnlockedEvent {
+    let cached = CacheManager::get_game(app_handle, game_id).unwrap_or_default();
+    let metadata = cached
+        .achievements
+        .as_ref()
+        .and_then(|achievements| achievements.get(&entry.name))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut event = AchievementUnlockedEvent {
+        game_id: game_id.to_string(),
+        game_name: cached.name.clone(),
+        name: entry.name.clone(),
+        display_name: metadata.display_name,
+        description: metadata.description,
+        icon: metadata.icon,
+        rarity: cached
+            .rarity
+            .as_ref()
+            .and_then(|rarity| rarity.get(&entry.name))
+            .copied(),
+        unlock_time: entry.unlock_time,
+    };
+
+    if event.display_name.is_none() || event.game_name.is_none() {
+        let schema = game_id
+            .parse::<u32>()
+            .ok()
+            .and_then(|app_id| SteamSchema::load(app_id).ok().flatten());
+        if let Some(schema) = schema {
+            let local: Option<SteamAchievement> = schema
+                .to_steam_achievements(language)
+                .into_iter()
+                .find(|achievement| achievement.apiname == entry.name);
+            if let Some(local) = local.filter(|_| event.display_name.is_none()) {
+                event.display_name = local.name;
+                event.description = event.description.or(local.description);
+                event.icon = event.icon.or(local.icon);
+            }
+            event.game_name = event.game_name.or(schema.game_name);
+        }
+    }
+
+    event
+}
+
+fn show_notification(app_handle: &AppHandle, event: &AchievementUnlockedEvent) {
+    let title = format!(
+        "Achievement unlocked: {}",
+        event.display_name.as_deref().unwrap_or(&event.name)
+    );
+    let mut lines: Vec<String> = event.description.iter().cloned().collect();
+    let game = event.game_name.as_deref().unwrap_or(&event.game_id);
+    lines.push(match event.rarity {
+        Some(rarity) => format!("{} · {:.1}% of players", game, rarity),
+        None => game.to_string(),
+    });
+
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(lines.join("\n"))
+        .show()
+    {
+        log::warn!("Failed to show unlock notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Cracker;
+    use crate::unlocker::journal::OperationKind;
+
+    fn entry(name: &str, unlock_time: i64) -> AchievementEntry {
+        AchievementEntry {
+            name: name.to_string(),
+            achieved: true,
+            unlock_time,
+            cur_progress: None,
+            max_progress: None,
+        }
+    }
+
+    fn diff(game_id: &str, unlocked: Vec<AchievementEntry>) -> GameAchievementDiff {
+        GameAchievementDiff {
+            game_id: game_id.to_string(),
+            directory: "/games".to_string(),
+            file_path: None,
+            cracker: None,
+            unlocked,
+            relocked: Vec::new(),
+            time_changed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn only_live_unlocks_from_running_unmuted_games_outside_ham_writes_notify() {
+        let now = 1_700_000_000;
+        let diffs = vec![
+            diff(
+                "480",
+                vec![
+                    entry("ACH_LIVE", now - 2),
+                    entry("ACH_NO_TIME", 0),
+                    entry("ACH_BACKDATED", now - 86_400),
+                    entry("ACH_FROM_HAM", now - 1),
+                ],
+            ),
+            diff("570", vec![entry("ACH_MUTED", now - 1)]),
+            diff("730", vec![entry("ACH_NOT_RUNNING", now - 1)]),
+        ];
+        let running: HashSet<String> = ["480", "570"].map(str::to_string).into();
+        let settings = NotificationSettings::from_settings(&serde_json::json!({
+            MUTED_GAMES_KEY: ["570"],
+        }));
+        let local_writes = vec![JournalRecord {
+            id: "1".to_string(),
+            kind: OperationKind::Unlock,
+            created_at: (now - 1) * 1000,
+            game_id: "480".to_string(),
+            file_path: "/games/480/achievements.ini".to_string(),
+            cracker: Cracker::Codex,
+            previous: None,
+            entries: vec![entry("ACH_FROM_HAM", now - 1)],
+            undoes: None,
+        }];
+
+        let live = live_unlocks(&diffs, &settings, &running, &local_writes, now);
+        let names: Vec<(&str, &str)> = live
+            .iter()
+            .map(|(game_id, entry)| (*game_id, entry.name.as_str()))
+            .collect();
+        assert_eq!(names, vec![("480", "ACH_LIVE"), ("480", "ACH_NO_TIME")]);
+
+        let disabled = NotificationSettings::from_settings(&serde_json::json!({
+            ENABLED_KEY: false,
+        }));
+        assert!(live_unlocks(&diffs, &disabled, &running, &[], now).is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+//! Diretórios monitorados que ainda não existem.
+//!
+//! Pastas como `GSE Saves` ou `Steam/CODEX` só aparecem quando o emulador
+//! roda pela primeira vez. Enquanto isso o monitor observa o ancestral
+//! existente mais próximo e, quando o diretório surge, passa a monitorá-lo
+//! como os demais, sem precisar reiniciar o app.
+
+use crate::models::DirectoryConfig;
+use crate::parser::expand_path;
+use std::path::{Path, PathBuf};
+
+struct PendingDirectory {
+    config: DirectoryConfig,
+    target: PathBuf,
+    ancestor: Option<PathBuf>,
+}
+
+/// Resultado de [`PendingDirectories::resolve`]
+#[derive(Debug, Default)]
+pub struct ResolvedDirectories {
+    /// Diretórios que passaram a existir e saíram da espera
+    pub created: Vec<DirectoryConfig>,
+    /// Novos ancestrais a observar para os que continuam pendentes
+    pub ancestors: Vec<PathBuf>,
+}
+
+#[derive(Default)]
+pub struct PendingDirectories {
+    items: Vec<PendingDirectory>,
+}
+
+impl PendingDirectories {
+    /// Passa a esperar pelo diretório e devolve o ancestral a observar.
+    pub fn add(&mut self, config: DirectoryConfig) -> Option<PathBuf> {
+        let target = expand_path(&config.path);
+        let ancestor = nearest_existing_ancestor(&target);
+        self.items.push(PendingDirectory {
+            config,
+            target,
+            ancestor: ancestor.clone(),
+        });
+        ancestor
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Se algum dos `paths` de um evento pode ser um diretório esperado ou um
+    /// nível intermediário até ele.
+    pub fn is_affected_by(&self, paths: &[PathBuf]) -> bool {
+        paths
+            .iter()
+            .any(|path| self.items.iter().any(|item| item.target.starts_with(path)))
+    }
+
+    /// Tira da espera os diretórios que já existem e aponta o próximo
+    /// ancestral dos que ganharam um nível intermediário.
+    pub fn resolve(&mut self) -> ResolvedDirectories {
+        let mut resolved = ResolvedDirectories::default();
+
+        self.items.retain_mut(|item| {
+            if item.target.is_dir() {
+                resolved.created.push(item.config.clone());
+                return false;
+            }
+
+            let ancestor = nearest_existing_ancestor(&item.target);
+            if ancestor != item.ancestor {
+                resolved.ancestors.extend(ancestor.clone());
+                item.ancestor = ancestor;
+            }
+            true
+        });
+
+        resolved
+    }
+}
+
+fn nearest_existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .find(|ancestor| ancestor.is_dir())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DirectoryDetectionPreset, WatchMode};
+    use crate::test_support::unique_temp_dir;
+    use std::fs;
+
+    #[test]
+    fn follows_the_nearest_ancestor_until_the_directory_is_created() {
+        let root = unique_temp_dir("pending");
+        fs::create_dir_all(&root).expect("create root");
+        let steam = root.join("Steam");
+        let codex = steam.join("CODEX");
+
+        let mut pending = PendingDirectories::default();
+        let ancestor = pending.add(DirectoryConfig {
+            path: codex.to_string_lossy().to_string(),
+            name: "CODEX".to_string(),
+            enabled: true,
+            is_default: true,
+            detection_preset: DirectoryDetectionPreset::Auto,
+            watch_mode: WatchMode::Auto,
+        });
+        assert_eq!(ancestor, Some(root.clone()));
+        assert!(!pending.is_affected_by(&[root.join("Other")]));
+
+        fs::create_dir(&steam).expect("create Steam");
+        assert!(pending.is_affected_by(std::slice::from_ref(&steam)));
+        let resolved = pending.resolve();
+        assert!(resolved.created.is_empty());
+        assert_eq!(resolved.ancestors, vec![steam.clone()]);
+        assert!(pending.resolve().ancestors.is_empty());
+
+        fs::create_dir(&codex).expect("create CODEX");
+        let resolved = pending.resolve();
+        assert_eq!(resolved.created.len(), 1);
+        assert_eq!(resolved.created[0].name, "CODEX");
+        assert!(pending.is_empty());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,220 @@
+//! Polling para diretórios onde o watcher nativo não funciona.
+//!
+//! Montagens NFS/SMB/FUSE (comuns em prefixes Wine compartilhados) não
+//! entregam eventos de inotify, e o limite de watches do inotify pode se
+//! esgotar. Nesses diretórios o monitor compara, a cada intervalo, o mtime
+//! dos arquivos de conquista com o da última varredura.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Intervalo entre varreduras dos diretórios em polling.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Profundidade máxima abaixo do diretório monitorado (jogo/subpastas do emulador).
+const MAX_DEPTH: usize = 5;
+
+#[derive(Debug, Default)]
+pub struct DirectoryPoller {
+    roots: Vec<PathBuf>,
+    /// Cache de mtime (ms) por arquivo para detectar mudanças.
+    file_mtimes: HashMap<PathBuf, u64>,
+}
+
+impl DirectoryPoller {
+    /// Cria o poller já com o estado atual dos arquivos, para que a primeira
+    /// varredura só reporte mudanças posteriores.
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        let mut poller = Self {
+            roots,
+            file_mtimes: HashMap::new(),
+        };
+        poller.poll();
+        poller
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Inclui um diretório; os arquivos que já existem nele aparecem como
+    /// criados na próxima varredura.
+    pub fn add_root(&mut self, root: PathBuf) {
+        if !self.roots.contains(&root) {
+            self.roots.push(root);
+        }
+    }
+
+    /// Arquivos de conquista criados, alterados ou removidos desde a última
+    /// varredura.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for root in &self.roots {
+            collect_achievement_files(root, 0, &mut files);
+        }
+
+        let present: HashSet<&PathBuf> = files.iter().collect();
+        let removed: Vec<PathBuf> = self
+            .file_mtimes
+            .keys()
+            .filter(|path| !present.contains(path))
+            .cloned()
+            .collect();
+        for path in &removed {
+            self.file_mtimes.remove(path);
+        }
+
+        let mut changed: Vec<PathBuf> = files
+            .into_iter()
+            .filter(|path| self.has_file_changed(path))
+            .collect();
+        changed.extend(removed);
+        changed
+    }
+
+    /// Compara o mtime de um arquivo com o cache para detectar mudanças.
+    ///
+    /// Retorna `true` se o arquivo mudou desde a última verificação.
+    fn has_file_changed(&mut self, file_path: &Path) -> bool {
+        let current_mtime = fs::metadata(file_path)
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| {
+                t.duration_since(std::time::UNIX_EPOCH)
+                    .ok()
+                    .map(|d| d.as_millis() as u64)
+            })
+            .unwrap_or(0);
+
+        let previous_mtime = self
+            .file_mtimes
+            .insert(file_path.to_path_buf(), current_mtime);
+
+        previous_mtime != Some(current_mtime)
+    }
+}
+
+/// Arquivos de conquista dentro de `root`.
+pub fn achievement_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_achievement_files(root, 0, &mut files);
+    files
+}
+
+fn collect_achievement_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() && depth < MAX_DEPTH => {
+                collect_achievement_files(&path, depth + 1, files)
+            }
+            Ok(file_type) if file_type.is_file() => {
+                let monitored = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(crate::formats::is_monitored_file_name);
+                if monitored {
+                    files.push(path);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Se `path` está em uma montagem de rede ou FUSE, onde o inotify não vê
+/// mudanças feitas fora da máquina.
+pub fn is_remote_mount(path: &Path) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        fs::read_to_string("/proc/self/mounts")
+            .map(|mounts| mount_fs_type(&mounts, path).is_some_and(is_remote_fs_type))
+            .unwrap_or(false)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// Tipo do sistema de arquivos da montagem mais específica que contém `path`,
+/// no formato de `/proc/self/mounts`.
+fn mount_fs_type<'a>(mounts: &'a str, path: &Path) -> Option<&'a str> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            // Espaços e tabs vêm escapados em octal
+            let mount_point = fields.next()?.replace("\\040", " ").replace("\\011", "\t");
+            let fs_type = fields.next()?;
+            Some((PathBuf::from(mount_point), fs_type))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        // Montagens posteriores no mesmo ponto escondem as anteriores
+        .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())
+        .map(|(_, fs_type)| fs_type)
+}
+
+fn is_remote_fs_type(fs_type: &str) -> bool {
+    matches!(
+        fs_type,
+        "nfs" | "nfs4" | "cifs" | "smb3" | "smbfs" | "9p" | "afs" | "ceph" | "davfs" | "fuse"
+    ) || fs_type.starts_with("fuse.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+    use std::time::SystemTime;
+
+    #[test]
+    fn poller_reports_created_changed_and_removed_files() {
+        let root = unique_temp_dir("polling");
+        let game_dir = root.join("480");
+        fs::create_dir_all(&game_dir).expect("create game dir");
+        let existing = game_dir.join("achievements.ini");
+        fs::write(&existing, "[ACH_ONE]\nAchieved=1\n").expect("write");
+        fs::write(game_dir.join("readme.txt"), "ignored").expect("write");
+
+        let mut poller = DirectoryPoller::new(vec![root.clone()]);
+        assert!(poller.poll().is_empty());
+
+        let created = root.join("570").join("achievements.json");
+        fs::create_dir_all(created.parent().expect("parent")).expect("create game dir");
+        fs::write(&created, "{}").expect("write");
+        assert_eq!(poller.poll(), vec![created.clone()]);
+
+        fs::remove_file(&created).expect("remove");
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .open(&existing)
+            .expect("open");
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .expect("set mtime");
+        let mut changed = poller.poll();
+        changed.sort();
+        assert_eq!(changed, vec![existing, created]);
+        assert!(poller.poll().is_empty());
+
+        let mounts = "server:/games /mnt/games nfs4 rw 0 0\n\
+                      /dev/sda1 / ext4 rw 0 0\n\
+                      sshfs#host: /mnt/my\\040share fuse.sshfs rw 0 0\n";
+        assert_eq!(
+            mount_fs_type(mounts, Path::new("/mnt/games/prefix")),
+            Some("nfs4")
+        );
+        assert_eq!(mount_fs_type(mounts, Path::new("/home/user")), Some("ext4"));
+        assert!(mount_fs_type(mounts, Path::new("/mnt/my share/x")).is_some_and(is_remote_fs_type));
+        assert!(!is_remote_fs_type("ext4"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
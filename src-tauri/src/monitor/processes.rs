@@ -0,0 +1,89 @@
+//! Jogos em execução, para as notificações de desbloqueio.
+//!
+//! O save do emulador não diz qual processo é o jogo. Um processo conta como
+//! o jogo `<appid>` quando:
+//!
+//! - o ambiente tem `SteamAppId`/`SteamGameId` com o appid (Steam, Proton e
+//!   os emuladores que o definem), ou
+//! - o diretório do executável ou o de trabalho (o do jogo, no Wine) tem um
+//!   `steam_appid.txt` com o appid (Goldberg e derivados).
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+const APP_ID_VARIABLES: &[&str] = &["SteamAppId", "SteamGameId"];
+const STEAM_APPID_FILE: &str = "steam_appid.txt";
+
+/// AppIDs dos jogos com um processo em execução.
+pub fn running_game_ids() -> HashSet<String> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cwd(UpdateKind::OnlyIfNotSet)
+            .with_environ(UpdateKind::OnlyIfNotSet),
+    );
+
+    system
+        .processes()
+        .values()
+        .filter_map(|process| {
+            let dirs = [process.exe().and_then(Path::parent), process.cwd()];
+            process_game_id(process.environ(), dirs.into_iter().flatten())
+        })
+        .collect()
+}
+
+/// AppID de um processo, pelo ambiente ou pelo `steam_appid.txt`.
+fn process_game_id<'a>(
+    environ: &[OsString],
+    dirs: impl IntoIterator<Item = &'a Path>,
+) -> Option<String> {
+    let from_environ = environ.iter().find_map(|variable| {
+        let (key, value) = variable.to_str()?.split_once('=')?;
+        let value = value.trim();
+        (APP_ID_VARIABLES.contains(&key) && !value.is_empty() && value != "0")
+            .then(|| value.to_string())
+    });
+
+    from_environ.or_else(|| {
+        dirs.into_iter().find_map(|dir| {
+            let content = fs::read_to_string(dir.join(STEAM_APPID_FILE)).ok()?;
+            let app_id = content.trim();
+            (!app_id.is_empty()).then(|| app_id.to_string())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn identifies_games_by_environment_or_steam_appid_file() {
+        let game_dir = unique_temp_dir("processes");
+        fs::create_dir_all(&game_dir).expect("create dir");
+        let environ = |vars: &[&str]| vars.iter().map(OsString::from).collect::<Vec<_>>();
+
+        assert_eq!(
+            process_game_id(&environ(&["HOME=/root", "SteamAppId=480"]), []),
+            Some("480".to_string())
+        );
+        assert_eq!(process_game_id(&environ(&["SteamGameId=0"]), []), None);
+        assert_eq!(process_game_id(&environ(&[]), [game_dir.as_path()]), None);
+
+        fs::write(game_dir.join(STEAM_APPID_FILE), "570\n").expect("write file");
+        assert_eq!(
+            process_game_id(&environ(&[]), [game_dir.as_path()]),
+            Some("570".to_string())
+        );
+
+        let _ = fs::remove_dir_all(&game_dir);
+    }
+}
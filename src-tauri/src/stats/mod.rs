@@ -0,0 +1,334 @@
+//! Estatísticas de usuário (não-conquistas) dos emuladores.
+//!
+//! - Goldberg/GSE: `GSE Saves/<appid>/stats/<nome>`, um arquivo binário de
+//!   4 bytes (i32 ou f32 little-endian) por estatística.
+//! - CODEX/RUNE e demais INI: `stats.ini` ao lado de `achievements.ini`, seção `[Stats]`.
+//!
+//! O arquivo Goldberg não guarda o tipo: o schema Steam em cache
+//! (`UserGameStatsSchema_<appid>.bin`) diz quais stats são float; sem ele o
+//! tipo é deduzido dos bits.
+//!
+//! Estatísticas da Steam são lidas/escritas pelo Steamworks (`SteamMonitor`)
+//! ou, offline, dos `UserGameStats` do cliente (`LocalSteamState`).
+
+use crate::formats;
+use crate::integrations::steam::steam_schema::SteamSchema;
+use crate::models::{Cracker, StatValue, UserStat};
+use crate::parser::detect_cracker_from_path;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STATS_INI: &str = "stats.ini";
+const STATS_INI_SECTION: &str = "Stats";
+const GOLDBERG_STATS_DIR: &str = "stats";
+
+/// Onde as estatísticas de um jogo ficam gravadas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatsLocation {
+    /// Diretório com um arquivo binário por estatística (Goldberg/GSE).
+    GoldbergDir(PathBuf),
+    /// `stats.ini` com seção `[Stats]` (CODEX/RUNE).
+    Ini(PathBuf),
+}
+
+impl StatsLocation {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::GoldbergDir(path) | Self::Ini(path) => path,
+        }
+    }
+}
+
+/// Nome do stat → se é float, segundo o schema Steam.
+pub type StatTypes = HashMap<String, bool>;
+
+pub struct StatsParser;
+
+impl StatsParser {
+    /// Encontra as estatísticas existentes no diretório do jogo.
+    pub fn find_stats_location(game_dir: &Path) -> Option<StatsLocation> {
+        let ini_path = game_dir.join(STATS_INI);
+        if ini_path.is_file() {
+            return Some(StatsLocation::Ini(ini_path));
+        }
+
+        let stats_dir = game_dir.join(GOLDBERG_STATS_DIR);
+        if stats_dir.is_dir() && !Self::goldberg_stat_files(&stats_dir).is_empty() {
+            return Some(StatsLocation::GoldbergDir(stats_dir));
+        }
+
+        None
+    }
+
+    /// Local existente ou, se não houver, o local padrão do cracker detectado
+    /// pelo caminho do jogo.
+    pub fn stats_location_for_game(game_dir: &Path) -> StatsLocation {
+        if let Some(location) = Self::find_stats_location(game_dir) {
+            return location;
+        }
+
+        match detect_cracker_from_path(&game_dir.to_string_lossy()) {
+            Cracker::Goldberg | Cracker::Empress => {
+                StatsLocation::GoldbergDir(game_dir.join(GOLDBERG_STATS_DIR))
+            }
+            _ => StatsLocation::Ini(game_dir.join(STATS_INI)),
+        }
+    }
+
+    /// Tipos dos stats no schema Steam em cache; vazio se o jogo não tiver
+    /// schema.
+    pub fn schema_stat_types(game_id: &str) -> StatTypes {
+        let Ok(app_id) = game_id.parse::<u32>() else {
+            return StatTypes::new();
+        };
+
+        match SteamSchema::load(app_id) {
+            Ok(Some(schema)) => schema
+                .stats
+                .into_iter()
+                .map(|stat| (stat.name, stat.is_float))
+                .collect(),
+            Ok(None) => StatTypes::new(),
+            Err(e) => {
+                log::warn!("Failed to load Steam schema for game {}: {}", game_id, e);
+                StatTypes::new()
+            }
+        }
+    }
+
+    /// Lê as estatísticas de um local. Local inexistente resulta em lista vazia.
+    pub fn read_stats(location: &StatsLocation, types: &StatTypes) -> Result<Vec<UserStat>> {
+        match location {
+            StatsLocation::GoldbergDir(dir) => Self::read_goldberg_stats(dir, types),
+            StatsLocation::Ini(path) => Self::read_ini_stats(path),
+        }
+    }
+
+    /// Lê as estatísticas do diretório do jogo, se houver alguma.
+    pub fn read_game_stats(game_dir: &Path, types: &StatTypes) -> Result<Vec<UserStat>> {
+        match Self::find_stats_location(game_dir) {
+            Some(location) => Self::read_stats(&location, types),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn goldberg_stat_files(dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        // RLD! usa `stats/achievements.ini`: só arquivos sem extensão de 4 bytes
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_none())
+            .filter(|path| {
+                fs::metadata(path)
+                    .map(|m| m.is_file() && m.len() == 4)
+                    .unwrap_or(false)
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn read_goldberg_stats(dir: &Path, types: &StatTypes) -> Result<Vec<UserStat>> {
+        let mut stats = Vec::new();
+
+        for path in Self::goldberg_stat_files(dir) {
+            let bytes = fs::read(&path)
+                .with_context(|| format!("Failed to read stat file: {}", path.display()))?;
+            let Ok(raw) = <[u8; 4]>::try_from(bytes.as_slice()) else {
+                continue;
+            };
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+
+            stats.push(UserStat {
+                name: name.to_string(),
+                value: decode_goldberg_value(raw, types.get(name).copied()),
+            });
+        }
+
+        Ok(stats)
+    }
+
+    fn read_ini_stats(path: &Path) -> Result<Vec<UserStat>> {
+        let doc = formats::read_ini_document(path)?;
+
+        Ok(doc
+            .pairs(STATS_INI_SECTION)
+            .into_iter()
+            .filter_map(|(name, value)| {
+                parse_ini_value(value).map(|value| UserStat {
+                    name: name.to_string(),
+                    value,
+                })
+            })
+            .collect())
+    }
+}
+
+pub struct StatsWriter;
+
+impl StatsWriter {
+    /// Grava as estatísticas informadas; as demais permanecem como estão.
+    pub fn write_stats(location: &StatsLocation, stats: &[UserStat]) -> Result<()> {
+        match location {
+            StatsLocation::GoldbergDir(dir) => Self::write_goldberg_stats(dir, stats),
+            StatsLocation::Ini(path) => Self::write_ini_stats(path, stats),
+        }
+    }
+
+    fn write_goldberg_stats(dir: &Path, stats: &[UserStat]) -> Result<()> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+
+        for stat in stats {
+            let bytes = match stat.value {
+                StatValue::Int(value) => value.to_le_bytes(),
+                StatValue::Float(value) => value.to_le_bytes(),
+            };
+            let path = dir.join(&stat.name);
+            // Sem snapshot: cada arquivo do diretório é lido como um stat
+            formats::snapshot::write_atomic(&path, &bytes)
+                .with_context(|| format!("Failed to write stat file: {}", path.display()))?;
+        }
+
+        log::info!("Goldberg stats written: {}", dir.display());
+        Ok(())
+    }
+
+    fn write_ini_stats(path: &Path, stats: &[UserStat]) -> Result<()> {
+        let mut doc = formats::read_ini_document(path)?;
+        for stat in stats {
+            doc.set(STATS_INI_SECTION, &stat.name, &format_ini_value(stat.value));
+        }
+
+        formats::write_text(path, &doc.to_string(), "Stats INI")
+    }
+}
+
+/// Interpreta os 4 bytes de um stat Goldberg.
+///
+/// Com o tipo do schema, ele decide. Sem schema: inteiros "pequenos" são i32;
+/// padrões de bits que só fazem sentido como float normal são f32.
+fn decode_goldberg_value(raw: [u8; 4], is_float: Option<bool>) -> StatValue {
+    match is_float {
+        Some(true) => return StatValue::Float(f32::from_le_bytes(raw)),
+        Some(false) => return StatValue::Int(i32::from_le_bytes(raw)),
+        None => {}
+    }
+
+    let int_value = i32::from_le_bytes(raw);
+    if int_value.unsigned_abs() < (1 << 24) {
+        return StatValue::Int(int_value);
+    }
+
+    let float_value = f32::from_le_bytes(raw);
+    if float_value.is_normal() && float_value.abs() < 1.0e9 {
+        StatValue::Float(float_value)
+    } else {
+        StatValue::Int(int_value)
+    }
+}
+
+fn parse_ini_value(value: &str) -> Option<StatValue> {
+    let value = value.trim();
+    if let Ok(int_value) = value.parse::<i32>() {
+        return Some(StatValue::Int(int_value));
+    }
+    value.parse::<f32>().ok().map(StatValue::Float)
+}
+
+/// Floats sempre levam `.` para serem relidos como float.
+fn format_ini_value(value: StatValue) -> String {
+    match value {
+        StatValue::Int(value) => value.to_string(),
+        StatValue::Float(value) => format!("{:?}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    fn sample_stats() -> Vec<UserStat> {
+        vec![
+            UserStat {
+                name: "ENEMIES_KILLED".to_string(),
+                value: StatValue::Int(42),
+            },
+            UserStat {
+                name: "DISTANCE_KM".to_string(),
+                value: StatValue::Float(12.5),
+            },
+        ]
+    }
+
+    #[test]
+    fn goldberg_and_ini_stats_roundtrip_with_types() {
+        let temp_dir = unique_temp_dir("stats_roundtrip");
+        let mut expected = sample_stats();
+        expected.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for location in [
+            StatsLocation::GoldbergDir(temp_dir.join("GSE Saves/480/stats")),
+            StatsLocation::Ini(temp_dir.join("CODEX/480/stats.ini")),
+        ] {
+            StatsWriter::write_stats(&location, &sample_stats()).expect("write");
+            let mut parsed = StatsParser::read_stats(&location, &StatTypes::new()).expect("read");
+            parsed.sort_by(|a, b| a.name.cmp(&b.name));
+            assert_eq!(parsed, expected, "{:?}", location);
+        }
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn goldberg_stats_use_schema_types_when_available() {
+        let temp_dir = unique_temp_dir("stats_schema");
+        let location = StatsLocation::GoldbergDir(temp_dir.join("stats"));
+        let stats = vec![
+            UserStat {
+                name: "GOLD_EARNED".to_string(),
+                value: StatValue::Int(20_000_000),
+            },
+            UserStat {
+                name: "ACCURACY".to_string(),
+                value: StatValue::Float(0.0),
+            },
+        ];
+        StatsWriter::write_stats(&location, &stats).expect("write");
+
+        // Sem schema, a dedução pelos bits erra os dois
+        let guessed = StatsParser::read_stats(&location, &StatTypes::new()).expect("read");
+        assert!(guessed.iter().all(|stat| !stats.contains(stat)));
+
+        let types = StatTypes::from([
+            ("GOLD_EARNED".to_string(), false),
+            ("ACCURACY".to_string(), true),
+        ]);
+        let mut parsed = StatsParser::read_stats(&location, &types).expect("read");
+        parsed.sort_by(|a, b| b.name.cmp(&a.name));
+        assert_eq!(parsed, stats);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn rld_stats_dir_is_not_mistaken_for_goldberg() {
+        let temp_dir = unique_temp_dir("stats_rld");
+        let game_dir = temp_dir.join("480");
+        fs::create_dir_all(game_dir.join("stats")).expect("create dir");
+        fs::write(game_dir.join("stats/achievements.ini"), "[A]\n").expect("write");
+
+        assert_eq!(StatsParser::find_stats_location(&game_dir), None);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
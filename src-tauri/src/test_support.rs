@@ -0,0 +1,13 @@
+//! Utilitários compartilhados pelos testes.
+
+use std::path::PathBuf;
+
+/// Caminho exclusivo `ham_<name>_<nanos>` no diretório temporário (não é
+/// criado).
+pub fn unique_temp_dir(name: &str) -> PathBuf {
+    let suffix = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system clock before unix epoch")
+        .as_nanos();
+    std::env::temp_dir().join(format!("ham_{name}_{suffix}"))
+}
@@ -0,0 +1,192 @@
+//! Conversão entre formatos de emulador.
+//!
+//! Lê o arquivo de um jogo com o parser de um [`Cracker`] e grava no formato
+//! de outro, mantendo o que já existe no destino. Sem `apply`, só devolve a
+//! prévia do que mudaria.
+
+use super::{AchievementUnlocker, AchievementWriter};
+use crate::formats;
+use crate::models::{
+    AchievementChangeKind, AchievementEntry, ConversionPreview, ConversionRequest, Cracker,
+};
+use crate::parser::{expand_path, AchievementParser};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+pub struct AchievementConverter;
+
+impl AchievementConverter {
+    /// Calcula a conversão e, com `apply`, grava o destino.
+    pub fn convert(request: &ConversionRequest, apply: bool) -> Result<ConversionPreview> {
+        let source_path = expand_path(&request.source_path);
+        let source_cracker = match request.source_cracker {
+            Some(cracker) => cracker,
+            None => formats::detect::best_match(&source_path).with_context(|| {
+                format!("Unknown achievement format: {}", source_path.display())
+            })?,
+        };
+
+        let mut source = AchievementParser::parse_achievement_file(&source_path, source_cracker)
+            .with_context(|| format!("Failed to read source file: {}", source_path.display()))?;
+        let mut warnings = Vec::new();
+
+        let filled = fill_missing_unlock_times(&mut source, &source_path, source_cracker);
+        if filled > 0 {
+            warnings.push(format!(
+                "{} unlock time(s) taken from file modification time",
+                filled
+            ));
+        }
+
+        let target_path = Self::target_path(request);
+
+        let existing = if target_path.exists() {
+            AchievementParser::parse_achievement_file(&target_path, request.target_cracker)
+                .with_context(|| {
+                    format!(
+                        "Refusing to convert over unreadable file: {}",
+                        target_path.display()
+                    )
+                })?
+        } else {
+            Vec::new()
+        };
+
+        let (merged, changes) = AchievementUnlocker::merge_entries(existing, &source);
+
+        if request.target_cracker == Cracker::Flt {
+            let dropped = changes
+                .iter()
+                .filter(|change| change.after.achieved && change.after.unlock_time > 0)
+                .count();
+            if dropped > 0 {
+                warnings.push(format!(
+                    "FLT does not store unlock times; {} timestamp(s) will be lost",
+                    dropped
+                ));
+            }
+        }
+
+        let changed = changes
+            .iter()
+            .filter(|change| change.kind != AchievementChangeKind::Unchanged)
+            .count();
+        let written = apply && changed > 0;
+        if written {
+            AchievementWriter::write_achievements(&target_path, &merged, request.target_cracker)?;
+            log::info!(
+                "Converted {} ({:?}) to {} ({:?}): {} change(s)",
+                source_path.display(),
+                source_cracker,
+                target_path.display(),
+                request.target_cracker,
+                changed
+            );
+        }
+
+        Ok(ConversionPreview {
+            source_path: source_path.to_string_lossy().to_string(),
+            source_cracker,
+            target_path: target_path.to_string_lossy().to_string(),
+            target_cracker: request.target_cracker,
+            changes,
+            warnings,
+            written,
+        })
+    }
+
+    /// Arquivo de destino: `target_path` ou o padrão do formato em `target_dir`.
+    pub fn target_path(request: &ConversionRequest) -> PathBuf {
+        match &request.target_path {
+            Some(path) => expand_path(path),
+            None => Self::target_file(
+                &expand_path(&request.target_dir).join(&request.game_id),
+                &request.game_id,
+                request.target_cracker,
+            ),
+        }
+    }
+
+    /// Arquivo padrão do formato no diretório do jogo: o primeiro candidato
+    /// ou, para o FLT (que não tem nome de arquivo), o diretório `stats`.
+    pub fn target_file(game_dir: &Path, game_id: &str, cracker: Cracker) -> PathBuf {
+        match formats::format_for(cracker).candidate_files().first() {
+            Some(candidate) => game_dir.join(candidate.replace("<objectId>", game_id)),
+            None => game_dir.join("stats"),
+        }
+    }
+}
+
+/// Conquistas desbloqueadas sem horário (FLT, Razor1911 antigo...) recebem o
+/// mtime do arquivo; no FLT, o de cada arquivo de conquista.
+fn fill_missing_unlock_times(
+    entries: &mut [AchievementEntry],
+    source_path: &Path,
+    cracker: Cracker,
+) -> usize {
+    let mut filled = 0;
+    for entry in entries
+        .iter_mut()
+        .filter(|entry| entry.achieved && entry.unlock_time <= 0)
+    {
+        let path = if cracker == Cracker::Flt {
+            source_path.join(&entry.name)
+        } else {
+            source_path.to_path_buf()
+        };
+        if let Some(mtime) = modified_unix(&path) {
+            entry.unlock_time = mtime;
+            filled += 1;
+        }
+    }
+    filled
+}
+
+fn modified_unix(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn converts_flt_directory_into_razor1911_with_file_times() {
+        let base = unique_temp_dir("convert");
+        let flt_dir = base.join("flt").join("480").join("stats");
+        fs::create_dir_all(&flt_dir).expect("create temp dir");
+        fs::write(flt_dir.join("ACH_ONE"), "").expect("write file");
+
+        let request = ConversionRequest {
+            source_path: flt_dir.to_string_lossy().to_string(),
+            source_cracker: Some(Cracker::Flt),
+            game_id: "480".to_string(),
+            target_dir: base.join("razor").to_string_lossy().to_string(),
+            target_cracker: Cracker::Razor1911,
+            target_path: None,
+        };
+
+        let preview = AchievementConverter::convert(&request, false).expect("preview");
+        assert!(!preview.written);
+        assert_eq!(preview.changes.len(), 1);
+        assert_eq!(preview.changes[0].kind, AchievementChangeKind::Unlocked);
+        assert!(preview.changes[0].after.unlock_time > 0);
+        assert!(!Path::new(&preview.target_path).exists());
+
+        let converted = AchievementConverter::convert(&request, true).expect("convert");
+        assert!(converted.written);
+        let written =
+            AchievementParser::parse_achievement_file(&converted.target_path, Cracker::Razor1911)
+                .expect("parse razor");
+        assert_eq!(written, vec![converted.changes[0].after.clone()]);
+
+        let again = AchievementConverter::convert(&request, true).expect("convert again");
+        assert!(!again.written);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
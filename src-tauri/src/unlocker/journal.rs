@@ -0,0 +1,446 @@
+//! Diário de operações sobre arquivos de conquista.
+//!
+//! Cada escrita feita pelo HAM (unlock, relock, restauração, conversão) vira
+//! uma linha JSON em um arquivo só de acréscimo, com o formato e as conquistas
+//! do arquivo antes e depois. [`OperationJournal::undo`] reverte uma operação
+//! específica: só as conquistas que ela alterou voltam ao estado anterior, e o
+//! que o jogo desbloqueou depois continua no arquivo.
+
+use super::AchievementWriter;
+use crate::models::{AchievementEntry, Cracker};
+use crate::parser::AchievementParser;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bytes lidos por vez por [`OperationJournal::since`], do fim para o começo.
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Tipo de operação registrada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationKind {
+    Unlock,
+    Relock,
+    Restore,
+    Convert,
+    Undo,
+}
+
+/// Uma escrita em um arquivo de conquista
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalRecord {
+    pub id: String,
+    pub kind: OperationKind,
+    /// Unix ms
+    pub created_at: i64,
+    pub game_id: String,
+    pub file_path: String,
+    pub cracker: Cracker,
+    /// Conteúdo antes da escrita; `None` quando o arquivo não existia
+    pub previous: Option<Vec<AchievementEntry>>,
+    /// Conteúdo depois da escrita
+    pub entries: Vec<AchievementEntry>,
+    /// Operação revertida, em registros do tipo `undo`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<String>,
+}
+
+pub struct OperationJournal {
+    path: PathBuf,
+}
+
+impl OperationJournal {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Conquistas atuais do arquivo; `None` quando ele não existe.
+    pub fn capture(file_path: &Path, cracker: Cracker) -> Result<Option<Vec<AchievementEntry>>> {
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        AchievementParser::parse_achievement_file(file_path, cracker)
+            .map(Some)
+            .with_context(|| format!("Failed to read {}", file_path.display()))
+    }
+
+    /// Registra uma escrita. Não grava nada (e devolve `None`) quando o
+    /// conteúdo não mudou.
+    ///
+    /// `Unlock` vira `Relock` quando a escrita só bloqueou conquistas.
+    pub fn record(
+        &self,
+        kind: OperationKind,
+        game_id: &str,
+        file_path: &Path,
+        cracker: Cracker,
+        previous: Option<Vec<AchievementEntry>>,
+        entries: Vec<AchievementEntry>,
+    ) -> Result<Option<JournalRecord>> {
+        if previous.as_ref() == Some(&entries) {
+            return Ok(None);
+        }
+
+        let kind = if kind == OperationKind::Unlock && only_relocks(previous.as_deref(), &entries) {
+            OperationKind::Relock
+        } else {
+            kind
+        };
+
+        let record = new_record(kind, game_id, file_path, cracker, previous, entries);
+        self.append(&record)?;
+        Ok(Some(record))
+    }
+
+    /// Operações registradas, da mais recente para a mais antiga.
+    pub fn list(&self) -> Result<Vec<JournalRecord>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read journal: {}", self.path.display()))
+            }
+        };
+
+        let mut records: Vec<JournalRecord> = content.lines().filter_map(parse_line).collect();
+        records.reverse();
+        Ok(records)
+    }
+
+    /// Operações criadas a partir de `since` (unix ms), da mais recente para
+    /// a mais antiga.
+    ///
+    /// O diário só cresce: ele é lido de trás para frente, em blocos, até o
+    /// primeiro registro mais antigo que `since`.
+    pub fn since(&self, since: i64) -> Result<Vec<JournalRecord>> {
+        self.since_in_chunks(since, TAIL_CHUNK_BYTES)
+    }
+
+    fn since_in_chunks(&self, since: i64, chunk_bytes: u64) -> Result<Vec<JournalRecord>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read journal: {}", self.path.display()))
+            }
+        };
+
+        let mut records = Vec::new();
+        let mut end = file.metadata()?.len();
+        // Começo de uma linha que continua no bloco anterior do arquivo
+        let mut carry: Vec<u8> = Vec::new();
+        while end > 0 {
+            let start = end.saturating_sub(chunk_bytes);
+            let mut chunk = vec![0; (end - start) as usize];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut chunk)
+                .with_context(|| format!("Failed to read journal: {}", self.path.display()))?;
+            chunk.append(&mut carry);
+            end = start;
+
+            // Antes do primeiro `\n` pode haver só o fim de uma linha
+            let first_line = match chunk.iter().position(|&b| b == b'\n') {
+                _ if start == 0 => 0,
+                Some(index) => index + 1,
+                None => {
+                    carry = chunk;
+                    continue;
+                }
+            };
+            carry = chunk[..first_line].to_vec();
+
+            let text = String::from_utf8_lossy(&chunk[first_line..]);
+            for record in text.lines().rev().filter_map(parse_line) {
+                if record.created_at < since {
+                    return Ok(records);
+                }
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
+    /// Reverte as conquistas alteradas pela operação `id` no arquivo atual e
+    /// registra a reversão como uma nova operação.
+    pub fn undo(&self, id: &str) -> Result<JournalRecord> {
+        let record = self
+            .list()?
+            .into_iter()
+            .find(|record| record.id == id)
+            .with_context(|| format!("Operation not found: {}", id))?;
+
+        let file_path = PathBuf::from(&record.file_path);
+        let current = Self::capture(&file_path, record.cracker)?;
+        let reverted = revert(
+            current.as_deref().unwrap_or_default(),
+            record.previous.as_deref().unwrap_or_default(),
+            &record.entries,
+        );
+
+        AchievementWriter::write_achievements(&file_path, &reverted, record.cracker)?;
+        log::info!(
+            "Undid operation {} ({:?}) on {}",
+            record.id,
+            record.kind,
+            file_path.display()
+        );
+
+        let mut undo = new_record(
+            OperationKind::Undo,
+            &record.game_id,
+            &file_path,
+            record.cracker,
+            current,
+            reverted,
+        );
+        undo.undoes = Some(record.id);
+        self.append(&undo)?;
+        Ok(undo)
+    }
+
+    fn append(&self, record: &JournalRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open journal: {}", self.path.display()))?;
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("Failed to write journal: {}", self.path.display()))?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Option<JournalRecord> {
+    if line.trim().is_empty() {
+        return None;
+    }
+    match serde_json::from_str(line) {
+        Ok(record) => Some(record),
+        // Uma linha truncada por queda não invalida o resto do diário
+        Err(e) => {
+            log::warn!("Skipping malformed journal line: {}", e);
+            None
+        }
+    }
+}
+
+fn new_record(
+    kind: OperationKind,
+    game_id: &str,
+    file_path: &Path,
+    cracker: Cracker,
+    previous: Option<Vec<AchievementEntry>>,
+    entries: Vec<AchievementEntry>,
+) -> JournalRecord {
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    JournalRecord {
+        id: format!("{:x}-{:04x}", created_at, rand::random::<u16>()),
+        kind,
+        created_at,
+        game_id: game_id.to_string(),
+        file_path: file_path.to_string_lossy().to_string(),
+        cracker,
+        previous,
+        entries,
+        undoes: None,
+    }
+}
+
+fn only_relocks(previous: Option<&[AchievementEntry]>, entries: &[AchievementEntry]) -> bool {
+    let previous = previous.unwrap_or_default();
+    let was_achieved = |name: &str| previous.iter().any(|e| e.name == name && e.achieved);
+    let now_achieved = |name: &str| entries.iter().any(|e| e.name == name && e.achieved);
+
+    !entries
+        .iter()
+        .any(|entry| entry.achieved && !was_achieved(&entry.name))
+        && previous
+            .iter()
+            .any(|entry| entry.achieved && !now_achieved(&entry.name))
+}
+
+/// Devolve `current` com as conquistas que mudaram entre `previous` e
+/// `entries` de volta ao estado de `previous`. As que não existiam voltam
+/// bloqueadas, já que nem todo formato consegue remover uma conquista.
+fn revert(
+    current: &[AchievementEntry],
+    previous: &[AchievementEntry],
+    entries: &[AchievementEntry],
+) -> Vec<AchievementEntry> {
+    let find =
+        |list: &[AchievementEntry], name: &str| list.iter().find(|e| e.name == name).cloned();
+    let restored = |name: &str| {
+        find(previous, name).unwrap_or_else(|| AchievementEntry {
+            name: name.to_string(),
+            achieved: false,
+            unlock_time: 0,
+            cur_progress: None,
+            max_progress: None,
+        })
+    };
+
+    let mut touched: Vec<&str> = Vec::new();
+    for name in entries.iter().chain(previous).map(|e| e.name.as_str()) {
+        if !touched.contains(&name) && find(previous, name) != find(entries, name) {
+            touched.push(name);
+        }
+    }
+
+    let mut reverted: Vec<AchievementEntry> = current
+        .iter()
+        .map(|entry| {
+            if touched.contains(&entry.name.as_str()) {
+                restored(&entry.name)
+            } else {
+                entry.clone()
+            }
+        })
+        .collect();
+    for name in touched {
+        if !current.iter().any(|e| e.name == name) {
+            reverted.push(restored(name));
+        }
+    }
+    reverted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    fn entry(name: &str, achieved: bool, unlock_time: i64) -> AchievementEntry {
+        AchievementEntry {
+            name: name.to_string(),
+            achieved,
+            unlock_time,
+            cur_progress: None,
+            max_progress: None,
+        }
+    }
+
+    #[test]
+    fn undo_reverts_only_what_the_operation_changed() {
+        let dir = unique_temp_dir("journal");
+        fs::create_dir_all(&dir).expect("create temp dir");
+        let file_path = dir.join("achievements.ini");
+        let journal = OperationJournal::new(dir.join("journal.jsonl"));
+
+        let before = vec![entry("ACH_OLD", true, 1_600_000_000)];
+        AchievementWriter::write_achievements(&file_path, &before, Cracker::Codex).expect("write");
+
+        // Unlock em massa: desbloqueia duas e bloqueia a antiga
+        let bulk = vec![
+            entry("ACH_OLD", false, 0),
+            entry("ACH_ONE", true, 1_700_000_000),
+            entry("ACH_TWO", true, 1_700_000_100),
+        ];
+        let previous = OperationJournal::capture(&file_path, Cracker::Codex).expect("capture");
+        AchievementWriter::write_achievements(&file_path, &bulk, Cracker::Codex).expect("write");
+        let after = OperationJournal::capture(&file_path, Cracker::Codex)
+            .expect("capture")
+            .expect("file exists");
+        let record = journal
+            .record(
+                OperationKind::Unlock,
+                "480",
+                &file_path,
+                Cracker::Codex,
+                previous,
+                after.clone(),
+            )
+            .expect("record")
+            .expect("changed");
+        assert_eq!(record.kind, OperationKind::Unlock);
+        assert!(journal
+            .record(
+                OperationKind::Unlock,
+                "480",
+                &file_path,
+                Cracker::Codex,
+                Some(after.clone()),
+                after,
+            )
+            .expect("record")
+            .is_none());
+
+        // O jogo desbloqueia outra depois da operação
+        let mut played = bulk.clone();
+        played.push(entry("ACH_GAME", true, 1_700_000_500));
+        AchievementWriter::write_achievements(&file_path, &played, Cracker::Codex).expect("write");
+
+        let undo = journal.undo(&record.id).expect("undo");
+        assert_eq!(undo.undoes.as_deref(), Some(record.id.as_str()));
+        let reverted =
+            AchievementParser::parse_achievement_file(&file_path, Cracker::Codex).expect("parse");
+        let mut unlocked: Vec<(&str, i64)> = reverted
+            .iter()
+            .filter(|e| e.achieved)
+            .map(|e| (e.name.as_str(), e.unlock_time))
+            .collect();
+        unlocked.sort_unstable();
+        assert_eq!(
+            unlocked,
+            vec![("ACH_GAME", 1_700_000_500), ("ACH_OLD", 1_600_000_000)]
+        );
+
+        let records = journal.list().expect("list");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, OperationKind::Undo);
+        assert_eq!(records[1].id, record.id);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn since_reads_only_the_tail_of_the_journal() {
+        let dir = unique_temp_dir("journal_since");
+        let journal = OperationJournal::new(dir.join("journal.jsonl"));
+        assert!(journal.since(0).expect("missing journal").is_empty());
+
+        for (index, created_at) in [1_000, 2_000, 3_000, 4_000].into_iter().enumerate() {
+            let mut record = new_record(
+                OperationKind::Unlock,
+                "480",
+                &dir.join("achievements.ini"),
+                Cracker::Codex,
+                None,
+                vec![entry(&format!("ACH_{index}"), true, created_at / 1000)],
+            );
+            record.created_at = created_at;
+            journal.append(&record).expect("append");
+        }
+
+        // Blocos menores que uma linha exercitam as linhas partidas
+        for chunk_bytes in [7, 100, TAIL_CHUNK_BYTES] {
+            let recent = journal.since_in_chunks(2_500, chunk_bytes).expect("since");
+            let times: Vec<i64> = recent.iter().map(|record| record.created_at).collect();
+            assert_eq!(times, vec![4_000, 3_000], "chunk {chunk_bytes}");
+        }
+        assert_eq!(
+            journal.since(0).expect("since"),
+            journal.list().expect("list")
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,472 +1,158 @@
+pub mod convert;
+pub mod journal;
+pub mod schedule;
+pub mod timestamp;
+
+use crate::formats;
 use crate::models::{
-    AchievementEntry, Cracker, DirectoryDetectionPreset, TimeFormat, Timestamp, UnlockMode,
-    UnlockOptions,
+    AchievementChange, AchievementChangeKind, AchievementEntry, AchievementToUnlock, ChangeReport,
+    Cracker, DirectoryDetectionPreset, FileAction, MirrorTargetResult, TimeFormat, Timestamp,
+    UnlockMode, UnlockOptions, UnlockPreview,
 };
 use crate::parser::{detect_cracker_from_path, expand_path, AchievementParser};
 use anyhow::{Context, Result};
-use chrono::{Datelike, NaiveDateTime, Timelike};
+use chrono::{Datelike, Timelike};
 use rand::Rng;
-use serde_json::{Map, Value};
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub struct AchievementWriter;
 
 impl AchievementWriter {
-    /// Converte Timestamp para Unix timestamp
-    pub fn timestamp_to_unix(timestamp: &Timestamp) -> i64 {
-        let day: u32 = timestamp.day.parse().unwrap_or(1);
-        let month: u32 = timestamp.month.parse().unwrap_or(1);
-        let year: i32 = timestamp.year.parse().unwrap_or(2024);
-        let minute: u32 = timestamp.minute.parse().unwrap_or(0);
-
-        let mut hour: u32 = timestamp.hour.parse().unwrap_or(0);
-
-        // Converte 12h para 24h se necessário
-        if let Some(ampm) = &timestamp.ampm {
-            if ampm == "PM" && hour != 12 {
-                hour += 12;
-            } else if ampm == "AM" && hour == 12 {
-                hour = 0;
-            }
-        }
-
-        // Cria NaiveDateTime
-        if let Some(dt) = NaiveDateTime::new(
-            chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default(),
-            chrono::NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or_default(),
-        )
-        .and_local_timezone(chrono::Local)
-        .single()
-        {
-            dt.timestamp()
-        } else {
-            0
-        }
+    /// Converte Timestamp para Unix timestamp.
+    ///
+    /// Falha com uma mensagem de validação em datas inválidas, fusos
+    /// desconhecidos ou horários inexistentes no fuso (horário de verão).
+    pub fn timestamp_to_unix(timestamp: &Timestamp) -> Result<i64> {
+        timestamp::to_unix(timestamp)
     }
 
     // ── Writers por formato ─────────────────────────────────────────────
 
     /// Escreve arquivo INI no formato padrão (CODEX, RUNE, RLE, SmartSteamEmu).
-    ///
-    /// Formato:
-    /// ```ini
-    /// [AchievementName]
-    /// Achieved=1
-    /// UnlockTime=1234567890
-    /// ```
     pub fn write_default_ini<P: AsRef<Path>>(
         file_path: P,
         achievements: &[AchievementEntry],
     ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::new();
-        for ach in achievements {
-            content.push_str(&format!("[{}]\n", ach.name));
-            content.push_str(&format!(
-                "Achieved={}\n",
-                if ach.achieved { "1" } else { "0" }
-            ));
-            content.push_str(&format!("UnlockTime={}\n", ach.unlock_time));
-            content.push('\n');
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write INI file: {}", file_path.display()))?;
-        log::info!("INI file written: {}", file_path.display());
-        Ok(())
-    }
-
-    /// Escreve arquivo INI no formato OnlineFix.
-    ///
-    /// Formato:
-    /// ```ini
-    /// [AchievementName]
-    /// achieved=true
-    /// timestamp=1234567890
-    /// ```
-    pub fn write_online_fix_ini<P: AsRef<Path>>(
-        file_path: P,
-        achievements: &[AchievementEntry],
-    ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::new();
-        for ach in achievements {
-            content.push_str(&format!("[{}]\n", ach.name));
-            content.push_str(&format!(
-                "achieved={}\n",
-                if ach.achieved { "true" } else { "false" }
-            ));
-            content.push_str(&format!("timestamp={}\n", ach.unlock_time));
-            content.push('\n');
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write OnlineFix file: {}", file_path.display()))?;
-        log::info!("OnlineFix file written: {}", file_path.display());
-        Ok(())
+        formats::default_ini::write(file_path.as_ref(), achievements)
     }
 
-    /// Escreve arquivo JSON no formato Goldberg/EMPRESS.
-    ///
-    /// Formato:
-    /// ```json
-    /// {
-    ///   "AchievementName": {
-    ///     "earned": true,
-    ///     "earned_time": 1234567890
-    ///   }
-    /// }
-    /// ```
-    ///
-    /// Preserva campos extras existentes no arquivo.
+    /// Escreve arquivo JSON no formato Goldberg/EMPRESS, preservando campos extras.
     pub fn write_goldberg_json<P: AsRef<Path>>(
         file_path: P,
         achievements: &[AchievementEntry],
     ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut root_obj: Map<String, Value> = if file_path.exists() {
-            let content = fs::read_to_string(file_path)
-                .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            Map::new()
-        };
-
-        for ach in achievements {
-            let mut entry_obj = root_obj
-                .get(&ach.name)
-                .and_then(|v| v.as_object())
-                .cloned()
-                .unwrap_or_default();
-
-            entry_obj.insert("earned".to_string(), Value::Bool(ach.achieved));
-            entry_obj.insert(
-                "earned_time".to_string(),
-                Value::from(if ach.achieved { ach.unlock_time } else { 0 }),
-            );
-
-            root_obj.insert(ach.name.clone(), Value::Object(entry_obj));
-        }
-
-        let content = serde_json::to_string_pretty(&root_obj)
-            .with_context(|| format!("Failed to serialize JSON: {}", file_path.display()))?;
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write JSON file: {}", file_path.display()))?;
-        log::info!("Goldberg JSON file written: {}", file_path.display());
-        Ok(())
-    }
-
-    /// Escreve arquivo INI no formato RLD! (valores em hexadecimal uint32 LE).
-    ///
-    /// Formato:
-    /// ```ini
-    /// [AchievementName]
-    /// State=01000000
-    /// Time=60E3A458
-    /// ```
-    pub fn write_rld_ini<P: AsRef<Path>>(
-        file_path: P,
-        achievements: &[AchievementEntry],
-    ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::new();
-        for ach in achievements {
-            content.push_str(&format!("[{}]\n", ach.name));
-            let state_hex = if ach.achieved {
-                u32_to_hex_le(1)
-            } else {
-                u32_to_hex_le(0)
-            };
-            let time_hex = u32_to_hex_le(ach.unlock_time as u32);
-            content.push_str(&format!("State={}\n", state_hex));
-            content.push_str(&format!("Time={}\n", time_hex));
-            content.push('\n');
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write RLD! file: {}", file_path.display()))?;
-        log::info!("RLD! file written: {}", file_path.display());
-        Ok(())
-    }
-
-    /// Escreve arquivo INI no formato SKIDROW.
-    ///
-    /// Formato (seção `[Achievements]`):
-    /// ```ini
-    /// [Achievements]
-    /// AchievementName=1@1234567890@DisplayName
-    /// ```
-    pub fn write_skidrow_ini<P: AsRef<Path>>(
-        file_path: P,
-        achievements: &[AchievementEntry],
-    ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::from("[Achievements]\n");
-        for ach in achievements {
-            let flag = if ach.achieved { "1" } else { "0" };
-            content.push_str(&format!(
-                "{}={}@{}@{}\n",
-                ach.name, flag, ach.unlock_time, ach.name
-            ));
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write SKIDROW file: {}", file_path.display()))?;
-        log::info!("SKIDROW file written: {}", file_path.display());
-        Ok(())
-    }
-
-    /// Escreve arquivo INI no formato CreamAPI.
-    ///
-    /// Formato:
-    /// ```ini
-    /// [AchievementName]
-    /// achieved=true
-    /// unlocktime=1234567890
-    /// ```
-    pub fn write_cream_api_ini<P: AsRef<Path>>(
-        file_path: P,
-        achievements: &[AchievementEntry],
-    ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::new();
-        for ach in achievements {
-            content.push_str(&format!("[{}]\n", ach.name));
-            content.push_str(&format!(
-                "achieved={}\n",
-                if ach.achieved { "true" } else { "false" }
-            ));
-            content.push_str(&format!("unlocktime={}\n", ach.unlock_time));
-            content.push('\n');
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write CreamAPI file: {}", file_path.display()))?;
-        log::info!("CreamAPI file written: {}", file_path.display());
-        Ok(())
+        formats::goldberg::write(file_path.as_ref(), achievements)
     }
 
     /// Escreve arquivo INI no formato user_stats.
-    ///
-    /// Formato (seção `[ACHIEVEMENTS]`):
-    /// ```ini
-    /// [ACHIEVEMENTS]
-    /// "AchievementName" = "{unlocked = true, time = 1234567890}"
-    /// ```
     pub fn write_user_stats_ini<P: AsRef<Path>>(
         file_path: P,
         achievements: &[AchievementEntry],
     ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::from("[ACHIEVEMENTS]\n");
-        for ach in achievements {
-            let unlocked = if ach.achieved { "true" } else { "false" };
-            content.push_str(&format!(
-                "\"{}\" = \"{{unlocked = {}, time = {}}}\"\n",
-                ach.name, unlocked, ach.unlock_time
-            ));
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write user_stats file: {}", file_path.display()))?;
-        log::info!("user_stats file written: {}", file_path.display());
-        Ok(())
+        Self::write_achievements(file_path, achievements, Cracker::UserStats)
     }
 
     /// Escreve arquivo INI no formato 3DM.
-    ///
-    /// Formato (seções `[State]` e `[Time]`, valores em hex):
-    /// ```ini
-    /// [State]
-    /// AchievementName=0101
-    /// [Time]
-    /// AchievementName=60E3A458
-    /// ```
     pub fn write_3dm_ini<P: AsRef<Path>>(
         file_path: P,
         achievements: &[AchievementEntry],
     ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::new();
-
-        // Seção State
-        content.push_str("[State]\n");
-        for ach in achievements {
-            let state = if ach.achieved { "0101" } else { "0000" };
-            content.push_str(&format!("{}={}\n", ach.name, state));
-        }
-
-        // Seção Time
-        content.push_str("\n[Time]\n");
-        for ach in achievements {
-            let time_hex = u32_to_hex_le(ach.unlock_time as u32);
-            content.push_str(&format!("{}={}\n", ach.name, time_hex));
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write 3DM file: {}", file_path.display()))?;
-        log::info!("3DM file written: {}", file_path.display());
-        Ok(())
+        Self::write_achievements(file_path, achievements, Cracker::ThreeDm)
     }
 
-    /// Escreve arquivo de texto plano no formato Razor1911.
+    // ── Writer genérico ─────────────────────────────────────────────────
+
+    /// Escreve conquistas no formato do cracker informado.
     ///
-    /// Formato: `<nome> <0|1> <unlockTime>` por linha.
-    pub fn write_razor1911<P: AsRef<Path>>(
+    /// O writer correto é obtido do registro de formatos.
+    pub fn write_achievements<P: AsRef<Path>>(
         file_path: P,
         achievements: &[AchievementEntry],
+        cracker: Cracker,
     ) -> Result<()> {
-        let file_path = file_path.as_ref();
-        Self::ensure_parent_dir(file_path)?;
-
-        let mut content = String::new();
-        for ach in achievements {
-            let flag = if ach.achieved { "1" } else { "0" };
-            content.push_str(&format!("{} {} {}\n", ach.name, flag, ach.unlock_time));
-        }
-
-        fs::write(file_path, content)
-            .with_context(|| format!("Failed to write Razor1911 file: {}", file_path.display()))?;
-        log::info!("Razor1911 file written: {}", file_path.display());
-        Ok(())
-    }
-
-    /// Escreve conquistas FLT (baseado em diretório - cada arquivo = uma conquista).
-    pub fn write_flt_directory<P: AsRef<Path>>(
-        directory_path: P,
-        achievements: &[AchievementEntry],
-    ) -> Result<()> {
-        let directory_path = directory_path.as_ref();
-        fs::create_dir_all(directory_path).with_context(|| {
-            format!(
-                "Failed to create FLT directory: {}",
-                directory_path.display()
-            )
-        })?;
-
-        // Remove arquivos de conquistas que não estão mais desbloqueadas
-        if let Ok(entries) = fs::read_dir(directory_path) {
-            for entry in entries.flatten() {
-                if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    if !achievements.iter().any(|a| a.name == name && a.achieved) {
-                        let _ = fs::remove_file(entry.path());
-                    }
-                }
-            }
-        }
-
-        // Cria arquivos para conquistas desbloqueadas
-        for ach in achievements {
-            if ach.achieved {
-                let file_path = directory_path.join(&ach.name);
-                if !file_path.exists() {
-                    fs::write(&file_path, "").with_context(|| {
-                        format!("Failed to write FLT file: {}", file_path.display())
-                    })?;
-                }
-            }
-        }
-
-        log::info!("FLT directory written: {}", directory_path.display());
-        Ok(())
+        formats::format_for(cracker).write(file_path.as_ref(), achievements)
     }
 
-    // ── Writer genérico ─────────────────────────────────────────────────
-
-    /// Escreve conquistas no formato apropriado baseado no path do arquivo.
+    /// Deixa o arquivo com exatamente `achievements`.
     ///
-    /// Detecta automaticamente o cracker pelo path e usa o writer correto.
-    pub fn write_achievements<P: AsRef<Path>>(
+    /// Os writers editam no lugar: conquistas que só existem no arquivo
+    /// atual são gravadas como `achieved: false`, como no `undo` do diário.
+    pub fn replace_achievements<P: AsRef<Path>>(
         file_path: P,
         achievements: &[AchievementEntry],
         cracker: Cracker,
     ) -> Result<()> {
-        match cracker {
-            Cracker::Codex | Cracker::Rune | Cracker::Rle | Cracker::SmartSteamEmu => {
-                Self::write_default_ini(file_path, achievements)
-            }
-            Cracker::OnlineFix => Self::write_online_fix_ini(file_path, achievements),
-            Cracker::Goldberg | Cracker::Empress => {
-                Self::write_goldberg_json(file_path, achievements)
-            }
-            Cracker::Rld => Self::write_rld_ini(file_path, achievements),
-            Cracker::Skidrow => Self::write_skidrow_ini(file_path, achievements),
-            Cracker::CreamApi => Self::write_cream_api_ini(file_path, achievements),
-            Cracker::UserStats => Self::write_user_stats_ini(file_path, achievements),
-            Cracker::ThreeDm => Self::write_3dm_ini(file_path, achievements),
-            Cracker::Razor1911 => Self::write_razor1911(file_path, achievements),
-            Cracker::Flt => Self::write_flt_directory(file_path, achievements),
-            Cracker::SteamCache => {
-                // Steam cache é read-only (gerenciado pelo cliente Steam)
-                log::warn!("Cannot write to Steam cache (read-only)");
-                Ok(())
-            }
-        }
-    }
-
-    // ── Utilitários ─────────────────────────────────────────────────────
-
-    fn ensure_parent_dir<P: AsRef<Path>>(file_path: P) -> Result<()> {
         let file_path = file_path.as_ref();
-        if let Some(parent) = file_path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
-        }
-        Ok(())
+        let current = if file_path.exists() {
+            AchievementParser::parse_achievement_file(file_path, cracker)?
+        } else {
+            Vec::new()
+        };
+
+        let mut entries = achievements.to_vec();
+        entries.extend(
+            current
+                .into_iter()
+                .filter(|entry| !achievements.iter().any(|a| a.name == entry.name))
+                .map(|entry| AchievementEntry {
+                    name: entry.name,
+                    achieved: false,
+                    unlock_time: 0,
+                    cur_progress: None,
+                    max_progress: None,
+                }),
+        );
+        Self::write_achievements(file_path, &entries, cracker)
     }
 }
 
 pub struct AchievementUnlocker;
 
+/// Escrita de `unlock_achievements` já resolvida, com a prévia correspondente.
+struct UnlockPlan {
+    file_path: PathBuf,
+    cracker: Cracker,
+    entries: Vec<AchievementEntry>,
+    preview: UnlockPreview,
+}
+
 impl AchievementUnlocker {
-    /// Processa achievements para unlock
-    pub fn process_achievements(options: &UnlockOptions) -> Vec<AchievementEntry> {
-        options
+    /// Processa achievements para unlock.
+    ///
+    /// Entradas não concluídas viram `achieved: false` explícito: os writers
+    /// editam o arquivo no lugar, então omiti-las não rebloquearia nada.
+    pub fn process_achievements(options: &UnlockOptions) -> Result<Vec<AchievementEntry>> {
+        let fallback_times = Self::fallback_unlock_times(options)?;
+
+        Ok(options
             .achievements
             .iter()
-            .filter(|ach| ach.completed)
-            .map(|ach| {
-                let unlock_time = if !ach.timestamp.day.is_empty()
-                    && !ach.timestamp.month.is_empty()
-                    && !ach.timestamp.year.is_empty()
-                    && !ach.timestamp.hour.is_empty()
-                    && !ach.timestamp.minute.is_empty()
-                {
-                    AchievementWriter::timestamp_to_unix(&ach.timestamp)
-                } else {
-                    Self::get_global_timestamp(
-                        &options.mode,
-                        options.custom_timestamp.as_ref(),
-                        &options.time_format,
-                    )
-                };
+            .zip(fallback_times)
+            .map(|(ach, unlock_time)| {
+                if !ach.completed {
+                    return AchievementEntry {
+                        name: ach.name.clone(),
+                        achieved: false,
+                        unlock_time: 0,
+                        cur_progress: ach.progress,
+                        max_progress: ach.max_progress,
+                    };
+                }
 
                 AchievementEntry {
                     name: ach.name.clone(),
                     achieved: true,
                     unlock_time,
+                    // Concluída: progresso cheio, se o máximo for conhecido
+                    cur_progress: ach.progress.or(ach.max_progress),
+                    max_progress: ach.max_progress,
                 }
             })
-            .collect()
+            .collect())
+    }
+
+    fn has_timestamp(ach: &AchievementToUnlock) -> bool {
+        timestamp::is_set(&ach.timestamp)
     }
 
     /// Obtém timestamp baseado no modo global
@@ -474,7 +160,7 @@ impl AchievementUnlocker {
         mode: &UnlockMode,
         custom_timestamp: Option<&Timestamp>,
         time_format: &TimeFormat,
-    ) -> i64 {
+    ) -> Result<i64> {
         let timestamp = match mode {
             UnlockMode::Current => {
                 let now = chrono::Local::now();
@@ -497,6 +183,7 @@ impl AchievementUnlocker {
                         }
                         TimeFormat::TwentyFourHour => None,
                     },
+                    ..Timestamp::default()
                 }
             }
             UnlockMode::Random => {
@@ -527,16 +214,13 @@ impl AchievementUnlocker {
                         }
                         TimeFormat::TwentyFourHour => None,
                     },
+                    ..Timestamp::default()
                 }
             }
-            UnlockMode::Custom => custom_timestamp.cloned().unwrap_or(Timestamp {
-                day: String::new(),
-                month: String::new(),
-                year: String::new(),
-                hour: String::new(),
-                minute: String::new(),
-                ampm: None,
-            }),
+            UnlockMode::Custom => custom_timestamp
+                .filter(|timestamp| timestamp::is_set(timestamp))
+                .cloned()
+                .context("Custom unlock mode requires a custom timestamp")?,
         };
 
         AchievementWriter::timestamp_to_unix(&timestamp)
@@ -551,35 +235,476 @@ impl AchievementUnlocker {
         options: &UnlockOptions,
         preset: DirectoryDetectionPreset,
     ) -> Result<()> {
-        let achievement_entries = Self::process_achievements(options);
+        let plan = Self::plan_unlock(options, preset)?;
+
+        // Escreve no formato correto
+        AchievementWriter::write_achievements(&plan.file_path, &plan.entries, plan.cracker)?;
+
+        log::info!(
+            "Achievements unlocked for game {} at {} (format: {:?})",
+            options.game_id,
+            plan.file_path.display(),
+            plan.cracker
+        );
+
+        Ok(())
+    }
+
+    /// O que [`Self::unlock_achievements_with_preset`] gravaria, sem tocar no
+    /// disco.
+    ///
+    /// Os horários gerados pelos modos atual/aleatório e pelas estratégias são
+    /// recalculados a cada chamada; `UnlockPreview::options` traz os da
+    /// prévia fixados, e gravá-las escreve exatamente o que a prévia mostrou.
+    pub fn preview_unlock_with_preset(
+        options: &UnlockOptions,
+        preset: DirectoryDetectionPreset,
+    ) -> Result<UnlockPreview> {
+        Ok(Self::plan_unlock(options, preset)?.preview)
+    }
 
-        let expanded_path = expand_path(&options.selected_path);
-        let game_dir = expanded_path.join(&options.game_id);
+    /// Destino, conteúdo e prévia de `unlock_achievements`; base comum da
+    /// escrita real e da prévia.
+    fn plan_unlock(
+        options: &UnlockOptions,
+        preset: DirectoryDetectionPreset,
+    ) -> Result<UnlockPlan> {
+        let mut entries = Self::process_achievements(options)?;
 
         // Detecta qual arquivo existe e qual cracker usar
-        let (file_path, cracker) =
-            Self::detect_achievement_file(&game_dir, &expanded_path, &options.game_id, preset);
+        let (file_path, cracker) = Self::selected_target(options, preset);
+        Self::ensure_writable(&file_path, cracker)?;
+
+        let mut warnings = Vec::new();
+        let exists = file_path.exists();
+        let existing = if exists {
+            AchievementParser::parse_achievement_file(&file_path, cracker).unwrap_or_else(|e| {
+                warnings.push(format!(
+                    "Current file could not be read and will be replaced: {:#}",
+                    e
+                ));
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
 
-        // Escreve no formato correto
-        AchievementWriter::write_achievements(&file_path, &achievement_entries, cracker)?;
+        // Bloqueadas sem progresso que nem estão no arquivo: nada a rebloquear
+        entries.retain(|entry| {
+            entry.achieved
+                || entry.cur_progress.is_some()
+                || existing.iter().any(|e| e.name == entry.name)
+        });
+
+        let preview = UnlockPreview {
+            options: Self::pinned_options(options, &entries),
+            game_id: options.game_id.clone(),
+            file_path: file_path.to_string_lossy().to_string(),
+            cracker,
+            action: if exists {
+                FileAction::Modify
+            } else {
+                FileAction::Create
+            },
+            changes: Self::rewrite_changes(&existing, &entries),
+            warnings,
+        };
+
+        Ok(UnlockPlan {
+            file_path,
+            cracker,
+            entries,
+            preview,
+        })
+    }
+
+    /// `options` com o horário planejado de cada conquista concluída fixado
+    /// em `unix`, sem nada que gere horários novos.
+    fn pinned_options(options: &UnlockOptions, entries: &[AchievementEntry]) -> UnlockOptions {
+        let mut pinned = options.clone();
+        for ach in &mut pinned.achievements {
+            if let Some(entry) = entries.iter().find(|e| e.name == ach.name && e.achieved) {
+                ach.timestamp = Timestamp {
+                    unix: Some(entry.unlock_time),
+                    ..Timestamp::default()
+                };
+            }
+        }
+        pinned.strategy = None;
+        pinned.order_by_rarity = false;
+        pinned
+    }
+
+    /// Mudanças de gravar `entries` sobre `existing`. Os writers editam o
+    /// arquivo no lugar, então conquistas fora de `entries` ficam como estão.
+    fn rewrite_changes(
+        existing: &[AchievementEntry],
+        entries: &[AchievementEntry],
+    ) -> Vec<AchievementChange> {
+        let written = entries.iter().map(|entry| {
+            let before = existing.iter().find(|e| e.name == entry.name).cloned();
+            AchievementChange {
+                name: entry.name.clone(),
+                kind: Self::change_kind(before.as_ref(), entry),
+                before,
+                after: entry.clone(),
+            }
+        });
+
+        let untouched = existing
+            .iter()
+            .filter(|before| !entries.iter().any(|entry| entry.name == before.name))
+            .map(|before| AchievementChange {
+                name: before.name.clone(),
+                kind: AchievementChangeKind::Unchanged,
+                before: Some(before.clone()),
+                after: before.clone(),
+            });
+
+        written.chain(untouched).collect()
+    }
+
+    /// Aplica só as conquistas informadas sobre o arquivo atual.
+    ///
+    /// Ao contrário de [`Self::unlock_achievements_with_preset`], as que já
+    /// estavam desbloqueadas mantêm o `unlock_time` original. O arquivo só é
+    /// regravado quando algo muda.
+    pub fn apply_changes_with_preset(
+        options: &UnlockOptions,
+        preset: DirectoryDetectionPreset,
+    ) -> Result<ChangeReport> {
+        let (file_path, cracker) = Self::selected_target(options, preset);
+        let fallback_times = Self::fallback_unlock_times(options)?;
+        Self::apply_changes_to_file(options, &file_path, cracker, &fallback_times)
+    }
+
+    /// Arquivo e formato de `options.selected_path` para o jogo.
+    pub fn selected_target(
+        options: &UnlockOptions,
+        preset: DirectoryDetectionPreset,
+    ) -> (PathBuf, Cracker) {
+        let game_dir = expand_path(&options.selected_path).join(&options.game_id);
+        Self::detect_achievement_file(&game_dir, &options.game_id, preset)
+    }
+
+    /// Aplica o mesmo delta a cada arquivo, cada um no próprio formato.
+    ///
+    /// Os horários gerados (modo atual/aleatório/custom) são calculados uma
+    /// única vez, para que todos os emuladores vejam o mesmo estado. Falhas em
+    /// um destino não interrompem os demais.
+    pub fn mirror_changes(
+        options: &UnlockOptions,
+        targets: &[(Cracker, PathBuf)],
+    ) -> Result<Vec<MirrorTargetResult>> {
+        let fallback_times = Self::fallback_unlock_times(options)?;
+
+        Ok(targets
+            .iter()
+            .map(|(cracker, file_path)| {
+                let result =
+                    Self::apply_changes_to_file(options, file_path, *cracker, &fallback_times);
+                if let Err(e) = &result {
+                    log::warn!(
+                        "Failed to mirror changes to {}: {:#}",
+                        file_path.display(),
+                        e
+                    );
+                }
+
+                MirrorTargetResult {
+                    file_path: file_path.to_string_lossy().to_string(),
+                    cracker: *cracker,
+                    error: result.as_ref().err().map(|e| format!("{:#}", e)),
+                    report: result.ok(),
+                }
+            })
+            .collect())
+    }
+
+    fn apply_changes_to_file(
+        options: &UnlockOptions,
+        file_path: &Path,
+        cracker: Cracker,
+        fallback_times: &[i64],
+    ) -> Result<ChangeReport> {
+        Self::ensure_writable(file_path, cracker)?;
+
+        // Sem conseguir ler o arquivo atual não há como preservar o resto
+        let existing = if file_path.exists() {
+            AchievementParser::parse_achievement_file(file_path, cracker).with_context(|| {
+                format!(
+                    "Refusing to apply changes over unreadable file: {}",
+                    file_path.display()
+                )
+            })?
+        } else {
+            Vec::new()
+        };
+
+        let (merged, changes) = Self::merge_changes_at(existing, options, fallback_times);
+        let mut report = ChangeReport {
+            game_id: options.game_id.clone(),
+            file_path: file_path.to_string_lossy().to_string(),
+            cracker,
+            changes,
+            written: false,
+        };
+
+        let changed = report.changed().count();
+        if changed > 0 {
+            AchievementWriter::write_achievements(file_path, &merged, cracker)?;
+            report.written = true;
+        }
 
         log::info!(
-            "Achievements unlocked for game {} at {} (format: {:?})",
+            "Applied {} achievement change(s) for game {} at {} (format: {:?})",
+            changed,
             options.game_id,
             file_path.display(),
             cracker
         );
 
+        Ok(report)
+    }
+
+    /// Falha antes de planejar qualquer escrita em formatos somente leitura.
+    fn ensure_writable(file_path: &Path, cracker: Cracker) -> Result<()> {
+        let format = formats::format_for(cracker);
+        if !format.is_writable() {
+            anyhow::bail!(
+                "{} files are read-only: {}",
+                format.display_name(),
+                file_path.display()
+            );
+        }
         Ok(())
     }
 
+    /// Horário a usar em cada conquista de `options` que ainda não estava
+    /// desbloqueada, na mesma ordem.
+    ///
+    /// Horários informados na própria conquista são mantidos. Os demais vêm
+    /// da `strategy` (gerados juntos) ou do `mode` global; com
+    /// `order_by_rarity`, as mais raras recebem os horários mais tardios.
+    fn fallback_unlock_times(options: &UnlockOptions) -> Result<Vec<i64>> {
+        let mut times: Vec<i64> = options
+            .achievements
+            .iter()
+            .map(|ach| {
+                if Self::has_timestamp(ach) {
+                    AchievementWriter::timestamp_to_unix(&ach.timestamp)
+                        .with_context(|| format!("Invalid timestamp for {}", ach.name))
+                } else {
+                    Ok(0)
+                }
+            })
+            .collect::<Result<_>>()?;
+        let pending: Vec<usize> = options
+            .achievements
+            .iter()
+            .enumerate()
+            .filter(|(_, ach)| ach.completed && !Self::has_timestamp(ach))
+            .map(|(index, _)| index)
+            .collect();
+
+        let generated = match &options.strategy {
+            Some(strategy) => schedule::plan_unlock_times(
+                pending.len(),
+                strategy,
+                chrono::Local::now().timestamp(),
+                &mut rand::thread_rng(),
+            )?,
+            None => {
+                let mut generated: Vec<i64> = pending
+                    .iter()
+                    .map(|_| {
+                        Self::get_global_timestamp(
+                            &options.mode,
+                            options.custom_timestamp.as_ref(),
+                            &options.time_format,
+                        )
+                    })
+                    .collect::<Result<_>>()?;
+                if options.order_by_rarity {
+                    generated.sort_unstable();
+                }
+                generated
+            }
+        };
+
+        let order: Vec<usize> = if options.order_by_rarity {
+            let names: Vec<&str> = pending
+                .iter()
+                .map(|index| options.achievements[*index].name.as_str())
+                .collect();
+            schedule::rarity_order(&names, &options.rarity)
+        } else {
+            (0..pending.len()).collect()
+        };
+
+        for (time, position) in generated.into_iter().zip(order) {
+            times[pending[position]] = time;
+        }
+        Ok(times)
+    }
+
+    /// Aplica as conquistas pedidas sobre as entradas existentes, devolvendo a
+    /// lista completa a gravar e o que mudou em cada conquista pedida.
+    pub fn merge_changes(
+        existing: Vec<AchievementEntry>,
+        options: &UnlockOptions,
+    ) -> Result<(Vec<AchievementEntry>, Vec<AchievementChange>)> {
+        let fallback_times = Self::fallback_unlock_times(options)?;
+        Ok(Self::merge_changes_at(existing, options, &fallback_times))
+    }
+
+    fn merge_changes_at(
+        existing: Vec<AchievementEntry>,
+        options: &UnlockOptions,
+        fallback_times: &[i64],
+    ) -> (Vec<AchievementEntry>, Vec<AchievementChange>) {
+        let mut merged = existing;
+        let mut changes = Vec::with_capacity(options.achievements.len());
+
+        for (ach, fallback_time) in options.achievements.iter().zip(fallback_times) {
+            let before = merged.iter().find(|entry| entry.name == ach.name).cloned();
+            let after = Self::apply_change(before.as_ref(), ach, *fallback_time);
+            changes.push(Self::upsert(&mut merged, before, after));
+        }
+
+        (merged, changes)
+    }
+
+    /// Como [`Self::merge_changes`], para entradas já prontas (ex.: lidas de
+    /// outro formato). Progresso ausente mantém o valor existente.
+    pub fn merge_entries(
+        existing: Vec<AchievementEntry>,
+        entries: &[AchievementEntry],
+    ) -> (Vec<AchievementEntry>, Vec<AchievementChange>) {
+        let mut merged = existing;
+        let mut changes = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            let before = merged.iter().find(|e| e.name == entry.name).cloned();
+            let after = AchievementEntry {
+                cur_progress: entry
+                    .cur_progress
+                    .or_else(|| before.as_ref().and_then(|e| e.cur_progress)),
+                max_progress: entry
+                    .max_progress
+                    .or_else(|| before.as_ref().and_then(|e| e.max_progress)),
+                ..entry.clone()
+            };
+            changes.push(Self::upsert(&mut merged, before, after));
+        }
+
+        (merged, changes)
+    }
+
+    /// Grava `after` em `merged` se algo mudou e descreve a mudança.
+    fn upsert(
+        merged: &mut Vec<AchievementEntry>,
+        before: Option<AchievementEntry>,
+        after: AchievementEntry,
+    ) -> AchievementChange {
+        let kind = Self::change_kind(before.as_ref(), &after);
+
+        if kind != AchievementChangeKind::Unchanged {
+            match merged.iter_mut().find(|entry| entry.name == after.name) {
+                Some(entry) => *entry = after.clone(),
+                None => merged.push(after.clone()),
+            }
+        }
+
+        AchievementChange {
+            name: after.name.clone(),
+            kind,
+            before,
+            after,
+        }
+    }
+
+    fn apply_change(
+        before: Option<&AchievementEntry>,
+        ach: &AchievementToUnlock,
+        fallback_time: i64,
+    ) -> AchievementEntry {
+        let max_progress = ach
+            .max_progress
+            .or_else(|| before.and_then(|entry| entry.max_progress));
+
+        match before {
+            Some(previous) if ach.completed && previous.achieved => {
+                // Timestamps da UI têm precisão de minuto: no mesmo minuto,
+                // mantém o horário exato já gravado
+                let unlock_time = if Self::has_timestamp(ach) {
+                    if fallback_time.div_euclid(60) == previous.unlock_time.div_euclid(60) {
+                        previous.unlock_time
+                    } else {
+                        fallback_time
+                    }
+                } else {
+                    previous.unlock_time
+                };
+
+                AchievementEntry {
+                    name: previous.name.clone(),
+                    achieved: true,
+                    unlock_time,
+                    cur_progress: ach.progress.or(previous.cur_progress),
+                    max_progress,
+                }
+            }
+            _ if ach.completed => AchievementEntry {
+                name: ach.name.clone(),
+                achieved: true,
+                unlock_time: fallback_time,
+                cur_progress: ach.progress.or(max_progress),
+                max_progress,
+            },
+            _ => AchievementEntry {
+                name: ach.name.clone(),
+                achieved: false,
+                unlock_time: 0,
+                // Ao rebloquear, o progresso cheio volta a zero
+                cur_progress: ach.progress.or_else(|| {
+                    before.and_then(|entry| {
+                        entry
+                            .cur_progress
+                            .map(|cur| if entry.achieved { 0 } else { cur })
+                    })
+                }),
+                max_progress,
+            },
+        }
+    }
+
+    fn change_kind(
+        before: Option<&AchievementEntry>,
+        after: &AchievementEntry,
+    ) -> AchievementChangeKind {
+        let was_achieved = before.is_some_and(|entry| entry.achieved);
+        let progress_changed = after.cur_progress.is_some()
+            && before.map(|entry| (entry.cur_progress, entry.max_progress))
+                != Some((after.cur_progress, after.max_progress));
+
+        match (was_achieved, after.achieved) {
+            (false, true) => AchievementChangeKind::Unlocked,
+            (true, false) => AchievementChangeKind::Relocked,
+            (true, true) if before.is_some_and(|entry| entry.unlock_time != after.unlock_time) => {
+                AchievementChangeKind::TimeChanged
+            }
+            _ if progress_changed => AchievementChangeKind::ProgressChanged,
+            _ => AchievementChangeKind::Unchanged,
+        }
+    }
+
     /// Detecta o arquivo de conquista existente no diretório do jogo.
     ///
     /// Tenta encontrar o arquivo existente e determinar o cracker.
-    /// Se não encontrar, assume Goldberg JSON como padrão.
+    /// Se não encontrar, usa o arquivo padrão do cracker detectado pelo path do jogo.
     fn detect_achievement_file(
         game_dir: &Path,
-        base_path: &Path,
         game_id: &str,
         preset: DirectoryDetectionPreset,
     ) -> (std::path::PathBuf, Cracker) {
@@ -588,45 +713,292 @@ impl AchievementUnlocker {
         }
 
         // Tenta encontrar arquivo existente
-        let candidates = [
-            ("achievements.ini", Cracker::Codex),
-            ("Achievements.ini", Cracker::Codex),
-            ("achievements.json", Cracker::Goldberg),
-            ("stats/Achievements.ini", Cracker::OnlineFix),
-            ("Achievements.ini", Cracker::OnlineFix),
-            ("SteamEmu/UserStats/achiev.ini", Cracker::Skidrow),
-            ("stats/CreamAPI.Achievements.cfg", Cracker::CreamApi),
-            ("User/Achievements.ini", Cracker::SmartSteamEmu),
-            ("achievement", Cracker::Razor1911),
-        ];
-
-        for (filename, cracker) in &candidates {
-            let path = game_dir.join(filename);
-            if path.exists() {
-                return (path, *cracker);
-            }
+        if let Some(existing) =
+            AchievementParser::find_achievement_file_in_game_dir(game_dir, game_id)
+        {
+            return existing;
         }
 
-        // Tenta detectar pelo path base
-        let base_str = base_path.to_string_lossy().to_lowercase();
-        let detected_cracker = detect_cracker_from_path(&base_str);
-
-        // Define o nome do arquivo baseado no cracker
-        let filename = match detected_cracker {
-            Cracker::Goldberg | Cracker::Empress => "achievements.json",
-            Cracker::Skidrow => "SteamEmu/UserStats/achiev.ini",
-            Cracker::CreamApi => "stats/CreamAPI.Achievements.cfg",
-            Cracker::SmartSteamEmu => "User/Achievements.ini",
-            Cracker::Razor1911 => "achievement",
-            _ => "achievements.ini",
-        };
+        // Tenta detectar pelo path do jogo e usa o arquivo padrão do formato
+        let detected_cracker = detect_cracker_from_path(&game_dir.to_string_lossy());
+        let filename = formats::format_for(detected_cracker)
+            .candidate_files()
+            .first()
+            .map(|candidate| candidate.replace("<objectId>", game_id))
+            .unwrap_or_else(|| "achievements.ini".to_string());
 
         (game_dir.join(filename), detected_cracker)
     }
 }
 
-/// Converte u32 para string hexadecimal little-endian (8 chars).
-fn u32_to_hex_le(value: u32) -> String {
-    let swapped = value.swap_bytes();
-    format!("{:08X}", swapped)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+    use std::collections::HashMap;
+
+    fn entry(name: &str, achieved: bool, unlock_time: i64) -> AchievementEntry {
+        AchievementEntry {
+            name: name.to_string(),
+            achieved,
+            unlock_time,
+            cur_progress: None,
+            max_progress: None,
+        }
+    }
+
+    fn request(name: &str, completed: bool) -> AchievementToUnlock {
+        AchievementToUnlock {
+            name: name.to_string(),
+            completed,
+            timestamp: Timestamp::default(),
+            progress: None,
+            max_progress: None,
+        }
+    }
+
+    #[test]
+    fn merge_changes_only_touches_requested_achievements() {
+        let existing = vec![
+            entry("ACH_KEEP", true, 1_600_000_000),
+            entry("ACH_RELOCK", true, 1_600_000_100),
+            entry("ACH_LOCKED", false, 0),
+            entry("ACH_UNTOUCHED", true, 1_600_000_200),
+        ];
+        let options = UnlockOptions {
+            game_id: "480".to_string(),
+            selected_path: String::new(),
+            achievements: vec![
+                request("ACH_KEEP", true),
+                request("ACH_RELOCK", false),
+                request("ACH_LOCKED", false),
+                request("ACH_NEW", true),
+            ],
+            mode: UnlockMode::Current,
+            custom_timestamp: None,
+            time_format: TimeFormat::TwentyFourHour,
+            strategy: None,
+            order_by_rarity: false,
+            rarity: HashMap::new(),
+        };
+
+        let (merged, changes) =
+            AchievementUnlocker::merge_changes(existing, &options).expect("merge");
+
+        let kinds: Vec<(&str, AchievementChangeKind)> = changes
+            .iter()
+            .map(|change| (change.name.as_str(), change.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("ACH_KEEP", AchievementChangeKind::Unchanged),
+                ("ACH_RELOCK", AchievementChangeKind::Relocked),
+                ("ACH_LOCKED", AchievementChangeKind::Unchanged),
+                ("ACH_NEW", AchievementChangeKind::Unlocked),
+            ]
+        );
+
+        let states: Vec<(&str, bool)> = merged
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.achieved))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("ACH_KEEP", true),
+                ("ACH_RELOCK", false),
+                ("ACH_LOCKED", false),
+                ("ACH_UNTOUCHED", true),
+                ("ACH_NEW", true),
+            ]
+        );
+        assert_eq!(merged[0].unlock_time, 1_600_000_000);
+        assert_eq!(merged[1].unlock_time, 0);
+        assert_eq!(merged[3].unlock_time, 1_600_000_200);
+        assert!(merged[4].unlock_time > 0);
+    }
+
+    #[test]
+    fn unlock_relocks_requested_achievements_in_ini_and_json() {
+        let base = unique_temp_dir("relock");
+
+        for preset in [
+            DirectoryDetectionPreset::CodexIni,
+            DirectoryDetectionPreset::GoldbergJson,
+        ] {
+            let mut options = UnlockOptions {
+                game_id: "480".to_string(),
+                selected_path: base
+                    .join(format!("{preset:?}"))
+                    .to_string_lossy()
+                    .to_string(),
+                achievements: vec![request("ACH_ONE", true), request("ACH_TWO", true)],
+                mode: UnlockMode::Current,
+                custom_timestamp: None,
+                time_format: TimeFormat::TwentyFourHour,
+                strategy: None,
+                order_by_rarity: false,
+                rarity: HashMap::new(),
+            };
+            AchievementUnlocker::unlock_achievements_with_preset(&options, preset).expect("unlock");
+
+            // Como a UI envia: todas as conquistas, ACH_TWO desmarcada
+            options.achievements = vec![
+                request("ACH_ONE", true),
+                request("ACH_TWO", false),
+                request("ACH_NEVER", false),
+            ];
+            AchievementUnlocker::unlock_achievements_with_preset(&options, preset).expect("relock");
+
+            let (file_path, cracker) = AchievementUnlocker::selected_target(&options, preset);
+            let written =
+                AchievementParser::parse_achievement_file(&file_path, cracker).expect("parse");
+            let states: Vec<(&str, bool)> = written
+                .iter()
+                .map(|entry| (entry.name.as_str(), entry.achieved))
+                .collect();
+            assert_eq!(
+                states,
+                vec![("ACH_ONE", true), ("ACH_TWO", false)],
+                "{preset:?}"
+            );
+            assert_eq!(written[1].unlock_time, 0, "{preset:?}");
+        }
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn replacing_relocks_unlocks_missing_from_the_new_list() {
+        let base = unique_temp_dir("replace");
+        std::fs::create_dir_all(&base).expect("create dir");
+
+        for (cracker, file_name) in [
+            (Cracker::Codex, "achievements.ini"),
+            (Cracker::Goldberg, "achievements.json"),
+        ] {
+            let file_path = base.join(file_name);
+            // Estado atual: ACH_EXTRA foi desbloqueada depois do backup
+            let current = vec![
+                entry("ACH_ONE", true, 1_700_000_100),
+                entry("ACH_EXTRA", true, 1_700_000_200),
+            ];
+            AchievementWriter::write_achievements(&file_path, &current, cracker).expect("write");
+
+            let backup = vec![entry("ACH_ONE", true, 1_600_000_000)];
+            AchievementWriter::replace_achievements(&file_path, &backup, cracker).expect("replace");
+
+            let mut restored =
+                AchievementParser::parse_achievement_file(&file_path, cracker).expect("parse");
+            restored.sort_by(|a, b| a.name.cmp(&b.name));
+            assert_eq!(
+                restored,
+                vec![
+                    entry("ACH_EXTRA", false, 0),
+                    entry("ACH_ONE", true, 1_600_000_000),
+                ],
+                "{cracker:?}"
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn read_only_formats_are_rejected_without_reporting_a_write() {
+        let base = unique_temp_dir("read_only");
+        std::fs::create_dir_all(&base).expect("create dir");
+        let cache_file = base.join("480.json");
+        let content = r#"[{"strID":"ACH_ONE","bAchieved":false,"rtUnlocked":0}]"#;
+        std::fs::write(&cache_file, content).expect("write");
+
+        let options = UnlockOptions {
+            game_id: "480".to_string(),
+            selected_path: base.to_string_lossy().to_string(),
+            achievements: vec![request("ACH_ONE", true)],
+            mode: UnlockMode::Current,
+            custom_timestamp: None,
+            time_format: TimeFormat::TwentyFourHour,
+            strategy: None,
+            order_by_rarity: false,
+            rarity: HashMap::new(),
+        };
+        let results = AchievementUnlocker::mirror_changes(
+            &options,
+            &[(Cracker::SteamCache, cache_file.clone())],
+        )
+        .expect("mirror");
+        assert!(results[0].report.is_none());
+        assert!(results[0]
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("read-only")));
+        assert_eq!(std::fs::read_to_string(&cache_file).expect("read"), content);
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn preview_matches_the_real_write() {
+        let base = unique_temp_dir("preview");
+        let mut options = UnlockOptions {
+            game_id: "480".to_string(),
+            selected_path: base.to_string_lossy().to_string(),
+            achievements: vec![request("ACH_ONE", true), request("ACH_TWO", true)],
+            mode: UnlockMode::Random,
+            custom_timestamp: None,
+            time_format: TimeFormat::TwentyFourHour,
+            strategy: None,
+            order_by_rarity: false,
+            rarity: HashMap::new(),
+        };
+        let preset = DirectoryDetectionPreset::GoldbergJson;
+
+        let created =
+            AchievementUnlocker::preview_unlock_with_preset(&options, preset).expect("preview");
+        assert_eq!(created.action, FileAction::Create);
+        assert!(!Path::new(&created.file_path).exists());
+
+        // Horários aleatórios: só as opções fixadas da prévia gravam os mesmos
+        AchievementUnlocker::unlock_achievements_with_preset(&created.options, preset)
+            .expect("unlock");
+        let written =
+            AchievementParser::parse_achievement_file(&created.file_path, created.cracker)
+                .expect("parse");
+        let previewed: Vec<AchievementEntry> = created
+            .changes
+            .iter()
+            .map(|change| change.after.clone())
+            .collect();
+        assert_eq!(written, previewed);
+
+        // Sem ACH_TWO no pedido, ela fica como está; ACH_ONE é rebloqueada
+        options.achievements = vec![request("ACH_ONE", false)];
+        let modified =
+            AchievementUnlocker::preview_unlock_with_preset(&options, preset).expect("preview");
+        assert_eq!(modified.action, FileAction::Modify);
+        let kinds: Vec<AchievementChangeKind> =
+            modified.changes.iter().map(|change| change.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AchievementChangeKind::Relocked,
+                AchievementChangeKind::Unchanged
+            ]
+        );
+
+        AchievementUnlocker::unlock_achievements_with_preset(&options, preset).expect("unlock");
+        let written =
+            AchievementParser::parse_achievement_file(&modified.file_path, modified.cracker)
+                .expect("parse");
+        let previewed: Vec<AchievementEntry> = modified
+            .changes
+            .iter()
+            .map(|change| change.after.clone())
+            .collect();
+        assert_eq!(written, previewed);
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
 }
@@ -0,0 +1,200 @@
+//! Horários realistas para desbloqueios em massa.
+//!
+//! O modo aleatório sorteia um segundo qualquer do último ano para cada
+//! conquista, o que gera desbloqueios às 4h e fora de qualquer ordem. Aqui os
+//! horários de um lote são gerados juntos, em ordem crescente, a partir de uma
+//! [`TimestampStrategy`].
+
+use crate::models::TimestampStrategy;
+use anyhow::{bail, Result};
+use chrono::{Duration, Local, NaiveDate, TimeZone, Timelike};
+use rand::Rng;
+use std::collections::HashMap;
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+/// Conquistas por sessão simulada, em média.
+const ACHIEVEMENTS_PER_SESSION: usize = 3;
+/// Duração média de uma sessão ao derivar do tempo de jogo.
+const PLAYTIME_SESSION_MINUTES: u64 = 120;
+
+/// Gera `count` horários em ordem crescente, nunca depois de `now`.
+pub fn plan_unlock_times<R: Rng>(
+    count: usize,
+    strategy: &TimestampStrategy,
+    now: i64,
+    rng: &mut R,
+) -> Result<Vec<i64>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut times = match strategy {
+        TimestampStrategy::Spread { start, end } => {
+            let (start, end) = checked_range(*start, *end, now)?;
+            (0..count).map(|_| rng.gen_range(start..=end)).collect()
+        }
+        TimestampStrategy::Sessions {
+            start,
+            end,
+            session_hours,
+            session_minutes,
+        } => {
+            let (start, end) = checked_range(*start, *end, now)?;
+            if session_hours.is_empty() {
+                bail!("At least one session hour is required");
+            }
+            if let Some(hour) = session_hours.iter().find(|hour| **hour > 23) {
+                bail!("Invalid session hour: {}", hour);
+            }
+            session_times(count, start, end, session_hours, *session_minutes, rng)
+        }
+        TimestampStrategy::Playtime {
+            last_played,
+            playtime_minutes,
+        } => {
+            let end = last_played.filter(|time| *time > 0).unwrap_or(now).min(now);
+            let minutes = playtime_minutes
+                .filter(|minutes| *minutes > 0)
+                .unwrap_or(count as u64 * 30);
+            let sessions = minutes.div_ceil(PLAYTIME_SESSION_MINUTES).max(1);
+            let session_minutes = (minutes / sessions).clamp(15, 600) as u32;
+            // Uma sessão a cada dois dias até a última vez jogada
+            let start = end - sessions as i64 * 2 * DAY_SECS;
+
+            let last_hour = Local
+                .timestamp_opt(end, 0)
+                .earliest()
+                .map(|time| time.hour())
+                .unwrap_or(20);
+            let hours = [(last_hour + 23) % 24, last_hour];
+            session_times(count, start, end, &hours, session_minutes, rng)
+        }
+    };
+
+    times.sort_unstable();
+    Ok(times)
+}
+
+/// Ordem de desbloqueio: das mais comuns para as mais raras. Conquistas sem
+/// porcentagem conhecida ficam no início, na ordem original.
+pub fn rarity_order(names: &[&str], rarity: &HashMap<String, f64>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..names.len()).collect();
+    order.sort_by(|a, b| {
+        let percent = |index: &usize| rarity.get(names[*index]).copied().unwrap_or(f64::MAX);
+        percent(b).total_cmp(&percent(a))
+    });
+    order
+}
+
+fn checked_range(start: i64, end: i64, now: i64) -> Result<(i64, i64)> {
+    let end = end.min(now);
+    if start > end {
+        bail!("Invalid date range: start is after end (or in the future)");
+    }
+    Ok((start, end))
+}
+
+/// Sorteia sessões em dias do intervalo, começando em um dos `hours` locais, e
+/// distribui as conquistas dentro delas.
+fn session_times<R: Rng>(
+    count: usize,
+    start: i64,
+    end: i64,
+    hours: &[u32],
+    session_minutes: u32,
+    rng: &mut R,
+) -> Vec<i64> {
+    let local_date = |time: i64| {
+        Local
+            .timestamp_opt(time, 0)
+            .earliest()
+            .map(|time| time.date_naive())
+    };
+    let (Some(first_day), Some(last_day)) = (local_date(start), local_date(end)) else {
+        return vec![end; count];
+    };
+    let days = (last_day - first_day).num_days() + 1;
+    let wanted = count.div_ceil(ACHIEVEMENTS_PER_SESSION);
+
+    let mut sessions = Vec::with_capacity(wanted);
+    for _ in 0..wanted * 8 {
+        if sessions.len() == wanted {
+            break;
+        }
+        let day = first_day + Duration::days(rng.gen_range(0..days));
+        let hour = hours[rng.gen_range(0..hours.len())];
+        let Some(session) = local_time(day, hour, rng.gen_range(0..60)) else {
+            continue;
+        };
+        if (start..=end).contains(&session) {
+            sessions.push(session);
+        }
+    }
+    // Intervalo curto demais para os horários pedidos: usa o próprio intervalo
+    if sessions.is_empty() {
+        sessions.push(start);
+    }
+
+    let session_secs = (session_minutes.max(1) as i64) * 60;
+    (0..count)
+        .map(|_| {
+            let session = sessions[rng.gen_range(0..sessions.len())];
+            (session + rng.gen_range(0..session_secs)).min(end)
+        })
+        .collect()
+}
+
+/// Horário local; `None` em horários que não existem (lacuna do horário de verão).
+fn local_time(day: NaiveDate, hour: u32, minute: u32) -> Option<i64> {
+    let naive = day.and_hms_opt(hour, minute, 0)?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sessions_stay_in_range_and_hours_and_rarest_come_last() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let now = Local::now().timestamp();
+        let start = now - 30 * DAY_SECS;
+        let strategy = TimestampStrategy::Sessions {
+            start,
+            end: now,
+            session_hours: vec![19],
+            session_minutes: 90,
+        };
+
+        let times = plan_unlock_times(12, &strategy, now, &mut rng).expect("plan");
+        assert_eq!(times.len(), 12);
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+        for time in &times {
+            assert!((start..=now).contains(time));
+            let hour = Local.timestamp_opt(*time, 0).unwrap().hour();
+            // Sessão das 19h com 90 minutos termina até as 20h59 (mais 1h de folga no DST)
+            assert!((19..=21).contains(&hour), "hour {hour}");
+        }
+
+        let invalid = TimestampStrategy::Spread {
+            start: now,
+            end: now - DAY_SECS,
+        };
+        assert!(plan_unlock_times(1, &invalid, now, &mut rng).is_err());
+
+        let rarity = HashMap::from([
+            ("RARE".to_string(), 1.5),
+            ("COMMON".to_string(), 80.0),
+            ("MID".to_string(), 20.0),
+        ]);
+        assert_eq!(
+            rarity_order(&["RARE", "UNKNOWN", "COMMON", "MID"], &rarity),
+            vec![1, 2, 3, 0]
+        );
+    }
+}
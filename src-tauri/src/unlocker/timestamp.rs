@@ -0,0 +1,225 @@
+//! Conversão e validação do [`Timestamp`] informado pela UI.
+//!
+//! Aceita, em ordem de prioridade, um unix em segundos, uma data
+//! ISO-8601/RFC3339 ou os campos separados (dia, mês, ano, hora, minuto,
+//! AM/PM). Sem offset explícito, a data é interpretada no fuso IANA de
+//! `time_zone` ou, sem ele, no fuso local. Entradas inválidas e horários que
+//! não existem (lacuna do horário de verão) viram erro em vez de uma data
+//! inventada.
+
+use crate::models::Timestamp;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+
+/// Formatos ISO-8601 sem offset aceitos em `iso`.
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Se o timestamp traz alguma data (unix, ISO ou todos os campos separados).
+pub fn is_set(timestamp: &Timestamp) -> bool {
+    timestamp.unix.is_some()
+        || timestamp
+            .iso
+            .as_deref()
+            .is_some_and(|iso| !iso.trim().is_empty())
+        || (!timestamp.day.is_empty()
+            && !timestamp.month.is_empty()
+            && !timestamp.year.is_empty()
+            && !timestamp.hour.is_empty()
+            && !timestamp.minute.is_empty())
+}
+
+/// Converte para unix em segundos, validando cada campo.
+pub fn to_unix(timestamp: &Timestamp) -> Result<i64> {
+    if let Some(unix) = timestamp.unix {
+        if unix < 0 {
+            bail!("Invalid unix timestamp: {}", unix);
+        }
+        return Ok(unix);
+    }
+
+    if let Some(iso) = timestamp.iso.as_deref().map(str::trim) {
+        if !iso.is_empty() {
+            return parse_iso(iso, timestamp.time_zone.as_deref());
+        }
+    }
+
+    let naive = fields_to_naive(timestamp)?;
+    localize(&naive, timestamp.time_zone.as_deref())
+}
+
+fn parse_iso(iso: &str, time_zone: Option<&str>) -> Result<i64> {
+    // Com offset (`Z`, `-03:00`), o offset vale mais que o fuso informado
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(iso) {
+        return Ok(datetime.timestamp());
+    }
+
+    let naive = NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(iso, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(iso, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+        .ok_or_else(|| anyhow!("Invalid ISO-8601 date: '{}'", iso))?;
+
+    localize(&naive, time_zone)
+}
+
+fn fields_to_naive(timestamp: &Timestamp) -> Result<NaiveDateTime> {
+    let year: i32 = parse_field(&timestamp.year, "year")?;
+    let month: u32 = parse_field(&timestamp.month, "month")?;
+    let day: u32 = parse_field(&timestamp.day, "day")?;
+    let mut hour: u32 = parse_field(&timestamp.hour, "hour")?;
+    let minute: u32 = parse_field(&timestamp.minute, "minute")?;
+
+    // Converte 12h para 24h se necessário
+    match timestamp
+        .ampm
+        .as_deref()
+        .map(str::to_ascii_uppercase)
+        .as_deref()
+    {
+        None => {}
+        Some(period @ ("AM" | "PM")) => {
+            if !(1..=12).contains(&hour) {
+                bail!("Invalid 12-hour clock hour: {}", hour);
+            }
+            hour = match (period, hour) {
+                ("AM", 12) => 0,
+                ("PM", 12) => 12,
+                ("PM", hour) => hour + 12,
+                (_, hour) => hour,
+            };
+        }
+        Some(other) => bail!("Invalid AM/PM value: '{}'", other),
+    }
+
+    let date = NaiveDate::from_ymd_opt(year, month, day)
+        .ok_or_else(|| anyhow!("Invalid date: {:04}-{:02}-{:02}", year, month, day))?;
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)
+        .ok_or_else(|| anyhow!("Invalid time: {:02}:{:02}", hour, minute))?;
+    Ok(date.and_time(time))
+}
+
+fn parse_field<T: std::str::FromStr>(value: &str, name: &str) -> Result<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid {}: '{}'", name, value))
+}
+
+/// Interpreta a data no fuso IANA informado ou no local.
+///
+/// Horários repetidos no fim do horário de verão usam a primeira ocorrência.
+fn localize(naive: &NaiveDateTime, time_zone: Option<&str>) -> Result<i64> {
+    match time_zone.map(str::trim).filter(|tz| !tz.is_empty()) {
+        Some(name) => {
+            let tz: Tz = name
+                .parse()
+                .map_err(|_| anyhow!("Unknown time zone: '{}'", name))?;
+            resolve(tz.from_local_datetime(naive), naive, name)
+        }
+        None => resolve(
+            chrono::Local.from_local_datetime(naive),
+            naive,
+            "the local time zone",
+        ),
+    }
+}
+
+fn resolve<T: TimeZone>(
+    result: LocalResult<DateTime<T>>,
+    naive: &NaiveDateTime,
+    zone: &str,
+) -> Result<i64> {
+    result
+        .earliest()
+        .map(|datetime| datetime.timestamp())
+        .with_context(|| {
+            format!(
+                "{} does not exist in {} (daylight saving time gap)",
+                naive, zone
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(day: &str, month: &str, year: &str, hour: &str, minute: &str) -> Timestamp {
+        Timestamp {
+            day: day.to_string(),
+            month: month.to_string(),
+            year: year.to_string(),
+            hour: hour.to_string(),
+            minute: minute.to_string(),
+            time_zone: Some("America/Sao_Paulo".to_string()),
+            ..Timestamp::default()
+        }
+    }
+
+    #[test]
+    fn converts_and_validates_every_input_form() {
+        let iso = |iso: &str, tz: Option<&str>| Timestamp {
+            iso: Some(iso.to_string()),
+            time_zone: tz.map(str::to_string),
+            ..Timestamp::default()
+        };
+
+        // 2024-05-01 20:30 em São Paulo (UTC-3) = 23:30 UTC
+        let expected = 1_714_606_200;
+        assert_eq!(
+            to_unix(&fields("01", "05", "2024", "20", "30")).unwrap(),
+            expected
+        );
+        assert_eq!(
+            to_unix(&iso("2024-05-01T20:30", Some("America/Sao_Paulo"))).unwrap(),
+            expected
+        );
+        assert_eq!(
+            to_unix(&iso("2024-05-01T23:30:00Z", None)).unwrap(),
+            expected
+        );
+        let pm = Timestamp {
+            ampm: Some("PM".to_string()),
+            ..fields("01", "05", "2024", "08", "30")
+        };
+        assert_eq!(to_unix(&pm).unwrap(), expected);
+        let unix = Timestamp {
+            unix: Some(expected),
+            ..Timestamp::default()
+        };
+        assert_eq!(to_unix(&unix).unwrap(), expected);
+
+        // Lacuna do horário de verão em Nova York: 2024-03-10 02:30 não existe
+        let gap = iso("2024-03-10T02:30", Some("America/New_York"));
+        assert!(to_unix(&gap).unwrap_err().to_string().contains("daylight"));
+
+        for invalid in [
+            fields("31", "02", "2024", "10", "00"),
+            fields("01", "13", "2024", "10", "00"),
+            fields("01", "05", "2024", "24", "00"),
+            fields("01", "05", "abc", "10", "00"),
+            Timestamp {
+                ampm: Some("PM".to_string()),
+                ..fields("01", "05", "2024", "13", "00")
+            },
+            Timestamp {
+                time_zone: Some("Mars/Olympus".to_string()),
+                ..fields("01", "05", "2024", "10", "00")
+            },
+            iso("yesterday", None),
+        ] {
+            assert!(to_unix(&invalid).is_err(), "{invalid:?}");
+        }
+        assert!(!is_set(&Timestamp::default()));
+    }
+}
@@ -12,9 +12,19 @@ pub struct CachedGame {
     pub achievements_total: Option<usize>,
     pub rarity: Option<HashMap<String, f64>>,
     pub hidden: Option<Vec<String>>,
+    /// Nome, descrição e ícone por nome de API, para notificações
+    #[serde(default)]
+    pub achievements: Option<HashMap<String, CachedAchievement>>,
     pub last_updated: u64,
 }
 
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct CachedAchievement {
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Default, Clone, Debug)]
 pub struct AppCache {
     pub games: HashMap<String, CachedGame>,
@@ -93,6 +103,7 @@ impl CacheManager {
             achievements_total: None,
             rarity: None,
             hidden: None,
+            achievements: None,
             last_updated: 0,
         });
 
@@ -143,6 +154,22 @@ impl CacheManager {
         Self::save(app_handle, &cache)
     }
 
+    /// Guarda os metadados das conquistas do jogo (schema Hydra/Steam)
+    pub fn update_achievement_metadata(
+        app_handle: &AppHandle,
+        game_id: &str,
+        achievements: HashMap<String, CachedAchievement>,
+    ) -> Result<()> {
+        if !Self::is_cache_enabled(app_handle) || achievements.is_empty() {
+            return Ok(());
+        }
+
+        let mut cache = Self::load(app_handle)?;
+        let entry = cache.games.entry(game_id.to_string()).or_default();
+        entry.achievements = Some(achievements);
+        Self::save(app_handle, &cache)
+    }
+
     pub fn get_game(app_handle: &AppHandle, game_id: &str) -> Option<CachedGame> {
         if !Self::is_cache_enabled(app_handle) {
             return None;
@@ -81,17 +81,27 @@ export interface Timestamp {
   hour: string;
   minute: string;
   ampm?: 'AM' | 'PM';
+  /** ISO-8601/RFC3339; tem prioridade sobre os campos separados */
+  iso?: string;
+  /** Unix em segundos; tem prioridade sobre `iso` */
+  unix?: number;
+  /** Fuso IANA (ex.: America/Sao_Paulo); sem ele, usa o fuso local */
+  timeZone?: string;
 }
 
 export interface AchievementStatus {
   completed: boolean;
   timestamp: Timestamp;
+  progress?: number;
+  maxProgress?: number;
 }
 
 export interface AchievementEntry {
   name: string;
   achieved: boolean;
   unlockTime: number;
+  curProgress?: number | null;
+  maxProgress?: number | null;
 }
 
 export interface GameAchievements {